@@ -1,9 +1,11 @@
 use std::cmp;
 use std::f64;
 
+use bound_obb::OrientedBBox;
 use i_bound::BoundType;
 use i_bound::IBound;
 use i_shape::ShapeType;
+use mat::Mat3x1;
 
 #[derive(Debug, Clone)]
 pub struct AxisAlignedBBox {
@@ -61,27 +63,115 @@ impl AxisAlignedBBox {
                 }
             }
             ShapeType::Box => {
-                assert!(vals.len() == 4);
+                //center followed by either a single half size or half extents per axis
+                assert!(vals.len() == 4 || vals.len() == 6);
+                let h = if vals.len() == 4 {
+                    [vals[3]; 3]
+                } else {
+                    [vals[3], vals[4], vals[5]]
+                };
                 AxisAlignedBBox {
-                    _bound_lower: [vals[0] - vals[3], vals[1] - vals[3], vals[2] - vals[3]],
-                    _bound_upper: [vals[0] + vals[3], vals[1] + vals[3], vals[2] + vals[3]],
+                    _bound_lower: [vals[0] - h[0], vals[1] - h[1], vals[2] - h[2]],
+                    _bound_upper: [vals[0] + h[0], vals[1] + h[1], vals[2] + h[2]],
+                }
+            }
+            ShapeType::Trig => {
+                assert!(vals.len() == 9);
+                let mut lower = [f64::INFINITY; 3];
+                let mut upper = [f64::NEG_INFINITY; 3];
+                for v in vals.chunks(3) {
+                    for i in 0..3 {
+                        lower[i] = lower[i].min(v[i]);
+                        upper[i] = upper[i].max(v[i]);
+                    }
+                }
+                AxisAlignedBBox {
+                    _bound_lower: lower,
+                    _bound_upper: upper,
                 }
             }
             ShapeType::Rect => {
-                assert!(vals.len() == 6);
+                //either lower and upper corners or center followed by two half axes
+                assert!(vals.len() == 6 || vals.len() == 9);
+                if vals.len() == 6 {
+                    return AxisAlignedBBox {
+                        _bound_lower: [vals[0], vals[1], vals[2]],
+                        _bound_upper: [vals[3], vals[4], vals[5]],
+                    };
+                }
+                let mut lower = [0f64; 3];
+                let mut upper = [0f64; 3];
+                for i in 0..3 {
+                    let e = vals[3 + i].abs() + vals[6 + i].abs();
+                    lower[i] = vals[i] - e;
+                    upper[i] = vals[i] + e;
+                }
                 AxisAlignedBBox {
-                    _bound_lower: [vals[0], vals[1], vals[2]],
-                    _bound_upper: [vals[3], vals[4], vals[5]],
+                    _bound_lower: lower,
+                    _bound_upper: upper,
                 }
             }
             ShapeType::Frustum => {
-                unimplemented!();
+                //corners of the near and far faces
+                assert!(vals.len() == 24);
+                let mut lower = [f64::INFINITY; 3];
+                let mut upper = [f64::NEG_INFINITY; 3];
+                for v in vals.chunks(3) {
+                    for i in 0..3 {
+                        lower[i] = lower[i].min(v[i]);
+                        upper[i] = upper[i].max(v[i]);
+                    }
+                }
+                AxisAlignedBBox {
+                    _bound_lower: lower,
+                    _bound_upper: upper,
+                }
             }
             _ => {
                 unimplemented!();
             }
         }
     }
+    ///axis aligned box enclosing an arbitrary bound
+    pub fn init_from_bound(bound: &dyn IBound) -> AxisAlignedBBox {
+        let b = bound.get_bound_data();
+        match bound.get_type() {
+            BoundType::AxisAlignBox => AxisAlignedBBox {
+                _bound_lower: [b[0], b[1], b[2]],
+                _bound_upper: [b[3], b[4], b[5]],
+            },
+            BoundType::Sphere => AxisAlignedBBox::init(ShapeType::Sphere, &b[0..4]),
+            BoundType::OrientedBox => {
+                let o = OrientedBBox::init_from_bound(bound);
+                let mut a = AxisAlignedBBox {
+                    _bound_lower: [0f64; 3],
+                    _bound_upper: [0f64; 3],
+                };
+                for i in 0..3 {
+                    let mut n = [0f64; 3];
+                    n[i] = 1f64;
+                    let (lower, upper) = o.project(&n);
+                    a._bound_lower[i] = lower;
+                    a._bound_upper[i] = upper;
+                }
+                a
+            }
+        }
+    }
+    ///tests whether inner lies within the box, touching faces included
+    pub fn contains(&self, inner: &AxisAlignedBBox) -> bool {
+        (0..3).all(|i| {
+            self._bound_lower[i] <= inner._bound_lower[i]
+                && self._bound_upper[i] >= inner._bound_upper[i]
+        })
+    }
+    ///tests for infinite extents such as bounds of rays and planes
+    pub fn is_unbounded(&self) -> bool {
+        self._bound_lower
+            .iter()
+            .chain(self._bound_upper.iter())
+            .any(|x| !x.is_finite())
+    }
     pub fn get_longest_axis(&self) -> (Axis, f64) {
         let dx = (Axis::X, self._bound_upper[0] - self._bound_lower[0]);
         let dy = (Axis::Y, self._bound_upper[1] - self._bound_lower[1]);
@@ -92,7 +182,7 @@ impl AxisAlignedBBox {
             .max_by(|x, y| {
                 if x.1 < y.1 {
                     cmp::Ordering::Less
-                } else if x.1 < y.1 {
+                } else if x.1 > y.1 {
                     cmp::Ordering::Greater
                 } else {
                     cmp::Ordering::Equal
@@ -101,6 +191,29 @@ impl AxisAlignedBBox {
             .unwrap();
         longest
     }
+    ///returns entry and exit parameters of a ray against the box using the slab method, parameters are clamped to be non-negative
+    pub fn intersect_ray(&self, ori: &Mat3x1<f64>, dir: &Mat3x1<f64>) -> Option<(f64, f64)> {
+        let mut t_enter = 0f64;
+        let mut t_exit = f64::INFINITY;
+        for i in 0..3 {
+            if dir[i] == 0f64 {
+                //parallel to slab
+                if ori[i] < self._bound_lower[i] || ori[i] > self._bound_upper[i] {
+                    return None;
+                }
+            } else {
+                let t0 = (self._bound_lower[i] - ori[i]) / dir[i];
+                let t1 = (self._bound_upper[i] - ori[i]) / dir[i];
+                let (t_near, t_far) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+                t_enter = t_enter.max(t_near);
+                t_exit = t_exit.min(t_far);
+                if t_enter > t_exit {
+                    return None;
+                }
+            }
+        }
+        Some((t_enter, t_exit))
+    }
 }
 
 impl IBound for AxisAlignedBBox {
@@ -125,13 +238,47 @@ impl IBound for AxisAlignedBBox {
                 }
                 return true;
             }
-            _ => {
-                unimplemented!();
+            BoundType::Sphere => {
+                //see BoundSphere for sphere box intersection
+                other.intersect(self)
+            }
+            BoundType::OrientedBox => {
+                //see OrientedBBox for oriented box intersection
+                other.intersect(self)
             }
         }
     }
-    fn get_shortest_separation(&self, _other: &dyn IBound) -> f64 {
-        unimplemented!();
+    fn get_shortest_separation(&self, other: &dyn IBound) -> f64 {
+        match other.get_type() {
+            BoundType::AxisAlignBox => {
+                let b_bounds = other.get_bound_data();
+                let b_lower = &b_bounds[0..3];
+                let b_upper = &b_bounds[3..6];
+
+                //accumulate gaps of non-overlapping axes only, comparisons are well defined
+                //for infinite extents whereas differences of them may not be
+                let mut d = 0f64;
+                for i in 0..3 {
+                    let gap = if b_lower[i] > self._bound_upper[i] {
+                        b_lower[i] - self._bound_upper[i]
+                    } else if self._bound_lower[i] > b_upper[i] {
+                        self._bound_lower[i] - b_upper[i]
+                    } else {
+                        0f64
+                    };
+                    d += gap * gap;
+                }
+                d.sqrt()
+            }
+            BoundType::Sphere => {
+                //see BoundSphere for sphere box separation
+                other.get_shortest_separation(self)
+            }
+            BoundType::OrientedBox => {
+                //see OrientedBBox for oriented box separation
+                other.get_shortest_separation(self)
+            }
+        }
     }
     fn get_bound_data(&self) -> [f64; 32] {
         let mut arr = [0f64; 32];
@@ -147,37 +294,26 @@ impl IBound for AxisAlignedBBox {
         self._bound_lower = [f64::INFINITY; 3];
         self._bound_upper = [f64::NEG_INFINITY; 3];
         for i in bounds {
-            match i.get_type() {
-                BoundType::AxisAlignBox => (),
-                _ => {
-                    unimplemented!();
-                }
-            }
-            let b = i.get_bound_data();
-            let b_lower = &b[0..3];
-            let b_upper = &b[3..6];
+            let b = AxisAlignedBBox::init_from_bound(*i);
             for j in 0..3 {
-                self._bound_lower[j] = self._bound_lower[j].min(b_lower[j]);
-                self._bound_upper[j] = self._bound_upper[j].max(b_upper[j]);
+                self._bound_lower[j] = self._bound_lower[j].min(b._bound_lower[j]);
+                self._bound_upper[j] = self._bound_upper[j].max(b._bound_upper[j]);
             }
         }
     }
     fn get_centroid(&self) -> [f64; 3] {
-        match self.get_type() {
-            BoundType::AxisAlignBox => {
-                let b = self.get_bound_data();
-                let b_lower = &b[0..3];
-                let b_upper = &b[3..6];
-                return [
-                    (b_lower[0] + b_upper[0]) / 2f64,
-                    (b_lower[1] + b_upper[1]) / 2f64,
-                    (b_lower[2] + b_upper[2]) / 2f64,
-                ];
-            }
-            _ => {
-                unimplemented!();
-            }
+        //unbounded axes are centered at whichever finite extent exists, or at zero
+        let mut c = [0f64; 3];
+        for (i, x) in c.iter_mut().enumerate() {
+            let (lower, upper) = (self._bound_lower[i], self._bound_upper[i]);
+            *x = match (lower.is_finite(), upper.is_finite()) {
+                (true, true) => (lower + upper) / 2f64,
+                (true, false) => lower,
+                (false, true) => upper,
+                _ => 0f64,
+            };
         }
+        c
     }
 }
 
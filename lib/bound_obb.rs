@@ -0,0 +1,266 @@
+use std::f64;
+
+use bound::AxisAlignedBBox;
+use i_bound::BoundType;
+use i_bound::IBound;
+use mat::{Mat3, Mat3x1};
+use quat::Quat;
+
+#[derive(Debug, Clone)]
+pub struct OrientedBBox {
+    pub _ori: [f64; 3],
+    ///half of the length of box edges along each of the axes
+    pub _half_extents: [f64; 3],
+    ///unit axes forming a right handed frame
+    pub _axes: [[f64; 3]; 3],
+}
+
+fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(a: &[f64; 3]) -> Option<[f64; 3]> {
+    let m = dot(a, a).sqrt();
+    if m > 0.000001f64 {
+        Some([a[0] / m, a[1] / m, a[2] / m])
+    } else {
+        None
+    }
+}
+
+impl OrientedBBox {
+    ///box rotated by rot from the world axes
+    pub fn init(ori: &[f64], half_extents: &[f64], rot: &Quat<f64>) -> OrientedBBox {
+        assert!(ori.len() == 3);
+        assert!(half_extents.len() == 3);
+        let mut axes = [[0f64; 3]; 3];
+        for (i, a) in axes.iter_mut().enumerate() {
+            let mut e: Mat3x1<f64> = Default::default();
+            e[i] = 1f64;
+            *a = rot.rotate_vector(e)._val;
+        }
+        OrientedBBox {
+            _ori: [ori[0], ori[1], ori[2]],
+            _half_extents: [half_extents[0], half_extents[1], half_extents[2]],
+            _axes: axes,
+        }
+    }
+    ///box of a finite point set with axes along the principal components of the point covariance
+    pub fn init_from_points(points: &[[f64; 3]]) -> OrientedBBox {
+        assert!(!points.is_empty());
+        let n = points.len() as f64;
+        let mut mean = [0f64; 3];
+        for p in points.iter() {
+            for i in 0..3 {
+                mean[i] += p[i] / n;
+            }
+        }
+        let mut cov = Mat3::<f64>::init([0f64; 9], true);
+        for p in points.iter() {
+            for i in 0..3 {
+                for j in 0..3 {
+                    *cov.index_mut(i as u64, j as u64) += (p[i] - mean[i]) * (p[j] - mean[j]) / n;
+                }
+            }
+        }
+        let (_, vectors) = cov.eigen_symmetric();
+        let column = |j: u64| {
+            [
+                vectors.index(0, j),
+                vectors.index(1, j),
+                vectors.index(2, j),
+            ]
+        };
+        //eigenvectors are orthogonal, the third one is recomputed to keep the frame right handed
+        let a0 = normalize(&column(0)).unwrap_or([1f64, 0f64, 0f64]);
+        let a1 = normalize(&column(1)).unwrap_or([0f64, 1f64, 0f64]);
+        let axes = [a0, a1, cross(&a0, &a1)];
+
+        let mut lower = [f64::INFINITY; 3];
+        let mut upper = [f64::NEG_INFINITY; 3];
+        for p in points.iter() {
+            for (i, a) in axes.iter().enumerate() {
+                let d = dot(p, a);
+                lower[i] = lower[i].min(d);
+                upper[i] = upper[i].max(d);
+            }
+        }
+        let mut ori = [0f64; 3];
+        let mut half_extents = [0f64; 3];
+        for (i, a) in axes.iter().enumerate() {
+            let c = (lower[i] + upper[i]) / 2f64;
+            for (j, o) in ori.iter_mut().enumerate() {
+                *o += a[j] * c;
+            }
+            half_extents[i] = (upper[i] - lower[i]) / 2f64;
+        }
+        OrientedBBox {
+            _ori: ori,
+            _half_extents: half_extents,
+            _axes: axes,
+        }
+    }
+    ///oriented box enclosing an arbitrary bound, non-oriented bounds keep the world axes
+    pub fn init_from_bound(bound: &dyn IBound) -> OrientedBBox {
+        let b = bound.get_bound_data();
+        match bound.get_type() {
+            BoundType::OrientedBox => OrientedBBox {
+                _ori: [b[0], b[1], b[2]],
+                _half_extents: [b[3], b[4], b[5]],
+                _axes: [
+                    [b[6], b[7], b[8]],
+                    [b[9], b[10], b[11]],
+                    [b[12], b[13], b[14]],
+                ],
+            },
+            _ => {
+                let a = AxisAlignedBBox::init_from_bound(bound);
+                let mut ori = [0f64; 3];
+                let mut half_extents = [0f64; 3];
+                for i in 0..3 {
+                    half_extents[i] = (a._bound_upper[i] - a._bound_lower[i]) / 2f64;
+                    ori[i] = if half_extents[i].is_finite() {
+                        a._bound_lower[i] + half_extents[i]
+                    } else {
+                        0f64
+                    };
+                }
+                OrientedBBox {
+                    _ori: ori,
+                    _half_extents: half_extents,
+                    _axes: [[1f64, 0f64, 0f64], [0f64, 1f64, 0f64], [0f64, 0f64, 1f64]],
+                }
+            }
+        }
+    }
+    pub fn get_corners(&self) -> Vec<[f64; 3]> {
+        (0..8)
+            .map(|i| {
+                let mut p = self._ori;
+                for (k, a) in self._axes.iter().enumerate() {
+                    let s = if i & (1 << k) != 0 {
+                        self._half_extents[k]
+                    } else {
+                        -self._half_extents[k]
+                    };
+                    for j in 0..3 {
+                        p[j] += a[j] * s;
+                    }
+                }
+                p
+            })
+            .collect()
+    }
+    ///interval covered by the box along unit axis n
+    pub fn project(&self, n: &[f64; 3]) -> (f64, f64) {
+        let c = dot(&self._ori, n);
+        let mut r = 0f64;
+        for (a, h) in self._axes.iter().zip(self._half_extents.iter()) {
+            //skipping perpendicular axes keeps infinite extents from producing nan
+            let d = dot(a, n).abs();
+            if d > 0f64 {
+                r += d * h;
+            }
+        }
+        (c - r, c + r)
+    }
+    pub fn get_closest_point(&self, p: &[f64; 3]) -> [f64; 3] {
+        let d = [
+            p[0] - self._ori[0],
+            p[1] - self._ori[1],
+            p[2] - self._ori[2],
+        ];
+        let mut q = self._ori;
+        for (a, h) in self._axes.iter().zip(self._half_extents.iter()) {
+            let s = dot(&d, a).clamp(-h, *h);
+            for j in 0..3 {
+                q[j] += a[j] * s;
+            }
+        }
+        q
+    }
+    ///largest gap between the boxes over the separating axes, positive if and only if the boxes are apart
+    fn get_axis_gap(&self, other: &OrientedBBox) -> f64 {
+        let mut axes = self._axes.to_vec();
+        axes.extend(other._axes.iter());
+        for a in self._axes.iter() {
+            for b in other._axes.iter() {
+                //parallel edges do not give an axis
+                if let Some(x) = normalize(&cross(a, b)) {
+                    axes.push(x);
+                }
+            }
+        }
+        axes.iter()
+            .map(|n| {
+                let (a_lower, a_upper) = self.project(n);
+                let (b_lower, b_upper) = other.project(n);
+                (b_lower - a_upper).max(a_lower - b_upper)
+            })
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+impl IBound for OrientedBBox {
+    fn get_type(&self) -> BoundType {
+        BoundType::OrientedBox
+    }
+    fn intersect(&self, other: &dyn IBound) -> bool {
+        self.get_shortest_separation(other) <= 0f64
+    }
+    ///exact for spheres, a lower bound of the distance for boxes
+    fn get_shortest_separation(&self, other: &dyn IBound) -> f64 {
+        let b = other.get_bound_data();
+        let d = match other.get_type() {
+            BoundType::Sphere => {
+                if !b[3].is_finite() {
+                    return 0f64;
+                }
+                let c = [b[0], b[1], b[2]];
+                let p = self.get_closest_point(&c);
+                let v = [c[0] - p[0], c[1] - p[1], c[2] - p[2]];
+                dot(&v, &v).sqrt() - b[3]
+            }
+            _ => self.get_axis_gap(&OrientedBBox::init_from_bound(other)),
+        };
+        d.max(0f64)
+    }
+    fn get_bound_data(&self) -> [f64; 32] {
+        let mut arr = [0f64; 32];
+        arr[0..3].copy_from_slice(&self._ori);
+        arr[3..6].copy_from_slice(&self._half_extents);
+        for (i, a) in self._axes.iter().enumerate() {
+            arr[6 + i * 3..9 + i * 3].copy_from_slice(a);
+        }
+        arr
+    }
+    fn get_union(&mut self, bounds: &[&dyn IBound]) {
+        if bounds.is_empty() {
+            //nothing to enclose, keep the current bound
+            return;
+        }
+        let mut points = vec![];
+        for i in bounds {
+            points.extend(OrientedBBox::init_from_bound(*i).get_corners());
+        }
+        if points.iter().flatten().any(|x| !x.is_finite()) {
+            //unbounded union keeps the world axes
+            let mut a = AxisAlignedBBox::default();
+            a.get_union(bounds);
+            *self = OrientedBBox::init_from_bound(&a);
+        } else {
+            *self = OrientedBBox::init_from_points(&points);
+        }
+    }
+    fn get_centroid(&self) -> [f64; 3] {
+        self._ori
+    }
+}
@@ -6,38 +6,288 @@ use i_shape::ShapeType;
 
 #[derive(Debug, Clone)]
 pub struct BoundSphere {
-    //todo
+    pub _ori: [f64; 3],
+    pub _radius: f64,
 }
 
 impl BoundSphere {
-    pub fn init(_shape_type: ShapeType, _vals: &[f64]) -> BoundSphere {
-        BoundSphere {}
+    pub fn init(shape_type: ShapeType, vals: &[f64]) -> BoundSphere {
+        match shape_type {
+            ShapeType::Ray => {
+                assert!(vals.len() == 6);
+                //unbounded in the direction of the ray
+                BoundSphere {
+                    _ori: [vals[0], vals[1], vals[2]],
+                    _radius: f64::INFINITY,
+                }
+            }
+            ShapeType::Point => {
+                assert!(vals.len() == 3);
+                BoundSphere {
+                    _ori: [vals[0], vals[1], vals[2]],
+                    _radius: 0f64,
+                }
+            }
+            ShapeType::Sphere => {
+                assert!(vals.len() == 4);
+                BoundSphere {
+                    _ori: [vals[0], vals[1], vals[2]],
+                    _radius: vals[3],
+                }
+            }
+            ShapeType::Plane => {
+                assert!(vals.len() == 6);
+                BoundSphere {
+                    _ori: [vals[0], vals[1], vals[2]],
+                    _radius: f64::INFINITY,
+                }
+            }
+            ShapeType::Box => {
+                //center followed by either a single half size or half extents per axis
+                assert!(vals.len() == 4 || vals.len() == 6);
+                let h = if vals.len() == 4 {
+                    [vals[3]; 3]
+                } else {
+                    [vals[3], vals[4], vals[5]]
+                };
+                let lower = [vals[0] - h[0], vals[1] - h[1], vals[2] - h[2]];
+                let upper = [vals[0] + h[0], vals[1] + h[1], vals[2] + h[2]];
+                BoundSphere::init_from_points(&box_corners(&lower, &upper))
+            }
+            ShapeType::Rect => {
+                //either lower and upper corners or center followed by two half axes
+                assert!(vals.len() == 6 || vals.len() == 9);
+                if vals.len() == 6 {
+                    let lower = [vals[0], vals[1], vals[2]];
+                    let upper = [vals[3], vals[4], vals[5]];
+                    return BoundSphere::init_from_points(&box_corners(&lower, &upper));
+                }
+                let corners: Vec<[f64; 3]> =
+                    [(-1f64, -1f64), (1f64, -1f64), (1f64, 1f64), (-1f64, 1f64)]
+                        .iter()
+                        .map(|(s, t)| {
+                            [
+                                vals[0] + s * vals[3] + t * vals[6],
+                                vals[1] + s * vals[4] + t * vals[7],
+                                vals[2] + s * vals[5] + t * vals[8],
+                            ]
+                        })
+                        .collect();
+                BoundSphere::init_from_points(&corners)
+            }
+            ShapeType::Line => {
+                assert!(vals.len() == 6);
+                BoundSphere::init_from_points(&[
+                    [vals[0], vals[1], vals[2]],
+                    [vals[3], vals[4], vals[5]],
+                ])
+            }
+            ShapeType::TriPrism => {
+                //base triangle followed by the offset of the top triangle
+                assert!(vals.len() == 12);
+                let mut points: Vec<[f64; 3]> =
+                    vals[0..9].chunks(3).map(|x| [x[0], x[1], x[2]]).collect();
+                for i in 0..3 {
+                    let p = points[i];
+                    points.push([p[0] + vals[9], p[1] + vals[10], p[2] + vals[11]]);
+                }
+                BoundSphere::init_from_points(&points)
+            }
+            ShapeType::OrientedBox => {
+                //center, half extents and axes
+                assert!(vals.len() == 15);
+                BoundSphere {
+                    _ori: [vals[0], vals[1], vals[2]],
+                    _radius: (vals[3] * vals[3] + vals[4] * vals[4] + vals[5] * vals[5]).sqrt(),
+                }
+            }
+            ShapeType::Trig | ShapeType::Frustum | ShapeType::Complex => {
+                //vertices of triangles, corners of the near and far faces of frustums,
+                //custom shapes are taken as a list of points
+                assert!(!vals.is_empty() && vals.chunks_exact(3).remainder().is_empty());
+                let points: Vec<[f64; 3]> = vals.chunks(3).map(|x| [x[0], x[1], x[2]]).collect();
+                BoundSphere::init_from_points(&points)
+            }
+        }
     }
+    ///bounding sphere of a point set using Ritter's algorithm
+    pub fn init_from_points(points: &[[f64; 3]]) -> BoundSphere {
+        assert!(!points.is_empty());
+
+        //pick the point furthest from an arbitrary point, then the point furthest from that
+        let furthest_from = |p: &[f64; 3]| {
+            let mut best = points[0];
+            let mut best_d = -1f64;
+            for i in points.iter() {
+                let d = distance_squared(p, i);
+                if d > best_d {
+                    best_d = d;
+                    best = *i;
+                }
+            }
+            best
+        };
+        let a = furthest_from(&points[0]);
+        let b = furthest_from(&a);
+
+        let mut ori = [
+            (a[0] + b[0]) / 2f64,
+            (a[1] + b[1]) / 2f64,
+            (a[2] + b[2]) / 2f64,
+        ];
+        let mut radius = distance_squared(&a, &b).sqrt() / 2f64;
+
+        //grow sphere to include any outlying points
+        for i in points.iter() {
+            let d = distance_squared(&ori, i).sqrt();
+            if d > radius {
+                let radius_new = (radius + d) / 2f64;
+                let k = (radius_new - radius) / d;
+                for (j, o) in ori.iter_mut().enumerate() {
+                    *o += (i[j] - *o) * k;
+                }
+                radius = radius_new;
+            }
+        }
+        BoundSphere {
+            _ori: ori,
+            _radius: radius,
+        }
+    }
+}
+
+fn distance_squared(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (0..3).map(|i| (a[i] - b[i]) * (a[i] - b[i])).sum()
+}
+
+fn box_corners(lower: &[f64; 3], upper: &[f64; 3]) -> Vec<[f64; 3]> {
+    let mut corners = vec![];
+    for i in 0..8 {
+        corners.push([
+            if i & 1 == 0 { lower[0] } else { upper[0] },
+            if i & 2 == 0 { lower[1] } else { upper[1] },
+            if i & 4 == 0 { lower[2] } else { upper[2] },
+        ]);
+    }
+    corners
+}
+
+///returns (center, radius) of a sphere enclosing the given bound
+fn enclosing_sphere(bound: &dyn IBound) -> ([f64; 3], f64) {
+    let b = bound.get_bound_data();
+    match bound.get_type() {
+        BoundType::Sphere => ([b[0], b[1], b[2]], b[3]),
+        BoundType::AxisAlignBox => {
+            let ori = [
+                (b[0] + b[3]) / 2f64,
+                (b[1] + b[4]) / 2f64,
+                (b[2] + b[5]) / 2f64,
+            ];
+            if ori.iter().any(|x| !x.is_finite()) {
+                //unbounded box, centered at whichever finite extent exists
+                let mut reference = [0f64; 3];
+                for i in 0..3 {
+                    if b[i].is_finite() {
+                        reference[i] = b[i];
+                    } else if b[i + 3].is_finite() {
+                        reference[i] = b[i + 3];
+                    }
+                }
+                return (reference, f64::INFINITY);
+            }
+            let half_diagonal =
+                distance_squared(&[b[0], b[1], b[2]], &[b[3], b[4], b[5]]).sqrt() / 2f64;
+            (ori, half_diagonal)
+        }
+        BoundType::OrientedBox => (
+            [b[0], b[1], b[2]],
+            (b[3] * b[3] + b[4] * b[4] + b[5] * b[5]).sqrt(),
+        ),
+    }
+}
+
+///distance from point p to the closest point of an axis aligned box
+fn distance_point_box(p: &[f64; 3], lower: &[f64], upper: &[f64]) -> f64 {
+    let mut d = 0f64;
+    for i in 0..3 {
+        let excess = if p[i] < lower[i] {
+            lower[i] - p[i]
+        } else if p[i] > upper[i] {
+            p[i] - upper[i]
+        } else {
+            0f64
+        };
+        d += excess * excess;
+    }
+    d.sqrt()
 }
 
 impl IBound for BoundSphere {
     fn get_type(&self) -> BoundType {
         BoundType::Sphere
     }
-    fn intersect(&self, _other: &dyn IBound) -> bool {
-        unimplemented!();
+    fn intersect(&self, other: &dyn IBound) -> bool {
+        self.get_shortest_separation(other) <= 0f64
     }
-    fn get_shortest_separation(&self, _other: &dyn IBound) -> f64 {
-        unimplemented!();
+    fn get_shortest_separation(&self, other: &dyn IBound) -> f64 {
+        let b = other.get_bound_data();
+        let d = match other.get_type() {
+            BoundType::Sphere => {
+                distance_squared(&self._ori, &[b[0], b[1], b[2]]).sqrt() - self._radius - b[3]
+            }
+            BoundType::AxisAlignBox => {
+                distance_point_box(&self._ori, &b[0..3], &b[3..6]) - self._radius
+            }
+            BoundType::OrientedBox => {
+                //see OrientedBBox for sphere oriented box separation
+                other.get_shortest_separation(self)
+            }
+        };
+        d.max(0f64)
     }
     fn get_bound_data(&self) -> [f64; 32] {
-        unimplemented!();
+        let mut arr = [0f64; 32];
+        arr[0..3].copy_from_slice(&self._ori);
+        arr[3] = self._radius;
+        arr
     }
-    fn get_union(&mut self, _bounds: &[&dyn IBound]) {
-        unimplemented!();
+    fn get_union(&mut self, bounds: &[&dyn IBound]) {
+        if bounds.is_empty() {
+            //nothing to enclose, keep the current bound
+            return;
+        }
+        //start from an empty sphere so that the first bound is taken as is
+        self._ori = [0f64; 3];
+        self._radius = f64::NEG_INFINITY;
+        for i in bounds {
+            let (ori, radius) = enclosing_sphere(*i);
+            let d = distance_squared(&self._ori, &ori).sqrt();
+            if d + radius <= self._radius {
+                //already enclosed
+                continue;
+            } else if d + self._radius <= radius {
+                self._ori = ori;
+                self._radius = radius;
+            } else {
+                let radius_new = (d + self._radius + radius) / 2f64;
+                let k = (radius_new - self._radius) / d;
+                for (j, o) in self._ori.iter_mut().enumerate() {
+                    *o += (ori[j] - *o) * k;
+                }
+                self._radius = radius_new;
+            }
+        }
     }
     fn get_centroid(&self) -> [f64; 3] {
-        unimplemented!();
+        self._ori
     }
 }
 
 impl Default for BoundSphere {
     fn default() -> BoundSphere {
-        unimplemented!();
+        BoundSphere {
+            _ori: [0f64; 3],
+            _radius: f64::INFINITY,
+        }
     }
 }
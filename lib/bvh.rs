@@ -0,0 +1,264 @@
+//bounding volume hierarchy over a static collection of shapes
+use bound::{Axis, AxisAlignedBBox};
+use i_bound::IBound;
+use i_shape::IShape;
+use mat::Mat3x1;
+use ray::Ray3;
+
+///maximum number of shapes stored in a leaf node
+const LEAF_SIZE_MAX: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct BvhNode {
+    pub _bound: AxisAlignedBBox,
+    ///indices of child nodes for interior nodes
+    pub _children: Option<(usize, usize)>,
+    ///indices of shapes for leaf nodes
+    pub _shapes: Vec<usize>,
+}
+
+pub struct Bvh {
+    pub _shapes: Vec<Box<dyn IShape>>,
+    pub _bounds: Vec<AxisAlignedBBox>,
+    pub _nodes: Vec<BvhNode>,
+    pub _root: Option<usize>,
+}
+
+impl Bvh {
+    ///builds hierarchy using median split on the longest axis of shape centroids
+    pub fn init(shapes: Vec<Box<dyn IShape>>) -> Bvh {
+        let bounds: Vec<AxisAlignedBBox> = shapes
+            .iter()
+            .map(|x| AxisAlignedBBox::init_from_bound(x.get_bound()))
+            .collect();
+        let mut bvh = Bvh {
+            _shapes: shapes,
+            _bounds: bounds,
+            _nodes: vec![],
+            _root: None,
+        };
+        if !bvh._shapes.is_empty() {
+            let mut indices: Vec<usize> = (0..bvh._shapes.len()).collect();
+            bvh._root = Some(bvh.build(&mut indices[..]));
+        }
+        bvh
+    }
+
+    fn build(&mut self, indices: &mut [usize]) -> usize {
+        let mut bound: AxisAlignedBBox = Default::default();
+        {
+            let bounds: Vec<&dyn IBound> = indices
+                .iter()
+                .map(|x| &self._bounds[*x] as &dyn IBound)
+                .collect();
+            bound.get_union(&bounds[..]);
+        }
+
+        if indices.len() <= LEAF_SIZE_MAX {
+            self._nodes.push(BvhNode {
+                _bound: bound,
+                _children: None,
+                _shapes: indices.to_vec(),
+            });
+            return self._nodes.len() - 1;
+        }
+
+        //split along the axis of largest centroid spread, unbounded centroids are ignored in the spread
+        let centroids: Vec<[f64; 3]> = indices
+            .iter()
+            .map(|x| self._bounds[*x].get_centroid())
+            .collect();
+        let mut centroid_bound = AxisAlignedBBox {
+            _bound_lower: [f64::INFINITY; 3],
+            _bound_upper: [f64::NEG_INFINITY; 3],
+        };
+        for c in centroids.iter() {
+            for (i, x) in c.iter().enumerate() {
+                centroid_bound._bound_lower[i] = centroid_bound._bound_lower[i].min(*x);
+                centroid_bound._bound_upper[i] = centroid_bound._bound_upper[i].max(*x);
+            }
+        }
+        let axis = match centroid_bound.get_longest_axis().0 {
+            Axis::X => 0,
+            Axis::Y => 1,
+            Axis::Z => 2,
+        };
+
+        let mid = indices.len() / 2;
+        {
+            let bounds = &self._bounds;
+            indices.select_nth_unstable_by(mid, |a, b| {
+                let ca = bounds[*a].get_centroid()[axis];
+                let cb = bounds[*b].get_centroid()[axis];
+                ca.total_cmp(&cb)
+            });
+        }
+
+        let (indices_left, indices_right) = indices.split_at_mut(mid);
+        let left = self.build(indices_left);
+        let right = self.build(indices_right);
+        self._nodes.push(BvhNode {
+            _bound: bound,
+            _children: Some((left, right)),
+            _shapes: vec![],
+        });
+        self._nodes.len() - 1
+    }
+
+    ///returns indices of shapes whose bounds intersect the given bound
+    pub fn query_bound(&self, bound: &dyn IBound) -> Vec<usize> {
+        let mut ret = vec![];
+        let mut stack: Vec<usize> = self._root.into_iter().collect();
+        while let Some(n) = stack.pop() {
+            let node = &self._nodes[n];
+            if !node._bound.intersect(bound) {
+                continue;
+            }
+            match node._children {
+                Some((l, r)) => {
+                    stack.push(l);
+                    stack.push(r);
+                }
+                _ => {
+                    for i in node._shapes.iter() {
+                        if self._bounds[*i].intersect(bound) {
+                            ret.push(*i);
+                        }
+                    }
+                }
+            }
+        }
+        ret.sort_unstable();
+        ret
+    }
+
+    ///returns indices and locations of intersection of shapes that intersect the given shape,
+    ///shapes forming a pair that is not supported are skipped
+    pub fn query_intersect(&self, shape: &dyn IShape) -> Vec<(usize, Option<Mat3x1<f64>>)> {
+        self.query_bound(shape.get_bound())
+            .into_iter()
+            .filter_map(|x| match shape.get_intersection(self._shapes[x].as_ref()) {
+                Ok(ref ret) if ret._is_intersect => Some((x, ret.get_point())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    ///returns index and location of the intersected shape closest to the ray origin,
+    ///shapes not supported by the ray are skipped
+    pub fn query_ray(&self, ray: &Ray3) -> Option<(usize, Mat3x1<f64>)> {
+        let mut nearest: Option<(usize, Mat3x1<f64>)> = None;
+        let mut t_nearest = f64::INFINITY;
+        let mut stack: Vec<(usize, f64)> = vec![];
+        if let Some(n) = self._root {
+            if let Some((t, _)) = self._nodes[n]._bound.intersect_ray(&ray._ori, &ray._dir) {
+                stack.push((n, t));
+            }
+        }
+        while let Some((n, t_enter)) = stack.pop() {
+            if t_enter > t_nearest {
+                //node is further than the closest hit so far
+                continue;
+            }
+            let node = &self._nodes[n];
+            match node._children {
+                Some((l, r)) => {
+                    let mut children: Vec<(usize, f64)> = [l, r]
+                        .iter()
+                        .filter_map(|x| {
+                            self._nodes[*x]
+                                ._bound
+                                .intersect_ray(&ray._ori, &ray._dir)
+                                .map(|(t, _)| (*x, t))
+                        })
+                        .collect();
+                    //visit nearer child first
+                    children.sort_by(|a, b| b.1.total_cmp(&a.1));
+                    stack.extend(children);
+                }
+                _ => {
+                    for i in node._shapes.iter() {
+                        let ret = match ray.get_intersection(self._shapes[*i].as_ref()) {
+                            Ok(ret) => ret,
+                            _ => continue,
+                        };
+                        if let (true, Some(loc)) = (ret._is_intersect, ret.get_point()) {
+                            let t = loc.minus(&ray._ori).unwrap().magnitude().unwrap();
+                            if t < t_nearest {
+                                t_nearest = t;
+                                nearest = Some((*i, loc));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        nearest
+    }
+
+    ///returns index pairs of all intersecting shapes in the hierarchy, pairs that are not supported are skipped
+    pub fn query_self_intersect(&self) -> Vec<(usize, usize)> {
+        let mut ret = vec![];
+        if let Some(n) = self._root {
+            self.collide_self(n, &mut ret);
+        }
+        ret.sort_unstable();
+        ret
+    }
+
+    fn collide_self(&self, n: usize, pairs: &mut Vec<(usize, usize)>) {
+        let node = &self._nodes[n];
+        match node._children {
+            Some((l, r)) => {
+                self.collide_self(l, pairs);
+                self.collide_self(r, pairs);
+                self.collide_nodes(l, r, pairs);
+            }
+            _ => {
+                for (k, i) in node._shapes.iter().enumerate() {
+                    for j in node._shapes[k + 1..].iter() {
+                        self.collide_shapes(*i, *j, pairs);
+                    }
+                }
+            }
+        }
+    }
+
+    fn collide_nodes(&self, a: usize, b: usize, pairs: &mut Vec<(usize, usize)>) {
+        let node_a = &self._nodes[a];
+        let node_b = &self._nodes[b];
+        if !node_a._bound.intersect(&node_b._bound) {
+            return;
+        }
+        match (node_a._children, node_b._children) {
+            (Some((l, r)), _) => {
+                self.collide_nodes(l, b, pairs);
+                self.collide_nodes(r, b, pairs);
+            }
+            (_, Some((l, r))) => {
+                self.collide_nodes(a, l, pairs);
+                self.collide_nodes(a, r, pairs);
+            }
+            _ => {
+                for i in node_a._shapes.iter() {
+                    for j in node_b._shapes.iter() {
+                        self.collide_shapes(*i, *j, pairs);
+                    }
+                }
+            }
+        }
+    }
+
+    fn collide_shapes(&self, i: usize, j: usize, pairs: &mut Vec<(usize, usize)>) {
+        if !self._bounds[i].intersect(&self._bounds[j]) {
+            return;
+        }
+        let is_intersect = match self._shapes[i].get_intersection(self._shapes[j].as_ref()) {
+            Ok(ret) => ret._is_intersect,
+            _ => false,
+        };
+        if is_intersect {
+            pairs.push(if i < j { (i, j) } else { (j, i) });
+        }
+    }
+}
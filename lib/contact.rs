@@ -0,0 +1,466 @@
+//contact manifold generation between boxes, prisms, spheres and planes
+use error::MazthError;
+use i_shape::{IShape, ShapeType};
+use intersect_gjk;
+use intersect_sat::{self, Feature, Polyhedron};
+use mat::Mat3x1;
+use point::Point3;
+
+///maximum number of points kept in a manifold
+pub const POINTS_MAX: usize = 4;
+
+///distance up to which separated features are still reported as touching
+pub const TOLERANCE: f64 = 0.000001f64;
+
+///kind of the reference and incident features of a contact point
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum FeatureKind {
+    #[default]
+    Face,
+    Edge,
+}
+
+///identifies the features a contact point is generated from, stable across frames for coherent motion
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct FeatureId {
+    ///tells whether _reference and _incident are face or edge indices
+    pub _kind: FeatureKind,
+    ///reference face, reference edge, or 0 for smooth shapes
+    pub _reference: usize,
+    ///incident face, incident edge, or 0 for smooth shapes
+    pub _incident: usize,
+    ///incident vertex the point originates from or starts the clipped incident edge
+    pub _vertex: usize,
+    ///side of the reference face clipping the incident edge
+    pub _side: Option<usize>,
+    ///set if the reference feature belongs to the second shape
+    pub _is_flipped: bool,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct ContactPoint {
+    ///midpoint between the surfaces of both shapes
+    pub _point: Mat3x1<f64>,
+    ///penetration along the manifold normal
+    pub _depth: f64,
+    pub _id: FeatureId,
+    ///accumulated normal impulse carried over between frames for warm starting
+    pub _impulse: f64,
+    ///number of consecutive frames this point has been matched
+    pub _age: usize,
+}
+
+///shapes apart from each other give a manifold without points
+#[derive(Debug, Clone, Default)]
+pub struct Manifold {
+    ///unit normal pointing from a towards b
+    pub _normal: Mat3x1<f64>,
+    pub _points: Vec<ContactPoint>,
+}
+
+impl ContactPoint {
+    fn init(point: Mat3x1<f64>, depth: f64, id: FeatureId) -> ContactPoint {
+        ContactPoint {
+            _point: point,
+            _depth: depth,
+            _id: id,
+            _impulse: 0f64,
+            _age: 0,
+        }
+    }
+}
+
+impl Manifold {
+    ///carries impulses over from points of the previous frame with matching feature ids
+    pub fn match_previous(&mut self, previous: &Manifold) {
+        for p in self._points.iter_mut() {
+            if let Some(old) = previous._points.iter().find(|x| x._id == p._id) {
+                p._impulse = old._impulse;
+                p._age = old._age + 1;
+            }
+        }
+    }
+
+    fn flip(mut self) -> Manifold {
+        self._normal = self._normal.scale(-1f64).unwrap();
+        for p in self._points.iter_mut() {
+            p._id._is_flipped = !p._id._is_flipped;
+        }
+        self
+    }
+}
+
+///clips polygon against plane through p with outward normal n, keeping the inner part
+fn clip_polygon(
+    polygon: &[(Mat3x1<f64>, usize, Option<usize>)],
+    p: &Mat3x1<f64>,
+    n: &Mat3x1<f64>,
+    side: usize,
+) -> Vec<(Mat3x1<f64>, usize, Option<usize>)> {
+    let mut ret = vec![];
+    for i in 0..polygon.len() {
+        let a = &polygon[i];
+        let b = &polygon[(i + 1) % polygon.len()];
+        let da = a.0.minus(p).unwrap().dot(n).unwrap();
+        let db = b.0.minus(p).unwrap().dot(n).unwrap();
+        if da <= 0f64 {
+            ret.push(*a);
+        }
+        if (da < 0f64 && db > 0f64) || (da > 0f64 && db < 0f64) {
+            let t = da / (da - db);
+            let x =
+                a.0.plus(&b.0.minus(&a.0).unwrap().scale(t).unwrap())
+                    .unwrap();
+            ret.push((x, a.1, Some(side)));
+        }
+    }
+    ret
+}
+
+///keeps the deepest point and the points spanning the largest area
+fn reduce(points: Vec<ContactPoint>, normal: &Mat3x1<f64>) -> Vec<ContactPoint> {
+    //coincident points keep the deeper one
+    let mut unique: Vec<ContactPoint> = vec![];
+    for p in points.into_iter() {
+        let duplicate = unique.iter().position(|x| {
+            let d = x._point.minus(&p._point).unwrap();
+            d.dot(&d).unwrap() <= TOLERANCE * TOLERANCE
+        });
+        match duplicate {
+            Some(i) if unique[i]._depth < p._depth => unique[i] = p,
+            Some(_) => {}
+            _ => unique.push(p),
+        }
+    }
+    let points = unique;
+    if points.len() <= POINTS_MAX {
+        return points;
+    }
+    let area = |a: &ContactPoint, b: &ContactPoint, c: &ContactPoint| {
+        b._point
+            .minus(&a._point)
+            .unwrap()
+            .cross(&c._point.minus(&a._point).unwrap())
+            .unwrap()
+            .dot(normal)
+            .unwrap()
+    };
+    //picks among points not chosen yet so that the same point is never kept twice
+    let argmax = |f: &dyn Fn(&ContactPoint) -> f64, chosen: &[usize]| {
+        let mut best: Option<usize> = None;
+        for i in (0..points.len()).filter(|x| !chosen.contains(x)) {
+            let is_better = match best {
+                Some(b) => f(&points[i]) > f(&points[b]),
+                _ => true,
+            };
+            if is_better {
+                best = Some(i);
+            }
+        }
+        best.unwrap()
+    };
+    let i0 = argmax(&|x| x._depth, &[]);
+    let p0 = points[i0];
+    let i1 = argmax(
+        &|x| {
+            let d = x._point.minus(&p0._point).unwrap();
+            d.dot(&d).unwrap()
+        },
+        &[i0],
+    );
+    let i2 = argmax(&|x| area(&p0, &points[i1], x).abs(), &[i0, i1]);
+    let (mut p1, mut p2) = (points[i1], points[i2]);
+    if area(&p0, &p1, &p2) < 0f64 {
+        std::mem::swap(&mut p1, &mut p2);
+    }
+    //fourth point adds the most area outside of the triangle
+    let i3 = argmax(
+        &|x| {
+            -area(&p0, &p1, x)
+                .min(area(&p1, &p2, x))
+                .min(area(&p2, &p0, x))
+        },
+        &[i0, i1, i2],
+    );
+    vec![p0, p1, p2, points[i3]]
+}
+
+fn closest_segments(
+    p1: &Mat3x1<f64>,
+    q1: &Mat3x1<f64>,
+    p2: &Mat3x1<f64>,
+    q2: &Mat3x1<f64>,
+) -> (Mat3x1<f64>, Mat3x1<f64>) {
+    let d1 = q1.minus(p1).unwrap();
+    let d2 = q2.minus(p2).unwrap();
+    let r = p1.minus(p2).unwrap();
+    let a = d1.dot(&d1).unwrap();
+    let e = d2.dot(&d2).unwrap();
+    let f = d2.dot(&r).unwrap();
+    let c = d1.dot(&r).unwrap();
+    let b = d1.dot(&d2).unwrap();
+    let denom = a * e - b * b;
+    let mut s = if denom > 0f64 {
+        ((b * f - c * e) / denom).clamp(0f64, 1f64)
+    } else {
+        0f64
+    };
+    let mut t = (b * s + f) / e;
+    if t < 0f64 {
+        t = 0f64;
+        s = (-c / a).clamp(0f64, 1f64);
+    } else if t > 1f64 {
+        t = 1f64;
+        s = ((b - c) / a).clamp(0f64, 1f64);
+    }
+    (
+        p1.plus(&d1.scale(s).unwrap()).unwrap(),
+        p2.plus(&d2.scale(t).unwrap()).unwrap(),
+    )
+}
+
+///clips the incident face of inc against the reference face of rf, normal points out of the reference face
+fn clip_faces(rf: &Polyhedron, reference: usize, inc: &Polyhedron) -> Vec<ContactPoint> {
+    let n = rf._normals[reference];
+    let face = &rf._faces[reference];
+
+    let mut incident = 0;
+    for (i, m) in inc._normals.iter().enumerate() {
+        if m.dot(&n).unwrap() < inc._normals[incident].dot(&n).unwrap() {
+            incident = i;
+        }
+    }
+
+    let mut polygon: Vec<(Mat3x1<f64>, usize, Option<usize>)> = inc._faces[incident]
+        .iter()
+        .map(|x| (inc._verts[*x], *x, None))
+        .collect();
+    for k in 0..face.len() {
+        let a = rf._verts[face[k]];
+        let b = rf._verts[face[(k + 1) % face.len()]];
+        let side = b.minus(&a).unwrap().cross(&n).unwrap();
+        polygon = clip_polygon(&polygon[..], &a, &side, k);
+    }
+
+    let origin = rf._verts[face[0]];
+    polygon
+        .iter()
+        .filter_map(|(p, vertex, side)| {
+            let separation = p.minus(&origin).unwrap().dot(&n).unwrap();
+            if separation > TOLERANCE {
+                return None;
+            }
+            Some(ContactPoint::init(
+                p.minus(&n.scale(separation * 0.5f64).unwrap()).unwrap(),
+                -separation,
+                FeatureId {
+                    _kind: FeatureKind::Face,
+                    _reference: reference,
+                    _incident: incident,
+                    _vertex: *vertex,
+                    _side: *side,
+                    _is_flipped: false,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn manifold_polyhedron(a: &Polyhedron, b: &Polyhedron) -> Option<Manifold> {
+    let overlap = intersect_sat::query_polyhedron(a, b)?;
+    if !overlap._is_intersect {
+        return None;
+    }
+    match overlap._feature {
+        Feature::FaceA(i) => Some(Manifold {
+            _normal: overlap._normal,
+            _points: reduce(clip_faces(a, i, b), &overlap._normal),
+        }),
+        Feature::FaceB(i) => Some(
+            Manifold {
+                _normal: b._normals[i],
+                _points: reduce(clip_faces(b, i, a), &b._normals[i]),
+            }
+            .flip(),
+        ),
+        Feature::Edge(i, j) => {
+            let (ea, eb) = (a._edges[i], b._edges[j]);
+            let (pa, pb) = closest_segments(
+                &a._verts[ea.0],
+                &a._verts[ea.1],
+                &b._verts[eb.0],
+                &b._verts[eb.1],
+            );
+            Some(Manifold {
+                _normal: overlap._normal,
+                _points: vec![ContactPoint::init(
+                    pa.midpoint(&pb).unwrap(),
+                    overlap._depth,
+                    FeatureId {
+                        _kind: FeatureKind::Edge,
+                        _reference: i,
+                        _incident: j,
+                        ..Default::default()
+                    },
+                )],
+            })
+        }
+    }
+}
+
+///contact of sphere b against a, given the point of a closest to the sphere center and the normal towards it
+fn manifold_sphere(closest: &Mat3x1<f64>, normal: &Mat3x1<f64>, depth: f64, b: &[f64]) -> Manifold {
+    let deepest = Mat3x1::<f64>::init_from_slice(b)
+        .minus(&normal.scale(b[3]).unwrap())
+        .unwrap();
+    Manifold {
+        _normal: *normal,
+        _points: vec![ContactPoint::init(
+            closest.midpoint(&deepest).unwrap(),
+            depth,
+            Default::default(),
+        )],
+    }
+}
+
+fn manifold_polyhedron_sphere(shape: &dyn IShape, a: &Polyhedron, b: &[f64]) -> Option<Manifold> {
+    let center = Point3::init(&b[0..3]);
+    match intersect_gjk::query_distance(shape, &center).ok()? {
+        Some(s) => {
+            let depth = b[3] - s._distance;
+            if depth < -TOLERANCE {
+                return None;
+            }
+            Some(manifold_sphere(&s._point_a, &s._axis, depth, b))
+        }
+        _ => {
+            //center is inside, push out through the closest face
+            let c = Mat3x1::<f64>::init_from_slice(b);
+            let mut best = 0;
+            let mut best_d = f64::NEG_INFINITY;
+            for (i, (f, n)) in a._faces.iter().zip(a._normals.iter()).enumerate() {
+                let d = c.minus(&a._verts[f[0]]).unwrap().dot(n).unwrap();
+                if d > best_d {
+                    best_d = d;
+                    best = i;
+                }
+            }
+            let n = a._normals[best];
+            let closest = c.minus(&n.scale(best_d).unwrap()).unwrap();
+            let mut m = manifold_sphere(&closest, &n, b[3] - best_d, b);
+            m._points[0]._id._reference = best;
+            Some(m)
+        }
+    }
+}
+
+fn manifold_sphere_sphere(a: &[f64], b: &[f64]) -> Option<Manifold> {
+    let v = Mat3x1::<f64>::init_from_slice(b)
+        .minus(&Mat3x1::<f64>::init_from_slice(a))
+        .unwrap();
+    let d = v.magnitude().unwrap();
+    let depth = a[3] + b[3] - d;
+    if depth < -TOLERANCE {
+        return None;
+    }
+    let normal = if d > 0f64 {
+        v.scale(1f64 / d).unwrap()
+    } else {
+        Mat3x1 {
+            _val: [0f64, 1f64, 0f64],
+        }
+    };
+    let closest = Mat3x1::<f64>::init_from_slice(a)
+        .plus(&normal.scale(a[3]).unwrap())
+        .unwrap();
+    Some(manifold_sphere(&closest, &normal, depth, b))
+}
+
+///plane a is treated as a half space bounded by the plane with its normal pointing outside
+fn manifold_plane_sphere(a: &[f64], b: &[f64]) -> Option<Manifold> {
+    let n = Mat3x1::<f64>::init_from_slice(&a[3..6]);
+    let s = Mat3x1::<f64>::init_from_slice(b)
+        .minus(&Mat3x1::<f64>::init_from_slice(a))
+        .unwrap()
+        .dot(&n)
+        .unwrap();
+    let depth = b[3] - s;
+    if depth < -TOLERANCE {
+        return None;
+    }
+    let closest = Mat3x1::<f64>::init_from_slice(b)
+        .minus(&n.scale(s).unwrap())
+        .unwrap();
+    Some(manifold_sphere(&closest, &n, depth, b))
+}
+
+fn manifold_plane_polyhedron(a: &[f64], b: &Polyhedron) -> Option<Manifold> {
+    let n = Mat3x1::<f64>::init_from_slice(&a[3..6]);
+    let points: Vec<ContactPoint> = b
+        ._verts
+        .iter()
+        .enumerate()
+        .filter_map(|(i, v)| {
+            let s = v
+                .minus(&Mat3x1::<f64>::init_from_slice(a))
+                .unwrap()
+                .dot(&n)
+                .unwrap();
+            if s > TOLERANCE {
+                return None;
+            }
+            Some(ContactPoint::init(
+                v.minus(&n.scale(s * 0.5f64).unwrap()).unwrap(),
+                -s,
+                FeatureId {
+                    _vertex: i,
+                    ..Default::default()
+                },
+            ))
+        })
+        .collect();
+    if points.is_empty() {
+        return None;
+    }
+    Some(Manifold {
+        _normal: n,
+        _points: reduce(points, &n),
+    })
+}
+
+fn is_polyhedron(t: ShapeType) -> bool {
+    matches!(
+        t,
+        ShapeType::Box | ShapeType::TriPrism | ShapeType::OrientedBox
+    )
+}
+
+///returns contact manifold with normal pointing from a towards b, the manifold has no points if the shapes are apart
+pub fn query_manifold(a: &dyn IShape, b: &dyn IShape) -> Result<Manifold, MazthError> {
+    let unsupported = MazthError::UnsupportedPair(a.get_type(), b.get_type());
+    let polyhedron = |x: &dyn IShape| Polyhedron::init_from_shape(x).ok_or(unsupported);
+    let da = a.get_shape_data();
+    let db = b.get_shape_data();
+    let m = match (a.get_type(), b.get_type()) {
+        (ShapeType::Sphere, ShapeType::Sphere) => manifold_sphere_sphere(&da, &db),
+        (ShapeType::Plane, ShapeType::Sphere) => manifold_plane_sphere(&da, &db),
+        (ShapeType::Sphere, ShapeType::Plane) => manifold_plane_sphere(&db, &da).map(|x| x.flip()),
+        (ShapeType::Plane, x) if is_polyhedron(x) => {
+            manifold_plane_polyhedron(&da, &polyhedron(b)?)
+        }
+        (x, ShapeType::Plane) if is_polyhedron(x) => {
+            manifold_plane_polyhedron(&db, &polyhedron(a)?).map(|x| x.flip())
+        }
+        (x, ShapeType::Sphere) if is_polyhedron(x) => {
+            manifold_polyhedron_sphere(a, &polyhedron(a)?, &db)
+        }
+        (ShapeType::Sphere, x) if is_polyhedron(x) => {
+            manifold_polyhedron_sphere(b, &polyhedron(b)?, &da).map(|x| x.flip())
+        }
+        (x, y) if is_polyhedron(x) && is_polyhedron(y) => {
+            manifold_polyhedron(&polyhedron(a)?, &polyhedron(b)?)
+        }
+        _ => return Err(unsupported),
+    };
+    Ok(m.unwrap_or_default())
+}
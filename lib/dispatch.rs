@@ -0,0 +1,188 @@
+//collision dispatcher selecting an intersection routine by the types of both shapes
+
+use std::collections::HashMap;
+
+use error::MazthError;
+use i_shape::{IShape, ShapeType};
+use intersect_epa;
+use intersect_gjk;
+use intersection::{Hit, Intersection};
+
+///intersection routine for a pair of shapes, results are seen from the first shape
+pub type Handler = Box<dyn Fn(&dyn IShape, &dyn IShape) -> Result<Intersection, MazthError>>;
+
+///pairs implemented by the shapes of this crate, the first type is the one implementing the pair
+pub const NATIVE_PAIRS: [(ShapeType, ShapeType); 42] = [
+    (ShapeType::Point, ShapeType::Point),
+    (ShapeType::Ray, ShapeType::Ray),
+    (ShapeType::Ray, ShapeType::Point),
+    (ShapeType::Ray, ShapeType::Sphere),
+    (ShapeType::Ray, ShapeType::Plane),
+    (ShapeType::Sphere, ShapeType::Sphere),
+    (ShapeType::Sphere, ShapeType::Point),
+    (ShapeType::Sphere, ShapeType::Plane),
+    (ShapeType::Plane, ShapeType::Point),
+    (ShapeType::Box, ShapeType::Point),
+    (ShapeType::Box, ShapeType::Ray),
+    (ShapeType::Box, ShapeType::Sphere),
+    (ShapeType::Box, ShapeType::Plane),
+    (ShapeType::Box, ShapeType::Box),
+    (ShapeType::Box, ShapeType::TriPrism),
+    (ShapeType::OrientedBox, ShapeType::Point),
+    (ShapeType::OrientedBox, ShapeType::Ray),
+    (ShapeType::OrientedBox, ShapeType::Sphere),
+    (ShapeType::OrientedBox, ShapeType::Plane),
+    (ShapeType::OrientedBox, ShapeType::Box),
+    (ShapeType::OrientedBox, ShapeType::TriPrism),
+    (ShapeType::OrientedBox, ShapeType::OrientedBox),
+    (ShapeType::TriPrism, ShapeType::Point),
+    (ShapeType::TriPrism, ShapeType::Ray),
+    (ShapeType::TriPrism, ShapeType::Sphere),
+    (ShapeType::TriPrism, ShapeType::Line),
+    (ShapeType::TriPrism, ShapeType::TriPrism),
+    (ShapeType::Trig, ShapeType::Point),
+    (ShapeType::Trig, ShapeType::Ray),
+    (ShapeType::Trig, ShapeType::Line),
+    (ShapeType::Trig, ShapeType::Sphere),
+    (ShapeType::Trig, ShapeType::Plane),
+    (ShapeType::Trig, ShapeType::Box),
+    (ShapeType::Trig, ShapeType::Trig),
+    (ShapeType::Trig, ShapeType::TriPrism),
+    (ShapeType::Trig, ShapeType::OrientedBox),
+    (ShapeType::Rect, ShapeType::Point),
+    (ShapeType::Rect, ShapeType::Ray),
+    (ShapeType::Rect, ShapeType::Line),
+    (ShapeType::Rect, ShapeType::Sphere),
+    (ShapeType::Rect, ShapeType::Plane),
+    (ShapeType::Frustum, ShapeType::Point),
+];
+
+///key of a handler, shapes of type Complex are told apart by the id returned from get_custom_id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShapeKey {
+    Native(ShapeType),
+    Custom(u32),
+}
+
+impl From<ShapeType> for ShapeKey {
+    fn from(t: ShapeType) -> ShapeKey {
+        match t {
+            ShapeType::Complex => ShapeKey::Custom(0),
+            x => ShapeKey::Native(x),
+        }
+    }
+}
+
+impl ShapeKey {
+    pub fn of(s: &dyn IShape) -> ShapeKey {
+        match s.get_type() {
+            ShapeType::Complex => ShapeKey::Custom(s.get_custom_id()),
+            x => ShapeKey::Native(x),
+        }
+    }
+}
+
+pub struct Dispatcher {
+    ///handlers are stored once per pair, the reversed pair is served by swapping the shapes
+    pub _handlers: HashMap<(ShapeKey, ShapeKey), Handler>,
+    ///use gjk and epa for pairs without handler if both shapes provide a support function
+    pub _is_gjk_fallback: bool,
+}
+
+impl Default for Dispatcher {
+    fn default() -> Dispatcher {
+        Dispatcher::init()
+    }
+}
+
+impl Dispatcher {
+    ///dispatcher with handlers for the pairs implemented by the shapes of this crate
+    pub fn init() -> Dispatcher {
+        let mut d = Dispatcher {
+            _handlers: HashMap::new(),
+            _is_gjk_fallback: true,
+        };
+        for (a, b) in NATIVE_PAIRS.iter() {
+            d.register(*a, *b, |x, y| x.get_intersection(y));
+        }
+        d
+    }
+
+    ///registers handler for shapes keyed a and b, replacing an existing handler of the same pair,
+    ///the handler receives shapes in the registered order
+    pub fn register<F>(&mut self, a: impl Into<ShapeKey>, b: impl Into<ShapeKey>, f: F)
+    where
+        F: Fn(&dyn IShape, &dyn IShape) -> Result<Intersection, MazthError> + 'static,
+    {
+        self._handlers.insert((a.into(), b.into()), Box::new(f));
+    }
+
+    pub fn unregister(
+        &mut self,
+        a: impl Into<ShapeKey>,
+        b: impl Into<ShapeKey>,
+    ) -> Option<Handler> {
+        self._handlers.remove(&(a.into(), b.into()))
+    }
+
+    ///tests whether the pair is served by a handler in either order
+    pub fn is_registered(&self, a: impl Into<ShapeKey>, b: impl Into<ShapeKey>) -> bool {
+        let (a, b) = (a.into(), b.into());
+        self._handlers.contains_key(&(a, b)) || self._handlers.contains_key(&(b, a))
+    }
+
+    ///intersects a and b with the handler of the pair, falling back to gjk if enabled,
+    ///results are seen from a
+    pub fn query_intersection(
+        &self,
+        a: &dyn IShape,
+        b: &dyn IShape,
+    ) -> Result<Intersection, MazthError> {
+        let (ka, kb) = (ShapeKey::of(a), ShapeKey::of(b));
+        if let Some(f) = self._handlers.get(&(ka, kb)) {
+            return f(a, b);
+        }
+        if let Some(f) = self._handlers.get(&(kb, ka)) {
+            return f(b, a).map(|x| x.flip());
+        }
+        let (ta, tb) = (a.get_type(), b.get_type());
+        if !self._is_gjk_fallback {
+            return Err(MazthError::UnsupportedPair(ta, tb));
+        }
+        match query_support(a, b) {
+            Err(MazthError::NoSupportFunction(_)) => Err(MazthError::UnsupportedPair(ta, tb)),
+            x => x,
+        }
+    }
+}
+
+///intersects shapes by their support functions, depth and contact come from epa
+pub fn query_support(a: &dyn IShape, b: &dyn IShape) -> Result<Intersection, MazthError> {
+    if !a.get_bound().intersect(b.get_bound()) {
+        return Ok(Default::default());
+    }
+    if !intersect_gjk::query_intersect(a, b)? {
+        return Ok(Default::default());
+    }
+    match intersect_epa::query_penetration(a, b) {
+        Some(p) => {
+            let point = p._point_a.midpoint(&p._point_b).unwrap();
+            Ok(Intersection {
+                _is_intersect: true,
+                _hits: vec![Hit {
+                    _normal: Some(p._normal.scale(-1f64).unwrap()),
+                    ..Hit::init(point)
+                }],
+                _depth: Some(p._depth),
+            })
+        }
+        _ => {
+            //touching without volume of overlap
+            Ok(Intersection {
+                _is_intersect: true,
+                _hits: vec![],
+                _depth: Some(0f64),
+            })
+        }
+    }
+}
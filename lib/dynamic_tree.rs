@@ -0,0 +1,356 @@
+//dynamic bounding volume tree supporting incremental insertion, removal and update of shapes
+use std::collections::HashSet;
+
+use bound::AxisAlignedBBox;
+use i_bound::IBound;
+use i_shape::IShape;
+
+///stable reference to a shape stored in the tree, valid until the shape is removed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle(pub usize);
+
+pub struct DynamicTreeNode {
+    ///fattened bound for leaf nodes, union of children bounds for interior nodes
+    pub _bound: AxisAlignedBBox,
+    pub _parent: Option<usize>,
+    pub _children: Option<(usize, usize)>,
+    ///0 for leaf nodes
+    pub _height: usize,
+    pub _shape: Option<Box<dyn IShape>>,
+}
+
+pub struct DynamicTree {
+    pub _nodes: Vec<DynamicTreeNode>,
+    pub _root: Option<usize>,
+    ///indices of nodes available for reuse
+    pub _free: Vec<usize>,
+    ///amount leaf bounds are enlarged by in each direction
+    pub _margin: f64,
+    ///leaves inserted or reinserted since last pair query
+    pub _moved: HashSet<usize>,
+    pub _pairs: HashSet<(usize, usize)>,
+}
+
+fn union(a: &AxisAlignedBBox, b: &AxisAlignedBBox) -> AxisAlignedBBox {
+    let mut u: AxisAlignedBBox = Default::default();
+    u.get_union(&[a as &dyn IBound, b as &dyn IBound]);
+    u
+}
+
+///sum of box extents, used as insertion cost since it stays meaningful for flat boxes
+fn perimeter(a: &AxisAlignedBBox) -> f64 {
+    (0..3).map(|i| a._bound_upper[i] - a._bound_lower[i]).sum()
+}
+
+impl DynamicTree {
+    pub fn init(margin: f64) -> DynamicTree {
+        DynamicTree {
+            _nodes: vec![],
+            _root: None,
+            _free: vec![],
+            _margin: margin.abs(),
+            _moved: HashSet::new(),
+            _pairs: HashSet::new(),
+        }
+    }
+
+    pub fn insert(&mut self, shape: Box<dyn IShape>) -> Handle {
+        let bound = self.fatten(shape.get_bound());
+        let leaf = self.allocate(DynamicTreeNode {
+            _bound: bound,
+            _parent: None,
+            _children: None,
+            _height: 0,
+            _shape: Some(shape),
+        });
+        self.insert_leaf(leaf);
+        self._moved.insert(leaf);
+        Handle(leaf)
+    }
+
+    pub fn remove(&mut self, handle: Handle) -> Option<Box<dyn IShape>> {
+        let leaf = handle.0;
+        if !self.is_leaf(leaf) {
+            return None;
+        }
+        self.remove_leaf(leaf);
+        self._moved.remove(&leaf);
+        self._pairs.retain(|x| x.0 != leaf && x.1 != leaf);
+        let shape = self._nodes[leaf]._shape.take();
+        self._free.push(leaf);
+        shape
+    }
+
+    ///updates bound of the shape, returns true if the shape is reinserted due to leaving its fattened bound
+    pub fn update(&mut self, handle: Handle, bound: &dyn IBound) -> bool {
+        let leaf = handle.0;
+        if !self.is_leaf(leaf) {
+            return false;
+        }
+        let tight = AxisAlignedBBox::init_from_bound(bound);
+        if self._nodes[leaf]._bound.contains(&tight) {
+            return false;
+        }
+        self.remove_leaf(leaf);
+        self._nodes[leaf]._bound = self.fatten(bound);
+        self.insert_leaf(leaf);
+        self._moved.insert(leaf);
+        true
+    }
+
+    pub fn get_shape(&self, handle: Handle) -> Option<&dyn IShape> {
+        match self._nodes.get(handle.0) {
+            Some(n) => n._shape.as_ref().map(|x| x.as_ref()),
+            _ => None,
+        }
+    }
+
+    pub fn get_shape_mut(&mut self, handle: Handle) -> Option<&mut Box<dyn IShape>> {
+        match self._nodes.get_mut(handle.0) {
+            Some(n) => n._shape.as_mut(),
+            _ => None,
+        }
+    }
+
+    pub fn get_fat_bound(&self, handle: Handle) -> Option<&AxisAlignedBBox> {
+        if self.is_leaf(handle.0) {
+            Some(&self._nodes[handle.0]._bound)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_height(&self) -> usize {
+        match self._root {
+            Some(n) => self._nodes[n]._height,
+            _ => 0,
+        }
+    }
+
+    ///returns handles of shapes whose fattened bounds intersect the given bound
+    pub fn query_bound(&self, bound: &dyn IBound) -> Vec<Handle> {
+        let mut ret = vec![];
+        let mut stack: Vec<usize> = self._root.into_iter().collect();
+        while let Some(n) = stack.pop() {
+            let node = &self._nodes[n];
+            if !node._bound.intersect(bound) {
+                continue;
+            }
+            match node._children {
+                Some((l, r)) => {
+                    stack.push(l);
+                    stack.push(r);
+                }
+                _ => {
+                    ret.push(Handle(n));
+                }
+            }
+        }
+        ret.sort_unstable();
+        ret
+    }
+
+    ///returns cached pairs of shapes with overlapping fattened bounds, only shapes moved since the last query are searched for new pairs
+    pub fn query_pairs(&mut self) -> Vec<(Handle, Handle)> {
+        let moved: Vec<usize> = self._moved.drain().collect();
+        for i in moved {
+            let candidates = self.query_bound(&self._nodes[i]._bound);
+            for j in candidates {
+                if j.0 != i {
+                    self._pairs
+                        .insert(if i < j.0 { (i, j.0) } else { (j.0, i) });
+                }
+            }
+        }
+        {
+            let nodes = &self._nodes;
+            self._pairs
+                .retain(|x| nodes[x.0]._bound.intersect(&nodes[x.1]._bound));
+        }
+        let mut ret: Vec<(Handle, Handle)> = self
+            ._pairs
+            .iter()
+            .map(|x| (Handle(x.0), Handle(x.1)))
+            .collect();
+        ret.sort_unstable();
+        ret
+    }
+
+    fn is_leaf(&self, n: usize) -> bool {
+        match self._nodes.get(n) {
+            Some(node) => node._shape.is_some(),
+            _ => false,
+        }
+    }
+
+    fn fatten(&self, bound: &dyn IBound) -> AxisAlignedBBox {
+        let mut b = AxisAlignedBBox::init_from_bound(bound);
+        for i in 0..3 {
+            b._bound_lower[i] -= self._margin;
+            b._bound_upper[i] += self._margin;
+        }
+        b
+    }
+
+    fn allocate(&mut self, node: DynamicTreeNode) -> usize {
+        match self._free.pop() {
+            Some(n) => {
+                self._nodes[n] = node;
+                n
+            }
+            _ => {
+                self._nodes.push(node);
+                self._nodes.len() - 1
+            }
+        }
+    }
+
+    fn replace_child(&mut self, parent: Option<usize>, old: usize, new: usize) {
+        match parent {
+            Some(p) => {
+                let (l, r) = self._nodes[p]._children.unwrap();
+                self._nodes[p]._children = if l == old {
+                    Some((new, r))
+                } else {
+                    Some((l, new))
+                };
+            }
+            _ => {
+                self._root = Some(new);
+            }
+        }
+    }
+
+    fn refit(&mut self, n: usize) {
+        let (l, r) = self._nodes[n]._children.unwrap();
+        self._nodes[n]._bound = union(&self._nodes[l]._bound, &self._nodes[r]._bound);
+        self._nodes[n]._height = 1 + self._nodes[l]._height.max(self._nodes[r]._height);
+    }
+
+    fn insert_leaf(&mut self, leaf: usize) {
+        let root = match self._root {
+            Some(n) => n,
+            _ => {
+                self._root = Some(leaf);
+                self._nodes[leaf]._parent = None;
+                return;
+            }
+        };
+
+        //find best sibling by descending towards the child with least cost increase
+        let leaf_bound = self._nodes[leaf]._bound.clone();
+        let mut index = root;
+        while let Some((l, r)) = self._nodes[index]._children {
+            let area = perimeter(&self._nodes[index]._bound);
+            let combined_area = perimeter(&union(&self._nodes[index]._bound, &leaf_bound));
+
+            //cost of creating a new parent for this node and the new leaf
+            let cost = 2f64 * combined_area;
+
+            //minimum cost of pushing the leaf further down the tree
+            let inheritance_cost = 2f64 * (combined_area - area);
+
+            let child_cost = |c: usize| {
+                let b = union(&leaf_bound, &self._nodes[c]._bound);
+                if self._nodes[c]._children.is_none() {
+                    perimeter(&b) + inheritance_cost
+                } else {
+                    perimeter(&b) - perimeter(&self._nodes[c]._bound) + inheritance_cost
+                }
+            };
+            let cost_l = child_cost(l);
+            let cost_r = child_cost(r);
+
+            if cost < cost_l && cost < cost_r {
+                break;
+            }
+            index = if cost_l < cost_r { l } else { r };
+        }
+
+        let sibling = index;
+        let old_parent = self._nodes[sibling]._parent;
+        let new_parent = self.allocate(DynamicTreeNode {
+            _bound: union(&leaf_bound, &self._nodes[sibling]._bound),
+            _parent: old_parent,
+            _children: Some((sibling, leaf)),
+            _height: self._nodes[sibling]._height + 1,
+            _shape: None,
+        });
+        self.replace_child(old_parent, sibling, new_parent);
+        self._nodes[sibling]._parent = Some(new_parent);
+        self._nodes[leaf]._parent = Some(new_parent);
+
+        self.refit_ancestors(Some(new_parent));
+    }
+
+    fn remove_leaf(&mut self, leaf: usize) {
+        if self._root == Some(leaf) {
+            self._root = None;
+            return;
+        }
+        let parent = self._nodes[leaf]._parent.unwrap();
+        let grand_parent = self._nodes[parent]._parent;
+        let sibling = match self._nodes[parent]._children.unwrap() {
+            (l, r) if l == leaf => r,
+            (l, _) => l,
+        };
+
+        self.replace_child(grand_parent, parent, sibling);
+        self._nodes[sibling]._parent = grand_parent;
+        self._nodes[parent]._children = None;
+        self._free.push(parent);
+        self._nodes[leaf]._parent = None;
+
+        self.refit_ancestors(grand_parent);
+    }
+
+    fn refit_ancestors(&mut self, start: Option<usize>) {
+        let mut index = start;
+        while let Some(n) = index {
+            let n = self.balance(n);
+            self.refit(n);
+            index = self._nodes[n]._parent;
+        }
+    }
+
+    ///performs a tree rotation at node a if its subtrees are imbalanced, returns the root of the resulting subtree
+    fn balance(&mut self, a: usize) -> usize {
+        let (b, c) = match self._nodes[a]._children {
+            Some(x) if self._nodes[a]._height >= 2 => x,
+            _ => return a,
+        };
+        let height_b = self._nodes[b]._height as i64;
+        let height_c = self._nodes[c]._height as i64;
+        if height_c - height_b > 1 {
+            self.rotate_up(a, c, b)
+        } else if height_b - height_c > 1 {
+            self.rotate_up(a, b, c)
+        } else {
+            a
+        }
+    }
+
+    ///promotes child node up to replace node a, other is the remaining child of a
+    fn rotate_up(&mut self, a: usize, up: usize, other: usize) -> usize {
+        let (f, g) = self._nodes[up]._children.unwrap();
+        let a_parent = self._nodes[a]._parent;
+
+        self._nodes[up]._parent = a_parent;
+        self._nodes[a]._parent = Some(up);
+        self.replace_child(a_parent, a, up);
+
+        //the taller grand child stays with the promoted node, the other moves under a
+        let (keep, give) = if self._nodes[f]._height > self._nodes[g]._height {
+            (f, g)
+        } else {
+            (g, f)
+        };
+        self._nodes[up]._children = Some((a, keep));
+        self._nodes[a]._children = Some((other, give));
+        self._nodes[give]._parent = Some(a);
+
+        self.refit(a);
+        self.refit(up);
+        up
+    }
+}
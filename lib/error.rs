@@ -0,0 +1,30 @@
+use std::error;
+use std::fmt;
+
+use i_shape::ShapeType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MazthError {
+    ///query is not implemented for the pair of shape types
+    UnsupportedPair(ShapeType, ShapeType),
+    ///input has no well defined result, such as a line of zero length
+    DegenerateInput,
+    ///shape of the given type does not provide a support function required by the query
+    NoSupportFunction(ShapeType),
+}
+
+impl fmt::Display for MazthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MazthError::UnsupportedPair(a, b) => {
+                write!(f, "unsupported shape pair: {:?}, {:?}", a, b)
+            }
+            MazthError::DegenerateInput => write!(f, "degenerate input"),
+            MazthError::NoSupportFunction(a) => {
+                write!(f, "no support function for shape: {:?}", a)
+            }
+        }
+    }
+}
+
+impl error::Error for MazthError {}
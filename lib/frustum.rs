@@ -0,0 +1,312 @@
+use i_bound::{BoundType, IBound};
+use i_shape::{IShape, ShapeType};
+use i_vicinity::IVicinity;
+
+use bound::AxisAlignedBBox;
+use bound_obb::OrientedBBox;
+use bound_sphere::BoundSphere;
+use error::MazthError;
+use intersection::Intersection;
+use mat::{Mat3x1, Mat4};
+use plane::Plane;
+use point::Point3;
+use sphere::Sphere;
+
+///result of a culling test against a frustum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Containment {
+    Inside,
+    Outside,
+    ///crossing the boundary, tests of bounding volumes may report this for volumes just outside of a corner
+    Intersecting,
+}
+
+#[derive(Debug, Clone)]
+pub struct Frustum {
+    ///planes with normals pointing out of the frustum, ordered left, right, bottom, top, near, far
+    pub _planes: [Plane; 6],
+    ///corners of the near face followed by the far face, each ordered left bottom, right bottom, right top, left top
+    pub _corners: [Mat3x1<f64>; 8],
+    pub _bound: AxisAlignedBBox,
+    pub _vicinity: f64,
+}
+
+///plane with outward normal from the coefficients of a x + b y + c z + d >= 0 describing the inside
+fn plane_from_coefficients(c: &[f64; 4]) -> Plane {
+    let n = Mat3x1 {
+        _val: [-c[0], -c[1], -c[2]],
+    };
+    let o = n.scale(c[3] / n.dot(&n).unwrap()).unwrap();
+    Plane::init(&o._val, &n._val)
+}
+
+///point shared by three planes
+fn intersect_planes(a: &Plane, b: &Plane, c: &Plane) -> Mat3x1<f64> {
+    let da = a._offset.dot(&a._normal).unwrap();
+    let db = b._offset.dot(&b._normal).unwrap();
+    let dc = c._offset.dot(&c._normal).unwrap();
+    let bc = b._normal.cross(&c._normal).unwrap();
+    let ca = c._normal.cross(&a._normal).unwrap();
+    let ab = a._normal.cross(&b._normal).unwrap();
+    let denom = a._normal.dot(&bc).unwrap();
+    bc.scale(da)
+        .unwrap()
+        .plus(&ca.scale(db).unwrap())
+        .unwrap()
+        .plus(&ab.scale(dc).unwrap())
+        .unwrap()
+        .scale(1f64 / denom)
+        .unwrap()
+}
+
+fn signed_distance(p: &Plane, x: &Mat3x1<f64>) -> f64 {
+    x.minus(&p._offset).unwrap().dot(&p._normal).unwrap()
+}
+
+impl Frustum {
+    ///frustum of a view projection matrix mapping to clip space with depth in [-w, w],
+    ///planes are extracted by the method of Gribb and Hartmann
+    pub fn init(view_proj: &Mat4<f64>) -> Frustum {
+        let mut rows = [[0f64; 4]; 4];
+        for (i, r) in rows.iter_mut().enumerate() {
+            for (j, x) in r.iter_mut().enumerate() {
+                *x = view_proj.index(i as u64, j as u64);
+            }
+        }
+        let combine = |i: usize, s: f64| {
+            let mut c = [0f64; 4];
+            for (j, x) in c.iter_mut().enumerate() {
+                *x = rows[3][j] + s * rows[i][j];
+            }
+            plane_from_coefficients(&c)
+        };
+        Frustum::init_planes([
+            combine(0, 1f64),
+            combine(0, -1f64),
+            combine(1, 1f64),
+            combine(1, -1f64),
+            combine(2, 1f64),
+            combine(2, -1f64),
+        ])
+    }
+
+    ///perspective frustum at ori looking along forward, fov_y is the full vertical angle in radians
+    pub fn init_perspective(
+        ori: &[f64],
+        forward: &[f64],
+        up: &[f64],
+        fov_y: f64,
+        aspect: f64,
+        near: f64,
+        far: f64,
+    ) -> Frustum {
+        assert!(ori.len() == 3);
+        assert!(forward.len() == 3);
+        assert!(up.len() == 3);
+        assert!(near > 0f64 && far > near);
+        let o = Mat3x1 {
+            _val: [ori[0], ori[1], ori[2]],
+        };
+        let f = Mat3x1 {
+            _val: [forward[0], forward[1], forward[2]],
+        }
+        .normalize()
+        .unwrap();
+        let r = f
+            .cross(&Mat3x1 {
+                _val: [up[0], up[1], up[2]],
+            })
+            .unwrap()
+            .normalize()
+            .unwrap();
+        let u = r.cross(&f).unwrap();
+        let ty = (fov_y / 2f64).tan();
+        let tx = ty * aspect;
+        //side planes pass through the origin, their normals lean back against the forward direction
+        let side = |n: Mat3x1<f64>, t: f64| {
+            Plane::init(&o._val, &n.minus(&f.scale(t).unwrap()).unwrap()._val)
+        };
+        Frustum::init_planes([
+            side(r.scale(-1f64).unwrap(), tx),
+            side(r, tx),
+            side(u.scale(-1f64).unwrap(), ty),
+            side(u, ty),
+            Plane::init(
+                &o.plus(&f.scale(near).unwrap()).unwrap()._val,
+                &f.scale(-1f64).unwrap()._val,
+            ),
+            Plane::init(&o.plus(&f.scale(far).unwrap()).unwrap()._val, &f._val),
+        ])
+    }
+
+    fn init_planes(planes: [Plane; 6]) -> Frustum {
+        let mut corners: [Mat3x1<f64>; 8] = Default::default();
+        for (i, c) in corners.iter_mut().enumerate() {
+            let (x, y) = match i % 4 {
+                0 => (0, 2),
+                1 => (1, 2),
+                2 => (1, 3),
+                _ => (0, 3),
+            };
+            *c = intersect_planes(&planes[x], &planes[y], &planes[4 + i / 4]);
+        }
+        let vals: Vec<f64> = corners.iter().flat_map(|x| x._val.to_vec()).collect();
+        Frustum {
+            _planes: planes,
+            _corners: corners,
+            _bound: AxisAlignedBBox::init(ShapeType::Frustum, &vals),
+            _vicinity: 0.000001f64,
+        }
+    }
+
+    pub fn get_corners(&self) -> [Mat3x1<f64>; 8] {
+        self._corners
+    }
+
+    pub fn cull_point(&self, p: &Point3) -> Containment {
+        self.cull_sphere_at(&p._ori, 0f64)
+    }
+
+    pub fn cull_sphere(&self, s: &Sphere) -> Containment {
+        self.cull_sphere_at(&s._ori, s._radius)
+    }
+
+    pub fn cull_bound_sphere(&self, b: &BoundSphere) -> Containment {
+        let c = Mat3x1 { _val: b._ori };
+        self.cull_sphere_at(&c, b._radius)
+    }
+
+    ///tests the box corners nearest to and furthest from each plane
+    pub fn cull_aabb(&self, b: &AxisAlignedBBox) -> Containment {
+        let mut ret = Containment::Inside;
+        for p in self._planes.iter() {
+            let d = p._offset.dot(&p._normal).unwrap();
+            let mut near = -d;
+            let mut far = -d;
+            for i in 0..3 {
+                let n = p._normal[i];
+                //skipping zero components keeps infinite extents from producing nan
+                if n > 0f64 {
+                    near += n * b._bound_lower[i];
+                    far += n * b._bound_upper[i];
+                } else if n < 0f64 {
+                    near += n * b._bound_upper[i];
+                    far += n * b._bound_lower[i];
+                }
+            }
+            if near > self._vicinity {
+                return Containment::Outside;
+            }
+            if far > -self._vicinity {
+                ret = Containment::Intersecting;
+            }
+        }
+        ret
+    }
+
+    pub fn cull_obb(&self, b: &OrientedBBox) -> Containment {
+        let mut ret = Containment::Inside;
+        for p in self._planes.iter() {
+            let (lower, upper) = b.project(&p._normal._val);
+            let d = p._offset.dot(&p._normal).unwrap();
+            if lower - d > self._vicinity {
+                return Containment::Outside;
+            }
+            if upper - d > -self._vicinity {
+                ret = Containment::Intersecting;
+            }
+        }
+        ret
+    }
+
+    ///dispatches to the test matching the type of bound
+    pub fn cull_bound(&self, b: &dyn IBound) -> Containment {
+        let d = b.get_bound_data();
+        match b.get_type() {
+            BoundType::AxisAlignBox => self.cull_aabb(&AxisAlignedBBox {
+                _bound_lower: [d[0], d[1], d[2]],
+                _bound_upper: [d[3], d[4], d[5]],
+            }),
+            BoundType::Sphere => self.cull_sphere_at(
+                &Mat3x1 {
+                    _val: [d[0], d[1], d[2]],
+                },
+                d[3],
+            ),
+            BoundType::OrientedBox => self.cull_obb(&OrientedBBox::init_from_bound(b)),
+        }
+    }
+
+    fn cull_sphere_at(&self, c: &Mat3x1<f64>, radius: f64) -> Containment {
+        let mut ret = Containment::Inside;
+        for p in self._planes.iter() {
+            let dist = signed_distance(p, c);
+            if dist > radius + self._vicinity {
+                return Containment::Outside;
+            }
+            if dist > -radius - self._vicinity {
+                ret = Containment::Intersecting;
+            }
+        }
+        ret
+    }
+}
+
+impl IShape for Frustum {
+    fn get_shape_data(&self) -> Vec<f64> {
+        self._planes
+            .iter()
+            .flat_map(|x| [x._offset._val, x._normal._val].concat())
+            .collect()
+    }
+    fn get_type(&self) -> ShapeType {
+        ShapeType::Frustum
+    }
+    fn get_bound(&self) -> &dyn IBound {
+        &self._bound
+    }
+    // this shall test for intersection of bounding shapes first before procedding to test intersection using algorithms of higher complexity
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError> {
+        if !self.get_bound().intersect(other.get_bound()) {
+            Ok(Default::default())
+        } else {
+            match other.get_type() {
+                ShapeType::Point => {
+                    let d = other.get_shape_data();
+                    let p = Point3::init(&d[0..3]);
+                    match self.cull_point(&p) {
+                        Containment::Outside => Ok(Default::default()),
+                        _ => Ok(Intersection::init_point(p._ori)),
+                    }
+                }
+                _ => Err(MazthError::UnsupportedPair(
+                    self.get_type(),
+                    other.get_type(),
+                )),
+            }
+        }
+    }
+    fn get_support(&self, v: &Mat3x1<f64>) -> Option<Mat3x1<f64>> {
+        if v.magnitude() != Some(0f64) {
+            let furthest = self
+                ._corners
+                .iter()
+                .map(|x| x.dot(v).unwrap())
+                .enumerate()
+                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+                .unwrap();
+            Some(self._corners[furthest.0])
+        } else {
+            None
+        }
+    }
+}
+
+impl IVicinity<f64> for Frustum {
+    fn set_vicinity(&mut self, epsilon: f64) {
+        self._vicinity = epsilon.abs();
+    }
+    fn within_vicinity(&self, a: f64, b: f64) -> bool {
+        a + self._vicinity >= b && a - self._vicinity <= b
+    }
+}
@@ -1,6 +1,7 @@
 pub enum BoundType {
     AxisAlignBox,
     Sphere,
+    OrientedBox,
 }
 
 pub trait IBound {
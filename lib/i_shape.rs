@@ -1,8 +1,11 @@
+use error::MazthError;
+use intersection::Intersection;
 use mat::Mat3x1;
 
 use i_bound::IBound;
 use i_vicinity::IVicinity;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ShapeType {
     //primitive shapes
     Point,
@@ -11,10 +14,10 @@ pub enum ShapeType {
     Plane,
     Trig,
     Box,
+    OrientedBox,
     Rect,
     TriPrism, //5 facets, 2 triangles, 3 rectangles
     Line,
-    //todo
     Frustum,
     Complex, //custom shapes
 }
@@ -23,8 +26,20 @@ pub trait IShape: IVicinity<f64> {
     fn get_shape_data(&self) -> Vec<f64>;
     fn get_type(&self) -> ShapeType;
     fn get_bound(&self) -> &dyn IBound;
-    //optionally returns a location of intersection of bounding shapes, preferrably closest of such locations
-    fn get_intersect(&self, other: &dyn IShape) -> (bool, Option<Mat3x1<f64>>);
+    //optionally returns a location of intersection of bounding shapes, preferrably closest of such locations,
+    //panics for pairs of shapes that are not supported, see get_intersection for the fallible version
+    fn get_intersect(&self, other: &dyn IShape) -> (bool, Option<Mat3x1<f64>>) {
+        match self.get_intersection(other) {
+            Ok(ret) => (ret._is_intersect, ret.get_point()),
+            Err(e) => panic!("{}", e),
+        }
+    }
+    //returns locations of intersection along with normals, ray parameters, facets and depth where available
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError>;
     //required for gjk intersection test
     fn get_support(&self, v: &Mat3x1<f64>) -> Option<Mat3x1<f64>>;
+    //tells apart custom shapes of type Complex when dispatching, unused by primitive shapes
+    fn get_custom_id(&self) -> u32 {
+        0
+    }
 }
@@ -0,0 +1,261 @@
+//continuous collision detection by conservative advancement, based on Mirtich, Impulse-based Dynamic Simulation of Rigid Body Systems
+
+use bound::AxisAlignedBBox;
+use i_shape::{IShape, ShapeType};
+use intersect_epa;
+use intersect_gjk::{self, SupportPoint};
+use mat::Mat3x1;
+use quat::Quat;
+
+///maximum number of advancement steps
+pub const ITERATION_MAX: usize = 64;
+
+///distance at which shapes are considered to be in contact
+pub const TOLERANCE: f64 = 0.00001f64;
+
+///rigid motion of a shape with constant linear and angular velocity
+#[derive(Debug, Clone, Default)]
+pub struct Motion {
+    pub _velocity: Mat3x1<f64>,
+    ///rotation axis scaled by angular speed in radians per unit time
+    pub _angular_velocity: Mat3x1<f64>,
+    ///center of rotation at time 0
+    pub _center: Mat3x1<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Impact {
+    ///first time of contact
+    pub _time: f64,
+    ///midpoint between the shapes at the time of contact
+    pub _point: Mat3x1<f64>,
+    ///unit contact normal pointing from a towards b
+    pub _normal: Mat3x1<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub enum TimeOfImpact {
+    Impact(Impact),
+    ///iteration limit was reached before contact, shapes are known to stay apart up to this time
+    ///but may still meet before t_max
+    NotConverged(f64),
+}
+
+///separation of two shapes at a point in time, negative if overlapping
+struct Proximity {
+    _distance: f64,
+    _normal: Mat3x1<f64>,
+    _point: Mat3x1<f64>,
+}
+
+impl Motion {
+    pub fn init(velocity: &[f64], angular_velocity: &[f64], center: &[f64]) -> Motion {
+        assert!(velocity.len() == 3);
+        assert!(angular_velocity.len() == 3);
+        assert!(center.len() == 3);
+        Motion {
+            _velocity: Mat3x1 {
+                _val: [velocity[0], velocity[1], velocity[2]],
+            },
+            _angular_velocity: Mat3x1 {
+                _val: [
+                    angular_velocity[0],
+                    angular_velocity[1],
+                    angular_velocity[2],
+                ],
+            },
+            _center: Mat3x1 {
+                _val: [center[0], center[1], center[2]],
+            },
+        }
+    }
+
+    pub fn init_linear(velocity: &[f64]) -> Motion {
+        Motion::init(velocity, &[0f64; 3], &[0f64; 3])
+    }
+
+    ///returns rotation accumulated at time t
+    pub fn get_rotation(&self, t: f64) -> Quat<f64> {
+        let speed = self._angular_velocity.magnitude().unwrap();
+        if speed == 0f64 || t == 0f64 {
+            Quat::<f64>::init()
+        } else {
+            Quat::<f64>::init_from_axis_angle_radian((self._angular_velocity, speed * t))
+        }
+    }
+
+    ///returns location of point p at time t
+    pub fn transform(&self, p: &Mat3x1<f64>, t: f64) -> Mat3x1<f64> {
+        self.get_rotation(t)
+            .rotate_vector(p.minus(&self._center).unwrap())
+            .plus(&self._center)
+            .unwrap()
+            .plus(&self._velocity.scale(t).unwrap())
+            .unwrap()
+    }
+
+    ///returns direction v at time t
+    pub fn rotate(&self, v: &Mat3x1<f64>, t: f64) -> Mat3x1<f64> {
+        self.get_rotation(t).rotate_vector(*v)
+    }
+
+    fn support(&self, shape: &dyn IShape, v: &Mat3x1<f64>, t: f64) -> Option<Mat3x1<f64>> {
+        let local = self.get_rotation(t).conjugate().rotate_vector(*v);
+        let p = shape.get_support(&local)?;
+        Some(self.transform(&p, t))
+    }
+
+    ///upper bound of the speed of any point of the shape along a fixed direction
+    fn get_speed_bound(&self, shape: &dyn IShape, n: &Mat3x1<f64>) -> f64 {
+        let linear = self._velocity.dot(n).unwrap();
+        let angular = self._angular_velocity.magnitude().unwrap();
+        if angular == 0f64 {
+            return linear;
+        }
+        let b = AxisAlignedBBox::init_from_bound(shape.get_bound());
+        let mut radius = 0f64;
+        for i in 0..8 {
+            let corner = Mat3x1 {
+                _val: [
+                    if i & 1 != 0 {
+                        b._bound_upper[0]
+                    } else {
+                        b._bound_lower[0]
+                    },
+                    if i & 2 != 0 {
+                        b._bound_upper[1]
+                    } else {
+                        b._bound_lower[1]
+                    },
+                    if i & 4 != 0 {
+                        b._bound_upper[2]
+                    } else {
+                        b._bound_lower[2]
+                    },
+                ],
+            };
+            radius = radius.max(corner.minus(&self._center).unwrap().magnitude().unwrap());
+        }
+        linear + angular * radius
+    }
+}
+
+///proximity of shape b to the half space bounded by plane a
+fn proximity_plane(
+    a: &dyn IShape,
+    ma: &Motion,
+    b: &dyn IShape,
+    mb: &Motion,
+    t: f64,
+) -> Option<Proximity> {
+    let d = a.get_shape_data();
+    let ori = ma.transform(
+        &Mat3x1 {
+            _val: [d[0], d[1], d[2]],
+        },
+        t,
+    );
+    let n = ma.rotate(
+        &Mat3x1 {
+            _val: [d[3], d[4], d[5]],
+        },
+        t,
+    );
+    let p = mb.support(b, &n.scale(-1f64).unwrap(), t)?;
+    let distance = p.minus(&ori).unwrap().dot(&n).unwrap();
+    let q = p.minus(&n.scale(distance).unwrap()).unwrap();
+    Some(Proximity {
+        _distance: distance,
+        _normal: n,
+        _point: p.midpoint(&q).unwrap(),
+    })
+}
+
+fn proximity(
+    a: &dyn IShape,
+    ma: &Motion,
+    b: &dyn IShape,
+    mb: &Motion,
+    t: f64,
+) -> Option<Proximity> {
+    match (a.get_type(), b.get_type()) {
+        (ShapeType::Plane, ShapeType::Plane) => None,
+        (ShapeType::Plane, _) => proximity_plane(a, ma, b, mb, t),
+        (_, ShapeType::Plane) => proximity_plane(b, mb, a, ma, t).map(|x| Proximity {
+            _normal: x._normal.scale(-1f64).unwrap(),
+            ..x
+        }),
+        _ => {
+            let f = |v: &Mat3x1<f64>| {
+                let pa = ma.support(a, v, t)?;
+                let pb = mb.support(b, &v.scale(-1f64).unwrap(), t)?;
+                Some(SupportPoint {
+                    _v: pa.minus(&pb).unwrap(),
+                    _a: pa,
+                    _b: pb,
+                })
+            };
+            let simplex = intersect_gjk::query_simplex(f, false)?;
+            if let Some(s) = simplex.get_separation() {
+                return Some(Proximity {
+                    _distance: s._distance,
+                    _normal: s._axis,
+                    _point: s._point_a.midpoint(&s._point_b).unwrap(),
+                });
+            }
+            match intersect_epa::query_polytope(&f, &simplex) {
+                Some(p) => Some(Proximity {
+                    _distance: -p._depth,
+                    _normal: p._normal,
+                    _point: p._point_a.midpoint(&p._point_b).unwrap(),
+                }),
+                _ => {
+                    //touching shapes without volume
+                    let s = &simplex._verts[0];
+                    Some(Proximity {
+                        _distance: 0f64,
+                        _normal: Default::default(),
+                        _point: s._a.midpoint(&s._b).unwrap(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+///returns first time of contact within [0, t_max] of shapes a and b moving with ma and mb,
+///returns None if shapes do not meet within the interval or the pair is not supported,
+///NotConverged is returned if the iteration limit is reached before either is known
+pub fn query_time_of_impact(
+    a: &dyn IShape,
+    ma: &Motion,
+    b: &dyn IShape,
+    mb: &Motion,
+    t_max: f64,
+) -> Option<TimeOfImpact> {
+    let mut t = 0f64;
+    for _ in 0..ITERATION_MAX {
+        let p = proximity(a, ma, b, mb, t)?;
+        if p._distance <= TOLERANCE {
+            return Some(TimeOfImpact::Impact(Impact {
+                _time: t,
+                _point: p._point,
+                _normal: p._normal,
+            }));
+        }
+        //advance by a step during which the shapes cannot close the current gap
+        let speed = ma.get_speed_bound(a, &p._normal)
+            + mb.get_speed_bound(b, &p._normal.scale(-1f64).unwrap());
+        if speed <= 0f64 {
+            return None;
+        }
+        let t_next = t + p._distance / speed;
+        if t_next > t_max {
+            return None;
+        }
+        t = t_next;
+    }
+    //shapes may still meet before t_max, report how far they are known to be apart
+    //instead of a miss that would let them tunnel through each other
+    Some(TimeOfImpact::NotConverged(t))
+}
@@ -0,0 +1,249 @@
+//expanding polytope algorithm, based on reference tutorial from http://www.dyn4j.org/2010/05/epa-expanding-polytope-algorithm/
+
+use i_shape::IShape;
+use intersect_gjk::{self, Simplex, SupportPoint};
+use mat::Mat3x1;
+
+///maximum number of polytope expansions before the closest face found so far is returned
+pub const ITERATION_MAX: usize = 128;
+
+#[derive(Debug, Clone)]
+pub struct Penetration {
+    ///distance b has to be translated along _normal to separate the shapes
+    pub _depth: f64,
+    ///unit contact normal pointing from a towards b
+    pub _normal: Mat3x1<f64>,
+    ///deepest point of a inside b
+    pub _point_a: Mat3x1<f64>,
+    ///deepest point of b inside a
+    pub _point_b: Mat3x1<f64>,
+}
+
+#[derive(Debug, Clone)]
+struct Face {
+    _verts: [usize; 3],
+    _normal: Mat3x1<f64>,
+    _distance: f64,
+}
+
+fn init_face(verts: &[SupportPoint], i: usize, j: usize, k: usize) -> Face {
+    let a = verts[i]._v;
+    let n = verts[j]
+        ._v
+        .minus(&a)
+        .unwrap()
+        .cross(&verts[k]._v.minus(&a).unwrap())
+        .unwrap();
+    match n.normalize() {
+        Some(n) if n._val.iter().all(|x| x.is_finite()) => Face {
+            _verts: [i, j, k],
+            _normal: n,
+            _distance: n.dot(&a).unwrap(),
+        },
+        _ => {
+            //degenerate face is never selected as closest
+            Face {
+                _verts: [i, j, k],
+                _normal: Default::default(),
+                _distance: f64::INFINITY,
+            }
+        }
+    }
+}
+
+fn is_independent(verts: &[SupportPoint], p: &Mat3x1<f64>) -> bool {
+    let v: Vec<Mat3x1<f64>> = verts
+        .iter()
+        .map(|x| x._v.minus(&verts[0]._v).unwrap())
+        .collect();
+    let e = p.minus(&verts[0]._v).unwrap();
+    let eps = intersect_gjk::TOLERANCE;
+    match v.len() {
+        1 => e.magnitude().unwrap() > eps,
+        2 => e.cross(&v[1]).unwrap().magnitude().unwrap() > eps * v[1].magnitude().unwrap(),
+        _ => {
+            let n = v[1].cross(&v[2]).unwrap();
+            e.dot(&n).unwrap().abs() > eps * n.magnitude().unwrap()
+        }
+    }
+}
+
+///grows simplex to a tetrahedron, returns false if the minkowski difference is flat
+fn expand_simplex<F>(f: &F, verts: &mut Vec<SupportPoint>) -> bool
+where
+    F: Fn(&Mat3x1<f64>) -> Option<SupportPoint>,
+{
+    let axes = [[1f64, 0f64, 0f64], [0f64, 1f64, 0f64], [0f64, 0f64, 1f64]];
+    while verts.len() < 4 {
+        let mut directions = vec![];
+        match verts.len() {
+            1 => {
+                for a in axes.iter() {
+                    directions.push(Mat3x1 { _val: *a });
+                }
+            }
+            2 => {
+                let e = verts[1]._v.minus(&verts[0]._v).unwrap();
+                for a in axes.iter() {
+                    directions.push(e.cross(&Mat3x1 { _val: *a }).unwrap());
+                }
+            }
+            _ => {
+                let e1 = verts[1]._v.minus(&verts[0]._v).unwrap();
+                let e2 = verts[2]._v.minus(&verts[0]._v).unwrap();
+                directions.push(e1.cross(&e2).unwrap());
+            }
+        }
+        let mut found = None;
+        'search: for d in directions.iter() {
+            if d.magnitude().unwrap() <= 0f64 {
+                continue;
+            }
+            for d in [*d, d.scale(-1f64).unwrap()].iter() {
+                if let Some(p) = f(d) {
+                    if is_independent(&verts[..], &p._v) {
+                        found = Some(p);
+                        break 'search;
+                    }
+                }
+            }
+        }
+        match found {
+            Some(p) => verts.push(p),
+            _ => return false,
+        }
+    }
+    true
+}
+
+///barycentric coordinates of p with respect to triangle a, b, c
+pub fn barycentric(
+    p: &Mat3x1<f64>,
+    a: &Mat3x1<f64>,
+    b: &Mat3x1<f64>,
+    c: &Mat3x1<f64>,
+) -> (f64, f64, f64) {
+    let v0 = b.minus(a).unwrap();
+    let v1 = c.minus(a).unwrap();
+    let v2 = p.minus(a).unwrap();
+    let d00 = v0.dot(&v0).unwrap();
+    let d01 = v0.dot(&v1).unwrap();
+    let d11 = v1.dot(&v1).unwrap();
+    let d20 = v2.dot(&v0).unwrap();
+    let d21 = v2.dot(&v1).unwrap();
+    let denom = d00 * d11 - d01 * d01;
+    if denom == 0f64 {
+        return (1f64, 0f64, 0f64);
+    }
+    let v = (d11 * d20 - d01 * d21) / denom;
+    let w = (d00 * d21 - d01 * d20) / denom;
+    (1f64 - v - w, v, w)
+}
+
+///sums points scaled by their barycentric weights
+pub fn weigh(points: &[Mat3x1<f64>; 3], weights: &[f64; 3]) -> Mat3x1<f64> {
+    let mut p: Mat3x1<f64> = Default::default();
+    for (x, w) in points.iter().zip(weights.iter()) {
+        p = p.plus(&x.scale(*w).unwrap()).unwrap();
+    }
+    p
+}
+
+fn closest_face(faces: &[Face]) -> &Face {
+    let mut closest = &faces[0];
+    for face in faces.iter() {
+        if face._distance < closest._distance {
+            closest = face;
+        }
+    }
+    closest
+}
+
+fn penetration(verts: &[SupportPoint], face: &Face) -> Penetration {
+    let p = face._normal.scale(face._distance).unwrap();
+    let [i, j, k] = face._verts;
+    let (u, v, w) = barycentric(&p, &verts[i]._v, &verts[j]._v, &verts[k]._v);
+    Penetration {
+        _depth: face._distance.max(0f64),
+        _normal: face._normal,
+        _point_a: weigh(&[verts[i]._a, verts[j]._a, verts[k]._a], &[u, v, w]),
+        _point_b: weigh(&[verts[i]._b, verts[j]._b, verts[k]._b], &[u, v, w]),
+    }
+}
+
+///expands the final simplex of an intersecting gjk query over support function f,
+///returns None if the minkowski difference has no volume
+pub fn query_polytope<F>(f: &F, simplex: &Simplex) -> Option<Penetration>
+where
+    F: Fn(&Mat3x1<f64>) -> Option<SupportPoint>,
+{
+    let mut verts = simplex._verts.clone();
+    if !expand_simplex(f, &mut verts) {
+        return None;
+    }
+
+    //orient faces of the initial tetrahedron outwards
+    let mut faces = vec![];
+    for &(i, j, k, l) in [(0, 1, 2, 3), (0, 3, 1, 2), (0, 2, 3, 1), (1, 3, 2, 0)].iter() {
+        let face = init_face(&verts[..], i, j, k);
+        let opposite = verts[l]._v.minus(&verts[i]._v).unwrap();
+        if face._normal.dot(&opposite).unwrap() > 0f64 {
+            faces.push(init_face(&verts[..], i, k, j));
+        } else {
+            faces.push(face);
+        }
+    }
+
+    for _ in 0..ITERATION_MAX {
+        let face = closest_face(&faces[..]).clone();
+        if !face._distance.is_finite() {
+            return None;
+        }
+
+        let p = f(&face._normal)?;
+        let d = p._v.dot(&face._normal).unwrap();
+        if d - face._distance <= intersect_gjk::TOLERANCE {
+            return Some(penetration(&verts[..], &face));
+        }
+
+        //remove faces visible from the new vertex and stitch the horizon edges to it
+        verts.push(p);
+        let index = verts.len() - 1;
+        let mut horizon: Vec<(usize, usize)> = vec![];
+        faces.retain(|x| {
+            let a = verts[x._verts[0]]._v;
+            let is_visible = x._normal.dot(&p._v.minus(&a).unwrap()).unwrap() > 0f64;
+            if is_visible {
+                for e in 0..3 {
+                    let edge = (x._verts[e], x._verts[(e + 1) % 3]);
+                    match horizon.iter().position(|y| *y == (edge.1, edge.0)) {
+                        Some(shared) => {
+                            horizon.remove(shared);
+                        }
+                        _ => horizon.push(edge),
+                    }
+                }
+            }
+            !is_visible
+        });
+        if horizon.is_empty() {
+            return Some(penetration(&verts[..], &face));
+        }
+        for (i, j) in horizon {
+            faces.push(init_face(&verts[..], i, j, index));
+        }
+    }
+
+    //iteration limit reached, report closest face found
+    Some(penetration(&verts[..], closest_face(&faces[..])))
+}
+
+///returns None if shapes do not intersect or do not provide a support function
+pub fn query_penetration(a: &dyn IShape, b: &dyn IShape) -> Option<Penetration> {
+    let f = |v: &Mat3x1<f64>| intersect_gjk::support(a, b, v);
+    let simplex = intersect_gjk::query_simplex(f, true)?;
+    if !simplex._is_intersect {
+        return None;
+    }
+    query_polytope(&f, &simplex)
+}
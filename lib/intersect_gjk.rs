@@ -1,103 +1,337 @@
 //based on reference tutorial from http://www.dyn4j.org/2010/04/gjk-gilbert-johnson-keerthi/
+//simplex reduction follows closest point computations of Real-Time Collision Detection, Ericson, chapter 5
 
-use i_shape::{IShape, ShapeType};
+use error::MazthError;
+use i_shape::IShape;
 use mat::Mat3x1;
 
-fn support(a: &dyn IShape, b: &dyn IShape, v: &Mat3x1<f64>) -> Option<Mat3x1<f64>> {
-    let p0 = match a.get_support(&v) {
-        Some(o) => o,
-        _ => return None,
-    };
+///maximum number of support evaluations before the current estimate is returned
+pub const ITERATION_MAX: usize = 64;
+
+///distance below which shapes are considered to be in contact
+pub const TOLERANCE: f64 = 0.000001f64;
+
+///relative improvement of the distance estimate below which iteration terminates
+const CONVERGENCE: f64 = 0.0000000001f64;
+
+///vertex of the minkowski difference a - b along with the support points it is formed from
+#[derive(Debug, Copy, Clone)]
+pub struct SupportPoint {
+    pub _v: Mat3x1<f64>,
+    pub _a: Mat3x1<f64>,
+    pub _b: Mat3x1<f64>,
+}
+
+///final state of gjk iteration
+#[derive(Debug, Clone)]
+pub struct Simplex {
+    ///vertices of the reduced simplex
+    pub _verts: Vec<SupportPoint>,
+    ///barycentric weights of the point closest to origin over _verts
+    pub _weights: Vec<f64>,
+    ///point of the minkowski difference closest to origin
+    pub _closest: Mat3x1<f64>,
+    pub _is_intersect: bool,
+}
+
+///closest features of separated shapes
+#[derive(Debug, Clone)]
+pub struct Separation {
+    pub _distance: f64,
+    ///point on a closest to b
+    pub _point_a: Mat3x1<f64>,
+    ///point on b closest to a
+    pub _point_b: Mat3x1<f64>,
+    ///unit separating axis pointing from a towards b
+    pub _axis: Mat3x1<f64>,
+}
+
+impl Simplex {
+    ///returns closest features over the simplex of a converged query, None if it contains origin
+    pub fn get_separation(&self) -> Option<Separation> {
+        if self._is_intersect {
+            return None;
+        }
+        let mut point_a: Mat3x1<f64> = Default::default();
+        let mut point_b: Mat3x1<f64> = Default::default();
+        for (x, w) in self._verts.iter().zip(self._weights.iter()) {
+            point_a = point_a.plus(&x._a.scale(*w).unwrap()).unwrap();
+            point_b = point_b.plus(&x._b.scale(*w).unwrap()).unwrap();
+        }
+        let distance = self._closest.magnitude().unwrap();
+        Some(Separation {
+            _distance: distance,
+            _point_a: point_a,
+            _point_b: point_b,
+            _axis: self._closest.scale(-1f64 / distance).unwrap(),
+        })
+    }
+}
+
+///support point of the minkowski difference a - b in direction v
+pub fn support(a: &dyn IShape, b: &dyn IShape, v: &Mat3x1<f64>) -> Option<SupportPoint> {
+    let p0 = a.get_support(v)?;
     let v_oppose = v.scale(-1f64).unwrap();
-    let p1 = match b.get_support(&v_oppose) {
-        Some(o) => o,
-        _ => return None,
-    };
+    let p1 = b.get_support(&v_oppose)?;
     let p10 = p0.minus(&p1).unwrap();
-    Some(p10)
-}
-
-fn pass_minkowski_origin(last_vert: &Mat3x1<f64>, support: &Mat3x1<f64>) -> bool {
-    // println!( "last vert dot product: {}", last_vert.dot( &support ).unwrap() );
-    last_vert.dot(&support).unwrap() > 0f64
-}
-
-fn contains_minkowski_origin(simplex: &mut Vec<Mat3x1<f64>>, support: &mut Mat3x1<f64>) -> bool {
-    let a = simplex.last().unwrap().clone();
-    let ao = a.scale(-1f64).unwrap();
-    if simplex.len() == 3 {
-        //triangle case
-        let b = simplex[1];
-        let c = simplex[0];
-        let ab = b.minus(&a).unwrap();
-        let ac = c.minus(&a).unwrap();
-        let ab_normal = ac.cross(&ab).unwrap().cross(&ab).unwrap();
-        let ac_normal = ab.cross(&ac).unwrap().cross(&ac).unwrap();
-        if ab_normal.dot(&ao).unwrap() > 0f64 {
-            //remove c and set new direction to ab_normal
-            let simplex_new = vec![simplex[1], simplex[2]];
-            *simplex = simplex_new;
-            *support = ab_normal.clone();
-        } else if ac_normal.dot(&ao).unwrap() > 0f64 {
-            //remove b and set new direction to ac_normal
-            let simplex_new = vec![simplex[0], simplex[2]];
-            *simplex = simplex_new.clone();
-            *support = ac_normal.clone();
-        } else {
-            //minkowski origin is enclosed by the triangle
-            return true;
-        }
+    Some(SupportPoint {
+        _v: p10,
+        _a: p0,
+        _b: p1,
+    })
+}
+
+///closest point to origin on segment of verts[i], verts[j], returns kept vertex indices and their weights
+fn closest_segment(verts: &[Mat3x1<f64>], i: usize, j: usize) -> (Vec<usize>, Vec<f64>) {
+    let a = verts[i];
+    let ab = verts[j].minus(&a).unwrap();
+    let denom = ab.dot(&ab).unwrap();
+    if denom <= 0f64 {
+        return (vec![i], vec![1f64]);
+    }
+    let t = -a.dot(&ab).unwrap() / denom;
+    if t <= 0f64 {
+        (vec![i], vec![1f64])
+    } else if t >= 1f64 {
+        (vec![j], vec![1f64])
     } else {
-        //line segment case
-        //set direction towards minkowski origin
-        let b = simplex[0];
-        let ab = b.minus(&a).unwrap();
-        let ab_normal = ab.cross(&ao).unwrap().cross(&ao).unwrap();
-        if ab_normal.magnitude().unwrap() == 0f64 {
-            return true;
-        } else {
-            *support = ab_normal.clone();
+        (vec![i, j], vec![1f64 - t, t])
+    }
+}
+
+///closest point to origin on triangle of verts[i], verts[j], verts[k] by testing its voronoi regions
+pub fn closest_triangle(
+    verts: &[Mat3x1<f64>],
+    i: usize,
+    j: usize,
+    k: usize,
+) -> (Vec<usize>, Vec<f64>) {
+    let a = verts[i];
+    let b = verts[j];
+    let c = verts[k];
+    let ab = b.minus(&a).unwrap();
+    let ac = c.minus(&a).unwrap();
+
+    let ap = a.scale(-1f64).unwrap();
+    let d1 = ab.dot(&ap).unwrap();
+    let d2 = ac.dot(&ap).unwrap();
+    if d1 <= 0f64 && d2 <= 0f64 {
+        return (vec![i], vec![1f64]);
+    }
+
+    let bp = b.scale(-1f64).unwrap();
+    let d3 = ab.dot(&bp).unwrap();
+    let d4 = ac.dot(&bp).unwrap();
+    if d3 >= 0f64 && d4 <= d3 {
+        return (vec![j], vec![1f64]);
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0f64 && d1 >= 0f64 && d3 <= 0f64 {
+        let v = d1 / (d1 - d3);
+        return (vec![i, j], vec![1f64 - v, v]);
+    }
+
+    let cp = c.scale(-1f64).unwrap();
+    let d5 = ab.dot(&cp).unwrap();
+    let d6 = ac.dot(&cp).unwrap();
+    if d6 >= 0f64 && d5 <= d6 {
+        return (vec![k], vec![1f64]);
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0f64 && d2 >= 0f64 && d6 <= 0f64 {
+        let w = d2 / (d2 - d6);
+        return (vec![i, k], vec![1f64 - w, w]);
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0f64 && (d4 - d3) >= 0f64 && (d5 - d6) >= 0f64 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return (vec![j, k], vec![1f64 - w, w]);
+    }
+
+    let denom = va + vb + vc;
+    if denom.abs() <= 0f64 {
+        //degenerate triangle, use the closest of its edges
+        let candidates = [
+            closest_segment(verts, i, j),
+            closest_segment(verts, i, k),
+            closest_segment(verts, j, k),
+        ];
+        return closest_of(verts, &candidates);
+    }
+    let v = vb / denom;
+    let w = vc / denom;
+    (vec![i, j, k], vec![1f64 - v - w, v, w])
+}
+
+///closest point to origin on tetrahedron, returns all vertices if origin is enclosed
+fn closest_tetrahedron(verts: &[Mat3x1<f64>]) -> (Vec<usize>, Vec<f64>) {
+    let faces = [(0, 1, 2, 3), (0, 1, 3, 2), (0, 2, 3, 1), (1, 2, 3, 0)];
+
+    let scale = verts
+        .iter()
+        .map(|x| x.dot(x).unwrap())
+        .fold(0f64, f64::max)
+        .max(1f64);
+
+    let mut candidates = vec![];
+    for &(i, j, k, l) in faces.iter() {
+        let a = verts[i];
+        let n = verts[j]
+            .minus(&a)
+            .unwrap()
+            .cross(&verts[k].minus(&a).unwrap())
+            .unwrap();
+        let sign_origin = -n.dot(&a).unwrap();
+        let sign_opposite = n.dot(&verts[l].minus(&a).unwrap()).unwrap();
+        let is_degenerate = sign_opposite.abs() <= CONVERGENCE * scale * scale.sqrt();
+        if is_degenerate || sign_origin * sign_opposite < 0f64 {
+            //origin is outside of this face
+            candidates.push(closest_triangle(verts, i, j, k));
         }
     }
-    return false;
+
+    if candidates.is_empty() {
+        //origin is enclosed, weights are not needed for the enclosed case
+        return (vec![0, 1, 2, 3], vec![0.25f64; 4]);
+    }
+    closest_of(verts, &candidates)
 }
 
-pub fn query_intersect(a: &dyn IShape, b: &dyn IShape) -> Option<bool> {
-    match (a.get_type(), b.get_type()) {
-        (ShapeType::Sphere, ShapeType::Sphere) => {}
-        //todo
-        // (ShapeType::POINT,ShapeType::BOX) => {},
-        // (ShapeType::BOX,ShapeType::POINT) => {},
-        _ => {
-            panic!("unsupported shape type");
+fn closest_of(
+    verts: &[Mat3x1<f64>],
+    candidates: &[(Vec<usize>, Vec<f64>)],
+) -> (Vec<usize>, Vec<f64>) {
+    let mut best = 0;
+    let mut best_d = f64::INFINITY;
+    for (n, c) in candidates.iter().enumerate() {
+        let p = combine(verts, &c.0, &c.1);
+        let d = p.dot(&p).unwrap();
+        if d < best_d {
+            best_d = d;
+            best = n;
         }
     }
+    candidates[best].clone()
+}
+
+///sums vertices of the given indices scaled by their weights
+pub fn combine(verts: &[Mat3x1<f64>], indices: &[usize], weights: &[f64]) -> Mat3x1<f64> {
+    let mut p: Mat3x1<f64> = Default::default();
+    for (i, w) in indices.iter().zip(weights.iter()) {
+        p = p.plus(&verts[*i].scale(*w).unwrap()).unwrap();
+    }
+    p
+}
+
+///reduces simplex to the smallest sub-simplex containing the point closest to origin
+fn reduce_simplex(simplex: &mut Simplex) {
+    let verts: Vec<Mat3x1<f64>> = simplex._verts.iter().map(|x| x._v).collect();
+    let (indices, weights) = match verts.len() {
+        1 => (vec![0], vec![1f64]),
+        2 => closest_segment(&verts[..], 0, 1),
+        3 => closest_triangle(&verts[..], 0, 1, 2),
+        _ => closest_tetrahedron(&verts[..]),
+    };
+    simplex._is_intersect = indices.len() == 4;
+    simplex._closest = if simplex._is_intersect {
+        Default::default()
+    } else {
+        combine(&verts[..], &indices[..], &weights[..])
+    };
+    simplex._verts = indices.iter().map(|x| simplex._verts[*x]).collect();
+    simplex._weights = weights;
+}
+
+///runs gjk over the minkowski difference given by support function f,
+///stops as soon as shapes are known to be separated if is_early_exit is set, otherwise until the closest point converges
+pub fn query_simplex<F>(f: F, is_early_exit: bool) -> Option<Simplex>
+where
+    F: Fn(&Mat3x1<f64>) -> Option<SupportPoint>,
+{
     //set initial minkowski vertex from an arbitrary support vector
-    let mut d = Mat3x1 {
+    let initial = f(&Mat3x1 {
         _val: [-1f64, 0f64, 0f64],
+    })?;
+    let mut simplex = Simplex {
+        _verts: vec![initial],
+        _weights: vec![1f64],
+        _closest: initial._v,
+        _is_intersect: false,
     };
-    let mut simplex = vec![];
-    {
-        let sup = support(a, b, &d).unwrap();
-        simplex.push(sup);
-    }
-
-    d = d.scale(-1f64).unwrap();
-    loop {
-        // println!( "support vector: {:?}", d );
-        {
-            let sup = support(a, b, &d).unwrap();
-            simplex.push(sup);
+
+    for _ in 0..ITERATION_MAX {
+        let v = simplex._closest;
+        let dist_squared = v.dot(&v).unwrap();
+        if simplex._is_intersect || dist_squared <= TOLERANCE * TOLERANCE {
+            simplex._is_intersect = true;
+            return Some(simplex);
         }
-        assert!(simplex.len() <= 3, "simplex vertices count unexpected");
-        // println!( "simplex len: {}", simplex.len() );
-        if !pass_minkowski_origin(simplex.last().unwrap(), &d) {
-            // println!( "new vert not pass origin" );
-            return Some(false);
-        } else {
-            if contains_minkowski_origin(&mut simplex, &mut d) {
-                return Some(true);
-            }
+
+        //search towards origin
+        let d = v.scale(-1f64).unwrap();
+        let sup = f(&d)?;
+
+        //lower bound of separation distance along d exceeds tolerance
+        let progress = dist_squared + sup._v.dot(&d).unwrap();
+        if is_early_exit && sup._v.dot(&d).unwrap() < -TOLERANCE * dist_squared.sqrt() {
+            return Some(simplex);
         }
+
+        //no further progress towards origin is possible
+        let is_duplicate = simplex._verts.iter().any(|x| {
+            let e = x._v.minus(&sup._v).unwrap();
+            e.dot(&e).unwrap() <= CONVERGENCE * CONVERGENCE
+        });
+        if is_duplicate || progress <= CONVERGENCE * dist_squared {
+            simplex._is_intersect = dist_squared.sqrt() <= TOLERANCE;
+            return Some(simplex);
+        }
+
+        simplex._verts.push(sup);
+        reduce_simplex(&mut simplex);
     }
+
+    //iteration limit reached, report current estimate
+    simplex._is_intersect =
+        simplex._is_intersect || simplex._closest.magnitude().unwrap() <= TOLERANCE;
+    Some(simplex)
+}
+
+///returns an error if either shape does not provide a support function
+pub fn check_support(a: &dyn IShape, b: &dyn IShape) -> Result<(), MazthError> {
+    let v = Mat3x1 {
+        _val: [1f64, 0f64, 0f64],
+    };
+    for x in [a, b].iter() {
+        if x.get_support(&v).is_none() {
+            return Err(MazthError::NoSupportFunction(x.get_type()));
+        }
+    }
+    Ok(())
+}
+
+///returns an error if either shape does not provide a support function
+pub fn query_intersect(a: &dyn IShape, b: &dyn IShape) -> Result<bool, MazthError> {
+    check_support(a, b)?;
+    query_simplex(|v| support(a, b, v), true)
+        .map(|x| x._is_intersect)
+        .ok_or(MazthError::DegenerateInput)
+}
+
+///runs gjk until convergence over support function f and returns closest features,
+///returns None if the minkowski difference contains origin
+pub fn query_separation<F>(f: F) -> Option<Separation>
+where
+    F: Fn(&Mat3x1<f64>) -> Option<SupportPoint>,
+{
+    query_simplex(f, false)?.get_separation()
+}
+
+///returns None if shapes intersect, returns an error if either shape does not provide a support function
+pub fn query_distance(a: &dyn IShape, b: &dyn IShape) -> Result<Option<Separation>, MazthError> {
+    check_support(a, b)?;
+    Ok(query_separation(|v| support(a, b, v)))
 }
@@ -0,0 +1,222 @@
+//minkowski portal refinement, based on XenoCollide from Game Programming Gems 7, Snethen
+
+use i_shape::IShape;
+use intersect_epa::{self, Penetration};
+use intersect_gjk::{self, SupportPoint};
+use mat::Mat3x1;
+
+///maximum number of portal updates in each of the discovery and refinement phases
+pub const ITERATION_MAX: usize = 64;
+
+enum Discovery {
+    Separated,
+    ///origin lies on the segment from the interior point to this support point
+    Origin(SupportPoint),
+    ///interior point followed by the portal vertices, the ray from the interior point through origin passes the portal
+    Portal(Box<[SupportPoint; 4]>),
+}
+
+///point inside the minkowski difference a - b, taken from centroids of support points along the world axes,
+///bound centroids are not used since they may lie outside of or on the boundary of the shape
+fn interior(a: &dyn IShape, b: &dyn IShape) -> Option<SupportPoint> {
+    let centroid = |s: &dyn IShape| {
+        let mut c: Mat3x1<f64> = Default::default();
+        for i in 0..6 {
+            let mut dir: Mat3x1<f64> = Default::default();
+            dir[i / 2] = if i % 2 == 0 { 1f64 } else { -1f64 };
+            c = c.plus(&s.get_support(&dir)?).unwrap();
+        }
+        c.scale(1f64 / 6f64)
+    };
+    let ca = centroid(a)?;
+    let cb = centroid(b)?;
+    let mut v = ca.minus(&cb).unwrap();
+    if v.magnitude().unwrap() <= intersect_gjk::TOLERANCE {
+        //origin must not coincide with the interior point
+        v._val[0] += intersect_gjk::TOLERANCE * 10f64;
+    }
+    Some(SupportPoint {
+        _v: v,
+        _a: ca,
+        _b: cb,
+    })
+}
+
+fn is_behind(p: &SupportPoint, dir: &Mat3x1<f64>) -> bool {
+    p._v.dot(dir).unwrap() < -intersect_gjk::TOLERANCE * dir.magnitude().unwrap()
+}
+
+fn discover<F>(f: &F, v0: SupportPoint) -> Option<Discovery>
+where
+    F: Fn(&Mat3x1<f64>) -> Option<SupportPoint>,
+{
+    let mut dir = v0._v.scale(-1f64).unwrap();
+    let mut v1 = f(&dir)?;
+    if is_behind(&v1, &dir) {
+        return Some(Discovery::Separated);
+    }
+
+    dir = v0._v.cross(&v1._v).unwrap();
+    if dir.magnitude().unwrap() <= 0f64 {
+        return Some(Discovery::Origin(v1));
+    }
+    let mut v2 = f(&dir)?;
+    if is_behind(&v2, &dir) {
+        return Some(Discovery::Separated);
+    }
+
+    dir = v1
+        ._v
+        .minus(&v0._v)
+        .unwrap()
+        .cross(&v2._v.minus(&v0._v).unwrap())
+        .unwrap();
+    if dir.dot(&v0._v).unwrap() > 0f64 {
+        std::mem::swap(&mut v1, &mut v2);
+        dir = dir.scale(-1f64).unwrap();
+    }
+
+    let mut v3 = f(&dir)?;
+    for _ in 0..ITERATION_MAX {
+        if is_behind(&v3, &dir) {
+            return Some(Discovery::Separated);
+        }
+        if v1._v.cross(&v3._v).unwrap().dot(&v0._v).unwrap() < 0f64 {
+            //origin is outside of plane v0, v1, v3
+            v2 = v3;
+            dir = v1
+                ._v
+                .minus(&v0._v)
+                .unwrap()
+                .cross(&v3._v.minus(&v0._v).unwrap())
+                .unwrap();
+        } else if v3._v.cross(&v2._v).unwrap().dot(&v0._v).unwrap() < 0f64 {
+            //origin is outside of plane v0, v3, v2
+            v1 = v3;
+            dir = v3
+                ._v
+                .minus(&v0._v)
+                .unwrap()
+                .cross(&v2._v.minus(&v0._v).unwrap())
+                .unwrap();
+        } else {
+            break;
+        }
+        v3 = f(&dir)?;
+    }
+    Some(Discovery::Portal(Box::new([v0, v1, v2, v3])))
+}
+
+fn portal_normal(p: &[SupportPoint; 4]) -> Option<Mat3x1<f64>> {
+    let n = p[2]
+        ._v
+        .minus(&p[1]._v)
+        .unwrap()
+        .cross(&p[3]._v.minus(&p[1]._v).unwrap())
+        .unwrap();
+    if n.magnitude().unwrap() <= 0f64 {
+        return None;
+    }
+    n.normalize()
+}
+
+///replaces a portal vertex with v4 keeping the ray from the interior point through origin inside the portal
+fn expand_portal(p: &mut [SupportPoint; 4], v4: SupportPoint) {
+    let v4v0 = v4._v.cross(&p[0]._v).unwrap();
+    if p[1]._v.dot(&v4v0).unwrap() > 0f64 {
+        if p[2]._v.dot(&v4v0).unwrap() > 0f64 {
+            p[1] = v4;
+        } else {
+            p[3] = v4;
+        }
+    } else if p[3]._v.dot(&v4v0).unwrap() > 0f64 {
+        p[2] = v4;
+    } else {
+        p[1] = v4;
+    }
+}
+
+///moves portal towards the boundary of the minkowski difference, returns whether origin is inside.
+///refinement stops as soon as origin is known to be inside unless is_converge is set
+fn refine<F>(f: &F, p: &mut [SupportPoint; 4], is_converge: bool) -> Option<bool>
+where
+    F: Fn(&Mat3x1<f64>) -> Option<SupportPoint>,
+{
+    for _ in 0..ITERATION_MAX {
+        let n = match portal_normal(p) {
+            Some(n) => n,
+            //ray through origin passes a degenerate portal
+            _ => return Some(true),
+        };
+        let is_hit = n.dot(&p[1]._v).unwrap() >= -intersect_gjk::TOLERANCE;
+        if is_hit && !is_converge {
+            return Some(true);
+        }
+        let v4 = f(&n)?;
+        let d = v4._v.dot(&n).unwrap();
+        if !is_hit && d < -intersect_gjk::TOLERANCE {
+            return Some(false);
+        }
+        if d - n.dot(&p[1]._v).unwrap() <= intersect_gjk::TOLERANCE {
+            return Some(is_hit);
+        }
+        expand_portal(p, v4);
+    }
+    match portal_normal(p) {
+        Some(n) => Some(n.dot(&p[1]._v).unwrap() >= -intersect_gjk::TOLERANCE),
+        _ => Some(true),
+    }
+}
+
+///returns None if either shape does not provide a support function
+pub fn query_intersect(a: &dyn IShape, b: &dyn IShape) -> Option<bool> {
+    let f = |v: &Mat3x1<f64>| intersect_gjk::support(a, b, v);
+    match discover(&f, interior(a, b)?)? {
+        Discovery::Separated => Some(false),
+        Discovery::Origin(_) => Some(true),
+        Discovery::Portal(mut p) => refine(&f, &mut p, false),
+    }
+}
+
+///returns approximate penetration with the contact normal taken from the portal the origin ray exits through,
+///returns None if shapes do not intersect or do not provide a support function
+pub fn query_penetration(a: &dyn IShape, b: &dyn IShape) -> Option<Penetration> {
+    let f = |v: &Mat3x1<f64>| intersect_gjk::support(a, b, v);
+    let v0 = interior(a, b)?;
+    match discover(&f, v0)? {
+        Discovery::Separated => None,
+        Discovery::Origin(v1) => {
+            let depth = v1._v.magnitude().unwrap();
+            let d = v1._v.minus(&v0._v).unwrap();
+            let normal = if d.magnitude().unwrap() <= intersect_gjk::TOLERANCE {
+                //support points coincide, fall back to an arbitrary axis
+                Mat3x1 {
+                    _val: [-1f64, 0f64, 0f64],
+                }
+            } else {
+                d.normalize().unwrap()
+            };
+            Some(Penetration {
+                _depth: depth,
+                _normal: normal,
+                _point_a: v1._a,
+                _point_b: v1._b,
+            })
+        }
+        Discovery::Portal(mut p) => {
+            if !refine(&f, &mut p, true)? {
+                return None;
+            }
+            let n = portal_normal(&p)?;
+            let depth = n.dot(&p[1]._v).unwrap();
+            let (u, v, w) =
+                intersect_epa::barycentric(&n.scale(depth).unwrap(), &p[1]._v, &p[2]._v, &p[3]._v);
+            Some(Penetration {
+                _depth: depth.max(0f64),
+                _normal: n,
+                _point_a: intersect_epa::weigh(&[p[1]._a, p[2]._a, p[3]._a], &[u, v, w]),
+                _point_b: intersect_epa::weigh(&[p[1]._b, p[2]._b, p[3]._b], &[u, v, w]),
+            })
+        }
+    }
+}
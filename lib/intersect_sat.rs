@@ -0,0 +1,411 @@
+//separating axis test for convex polyhedra, candidate axes are face normals of both shapes and cross products of their edges
+
+use i_shape::{IShape, ShapeType};
+use intersection::{Hit, Intersection};
+use mat::Mat3x1;
+
+///tolerance for touching contact and for preferring face axes over nearly equivalent edge axes
+pub const TOLERANCE: f64 = 0.000001f64;
+
+///convex polyhedron with face vertex loops in counter clockwise order when viewed from outside
+#[derive(Debug, Clone)]
+pub struct Polyhedron {
+    pub _verts: Vec<Mat3x1<f64>>,
+    pub _faces: Vec<Vec<usize>>,
+    ///outward unit normal of each face
+    pub _normals: Vec<Mat3x1<f64>>,
+    ///unique edges as pairs of vertex indices
+    pub _edges: Vec<(usize, usize)>,
+}
+
+///feature whose axis gives the minimum overlap or the maximum separation
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Feature {
+    ///face index of a
+    FaceA(usize),
+    ///face index of b
+    FaceB(usize),
+    ///edge indices of a and b
+    Edge(usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Overlap {
+    pub _is_intersect: bool,
+    ///overlap along _normal, negative for separated shapes
+    pub _depth: f64,
+    ///unit axis pointing from a towards b, translating b by _normal * _depth separates the shapes
+    pub _normal: Mat3x1<f64>,
+    pub _feature: Feature,
+    ///point inside both shapes for intersecting shapes
+    pub _point: Option<Mat3x1<f64>>,
+}
+
+impl Polyhedron {
+    ///builds polyhedron from loops of vertex indices in any consistent cyclic order,
+    ///loops are reoriented to be counter clockwise around the outward normal and loops of zero area are dropped
+    pub fn init(verts: Vec<Mat3x1<f64>>, faces: Vec<Vec<usize>>) -> Polyhedron {
+        let mut kept = vec![];
+        let mut normals: Vec<Mat3x1<f64>> = vec![];
+        let mut edges = vec![];
+        for mut f in faces.into_iter() {
+            //newell's method for the loop normal
+            let mut n: Mat3x1<f64> = Default::default();
+            for i in 0..f.len() {
+                let a = verts[f[i]];
+                let b = verts[f[(i + 1) % f.len()]];
+                n = n.plus(&a.cross(&b).unwrap()).unwrap();
+            }
+            let m = n.magnitude().unwrap();
+            if m <= TOLERANCE || !m.is_finite() {
+                //zero area face has no normal, its edges are shared with the remaining faces,
+                //faces of shapes built from a collinear base have no finite vertices to span a normal
+                continue;
+            }
+            let mut n = n.scale(1f64 / m).unwrap();
+            //orient away from the vertex furthest from the face plane, faces of a flat polyhedron
+            //share the plane with every vertex and are oriented opposite to a face already seen
+            let furthest = verts
+                .iter()
+                .map(|x| x.minus(&verts[f[0]]).unwrap().dot(&n).unwrap())
+                .fold(0f64, |acc, x| if x.abs() > acc.abs() { x } else { acc });
+            let is_inward = if furthest.abs() > TOLERANCE {
+                furthest > 0f64
+            } else {
+                normals
+                    .iter()
+                    .any(|x| x.dot(&n).unwrap() > 1f64 - TOLERANCE)
+            };
+            if is_inward {
+                f.reverse();
+                n = n.scale(-1f64).unwrap();
+            }
+            normals.push(n);
+            for i in 0..f.len() {
+                let (a, b) = (f[i], f[(i + 1) % f.len()]);
+                let e = if a < b { (a, b) } else { (b, a) };
+                if !edges.contains(&e) {
+                    edges.push(e);
+                }
+            }
+            kept.push(f);
+        }
+        Polyhedron {
+            _verts: verts,
+            _faces: kept,
+            _normals: normals,
+            _edges: edges,
+        }
+    }
+
+    ///returns None for shapes that are not polyhedra
+    pub fn init_from_shape(shape: &dyn IShape) -> Option<Polyhedron> {
+        let d = shape.get_shape_data();
+        match shape.get_type() {
+            ShapeType::Box | ShapeType::OrientedBox => {
+                let axes: Vec<Mat3x1<f64>> = (0..3)
+                    .map(|k| match shape.get_type() {
+                        ShapeType::Box => {
+                            let mut x: Mat3x1<f64> = Default::default();
+                            x[k] = 1f64;
+                            x
+                        }
+                        _ => Mat3x1 {
+                            _val: [d[6 + k * 3], d[7 + k * 3], d[8 + k * 3]],
+                        },
+                    })
+                    .collect();
+                //vertex i is offset in positive direction along axis k if bit k of i is set
+                let verts = (0..8)
+                    .map(|i| {
+                        let mut v = Mat3x1 {
+                            _val: [d[0], d[1], d[2]],
+                        };
+                        for (k, a) in axes.iter().enumerate() {
+                            let s = if i & (1 << k) != 0 {
+                                d[3 + k]
+                            } else {
+                                -d[3 + k]
+                            };
+                            v = v.plus(&a.scale(s).unwrap()).unwrap();
+                        }
+                        v
+                    })
+                    .collect();
+                let mut faces = vec![];
+                for axis in 0..3 {
+                    let b = 1 << ((axis + 1) % 3);
+                    let c = 1 << ((axis + 2) % 3);
+                    for side in [0, 1 << axis].iter() {
+                        faces.push(vec![*side, side | b, side | b | c, side | c]);
+                    }
+                }
+                Some(Polyhedron::init(verts, faces))
+            }
+            ShapeType::Trig => {
+                let verts = (0..3)
+                    .map(|i| Mat3x1 {
+                        _val: [d[i * 3], d[i * 3 + 1], d[i * 3 + 2]],
+                    })
+                    .collect();
+                //both sides of the triangle
+                Some(Polyhedron::init(verts, vec![vec![0, 1, 2], vec![2, 1, 0]]))
+            }
+            ShapeType::TriPrism => {
+                let base: Vec<Mat3x1<f64>> = (0..3)
+                    .map(|i| Mat3x1 {
+                        _val: [d[i * 3], d[i * 3 + 1], d[i * 3 + 2]],
+                    })
+                    .collect();
+                let h = Mat3x1 {
+                    _val: [d[9], d[10], d[11]],
+                };
+                let mut verts = base.clone();
+                verts.extend(base.iter().map(|x| x.plus(&h).unwrap()));
+                let mut faces = vec![vec![0, 1, 2], vec![3, 4, 5]];
+                for i in 0..3 {
+                    let j = (i + 1) % 3;
+                    faces.push(vec![i, j, j + 3, i + 3]);
+                }
+                Some(Polyhedron::init(verts, faces))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn get_support(&self, v: &Mat3x1<f64>) -> Mat3x1<f64> {
+        let mut best = self._verts[0];
+        let mut best_d = f64::NEG_INFINITY;
+        for x in self._verts.iter() {
+            let d = x.dot(v).unwrap();
+            if d > best_d {
+                best_d = d;
+                best = *x;
+            }
+        }
+        best
+    }
+
+    ///returns a point and an outward normal of the planes bounding a flat polyhedron across its edges,
+    ///empty if the polyhedron has volume
+    pub fn get_sides(&self) -> Vec<(Mat3x1<f64>, Mat3x1<f64>)> {
+        let n = match self._normals.first() {
+            Some(x) => x,
+            _ => return vec![],
+        };
+        if self
+            ._normals
+            .iter()
+            .any(|x| x.dot(n).unwrap().abs() < 1f64 - TOLERANCE)
+        {
+            return vec![];
+        }
+        //edges of a counter clockwise loop have their outside to the right of n
+        let f = &self._faces[0];
+        (0..f.len())
+            .filter_map(|i| {
+                let a = self._verts[f[i]];
+                let x = self._verts[f[(i + 1) % f.len()]]
+                    .minus(&a)
+                    .unwrap()
+                    .cross(n)
+                    .unwrap();
+                let m = x.magnitude().unwrap();
+                if m <= TOLERANCE {
+                    None
+                } else {
+                    Some((a, x.scale(1f64 / m).unwrap()))
+                }
+            })
+            .collect()
+    }
+
+    ///returns extents of the polyhedron projected onto axis
+    pub fn project(&self, axis: &Mat3x1<f64>) -> (f64, f64) {
+        self._verts
+            .iter()
+            .map(|x| x.dot(axis).unwrap())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |acc, x| {
+                (acc.0.min(x), acc.1.max(x))
+            })
+    }
+
+    ///returns the part of segment p0, p1 inside the polyhedron, faces are expanded by TOLERANCE
+    pub fn clip_segment(
+        &self,
+        p0: &Mat3x1<f64>,
+        p1: &Mat3x1<f64>,
+    ) -> Option<(Mat3x1<f64>, Mat3x1<f64>)> {
+        let d = p1.minus(p0).unwrap();
+        let mut t0 = 0f64;
+        let mut t1 = 1f64;
+        let planes = self
+            ._faces
+            .iter()
+            .zip(self._normals.iter())
+            .map(|(f, n)| (self._verts[f[0]], *n))
+            .chain(self.get_sides());
+        for (x, n) in planes {
+            let n = &n;
+            let num = x.minus(p0).unwrap().dot(n).unwrap() + TOLERANCE;
+            let den = d.dot(n).unwrap();
+            if den == 0f64 {
+                if num < 0f64 {
+                    return None;
+                }
+            } else if den < 0f64 {
+                t0 = t0.max(num / den);
+            } else {
+                t1 = t1.min(num / den);
+            }
+            if t0 > t1 {
+                return None;
+            }
+        }
+        Some((
+            p0.plus(&d.scale(t0).unwrap()).unwrap(),
+            p0.plus(&d.scale(t1).unwrap()).unwrap(),
+        ))
+    }
+}
+
+impl Overlap {
+    ///returns intersection as seen from a, with the normal pointing from b towards a
+    pub fn get_intersection(&self) -> Intersection {
+        if !self._is_intersect {
+            return Default::default();
+        }
+        let (feature, feature_other) = match self._feature {
+            Feature::FaceA(i) => (Some(i), None),
+            Feature::FaceB(i) => (None, Some(i)),
+            Feature::Edge(_, _) => (None, None),
+        };
+        Intersection {
+            _is_intersect: true,
+            _hits: self
+                ._point
+                .iter()
+                .map(|x| Hit {
+                    _point: *x,
+                    _normal: Some(self._normal.scale(-1f64).unwrap()),
+                    _t: None,
+                    _feature: feature,
+                    _feature_other: feature_other,
+                })
+                .collect(),
+            _depth: Some(self._depth),
+        }
+    }
+}
+
+///returns average of the edges of each polyhedron clipped by the other, which lies in the intersection of both
+fn contact_point(a: &Polyhedron, b: &Polyhedron) -> Option<Mat3x1<f64>> {
+    let mut sum: Mat3x1<f64> = Default::default();
+    let mut count = 0;
+    for (p, other) in [(a, b), (b, a)].iter() {
+        for e in p._edges.iter() {
+            if let Some((c0, c1)) = other.clip_segment(&p._verts[e.0], &p._verts[e.1]) {
+                sum = sum.plus(&c0).unwrap().plus(&c1).unwrap();
+                count += 2;
+            }
+        }
+    }
+    if count == 0 {
+        None
+    } else {
+        sum.scale(1f64 / count as f64)
+    }
+}
+
+///returns overlap along the axis oriented from a towards b
+fn test_axis(a: &Polyhedron, b: &Polyhedron, axis: &Mat3x1<f64>) -> (f64, Mat3x1<f64>) {
+    let (a_min, a_max) = a.project(axis);
+    let (b_min, b_max) = b.project(axis);
+    let forward = a_max - b_min;
+    let backward = b_max - a_min;
+    if forward <= backward {
+        (forward, *axis)
+    } else {
+        (backward, axis.scale(-1f64).unwrap())
+    }
+}
+
+///tests all candidate axes of two polyhedra, returns None if neither polyhedron has a face or edge axis
+pub fn query_polyhedron(a: &Polyhedron, b: &Polyhedron) -> Option<Overlap> {
+    let mut best: Option<(f64, Mat3x1<f64>, Feature)> = None;
+    let mut consider = |depth: f64, normal: Mat3x1<f64>, feature: Feature, bias: f64| {
+        let is_better = match best {
+            Some((d, _, _)) => depth + bias < d,
+            _ => true,
+        };
+        if is_better {
+            best = Some((depth, normal, feature));
+        }
+    };
+    //face axes are tested one sided as the other shape has to lie outside of the face plane to be separated by it
+    for (i, n) in a._normals.iter().enumerate() {
+        let depth = a.project(n).1 - b.project(n).0;
+        consider(depth, *n, Feature::FaceA(i), 0f64);
+    }
+    for (i, n) in b._normals.iter().enumerate() {
+        let depth = b.project(n).1 - a.project(n).0;
+        consider(depth, n.scale(-1f64).unwrap(), Feature::FaceB(i), TOLERANCE);
+    }
+    //sides of flat polyhedra only separate, overlaps along them are covered by the axes above
+    for n in a.get_sides().iter().map(|x| x.1) {
+        let depth = a.project(&n).1 - b.project(&n).0;
+        if depth < -TOLERANCE {
+            consider(depth, n, Feature::FaceA(0), 0f64);
+        }
+    }
+    for n in b.get_sides().iter().map(|x| x.1) {
+        let depth = b.project(&n).1 - a.project(&n).0;
+        if depth < -TOLERANCE {
+            consider(depth, n.scale(-1f64).unwrap(), Feature::FaceB(0), 0f64);
+        }
+    }
+    for (i, ea) in a._edges.iter().enumerate() {
+        let da = a._verts[ea.1].minus(&a._verts[ea.0]).unwrap();
+        for (j, eb) in b._edges.iter().enumerate() {
+            let db = b._verts[eb.1].minus(&b._verts[eb.0]).unwrap();
+            let axis = da.cross(&db).unwrap();
+            let m = axis.magnitude().unwrap();
+            if m <= TOLERANCE * da.magnitude().unwrap() * db.magnitude().unwrap() {
+                //parallel edges are covered by face axes
+                continue;
+            }
+            let (depth, normal) = test_axis(a, b, &axis.scale(1f64 / m).unwrap());
+            consider(depth, normal, Feature::Edge(i, j), TOLERANCE);
+        }
+    }
+
+    let (depth, normal, feature) = best?;
+    let is_intersect = depth >= -TOLERANCE;
+    let point = if is_intersect {
+        match contact_point(a, b) {
+            Some(p) => Some(p),
+            _ => {
+                //touching within tolerance
+                let pa = a.get_support(&normal);
+                let pb = b.get_support(&normal.scale(-1f64).unwrap());
+                pa.plus(&pb).unwrap().scale(0.5f64)
+            }
+        }
+    } else {
+        None
+    };
+    Some(Overlap {
+        _is_intersect: is_intersect,
+        _depth: depth,
+        _normal: normal,
+        _feature: feature,
+        _point: point,
+    })
+}
+
+///returns None if either shape is not a polyhedron or both are degenerate
+pub fn query_intersect(a: &dyn IShape, b: &dyn IShape) -> Option<Overlap> {
+    let pa = Polyhedron::init_from_shape(a)?;
+    let pb = Polyhedron::init_from_shape(b)?;
+    query_polyhedron(&pa, &pb)
+}
@@ -0,0 +1,68 @@
+use mat::Mat3x1;
+
+///location of contact between two shapes
+#[derive(Debug, Clone, Default)]
+pub struct Hit {
+    pub _point: Mat3x1<f64>,
+    ///unit normal pointing from the other shape towards self, this is the outward surface normal of the other shape where it has one
+    pub _normal: Option<Mat3x1<f64>>,
+    ///parameter of the point along the ray or line taking part in the query, normalized to [0, 1] for lines
+    pub _t: Option<f64>,
+    ///facet index of self carrying the point
+    pub _feature: Option<usize>,
+    ///facet index of the other shape carrying the point
+    pub _feature_other: Option<usize>,
+}
+
+///result of an intersection query of self against another shape
+#[derive(Debug, Clone, Default)]
+pub struct Intersection {
+    pub _is_intersect: bool,
+    ///contact locations ordered by increasing ray parameter where there is one, the first is the preferred location
+    pub _hits: Vec<Hit>,
+    ///penetration depth for overlapping volumes
+    pub _depth: Option<f64>,
+}
+
+impl Hit {
+    pub fn init(point: Mat3x1<f64>) -> Hit {
+        Hit {
+            _point: point,
+            ..Default::default()
+        }
+    }
+
+    ///returns the hit as seen from the other shape
+    pub fn flip(self) -> Hit {
+        Hit {
+            _normal: self._normal.map(|x| x.scale(-1f64).unwrap()),
+            _feature: self._feature_other,
+            _feature_other: self._feature,
+            ..self
+        }
+    }
+}
+
+impl Intersection {
+    ///intersection with a single location and no further information
+    pub fn init_point(point: Mat3x1<f64>) -> Intersection {
+        Intersection {
+            _is_intersect: true,
+            _hits: vec![Hit::init(point)],
+            _depth: None,
+        }
+    }
+
+    ///returns the intersection as seen from the other shape
+    pub fn flip(self) -> Intersection {
+        Intersection {
+            _hits: self._hits.into_iter().map(|x| x.flip()).collect(),
+            ..self
+        }
+    }
+
+    ///returns the preferred location of intersection
+    pub fn get_point(&self) -> Option<Mat3x1<f64>> {
+        self._hits.first().map(|x| x._point)
+    }
+}
@@ -3,6 +3,8 @@ use i_shape::{IShape, ShapeType};
 use i_vicinity::IVicinity;
 
 use bound::AxisAlignedBBox;
+use error::MazthError;
+use intersection::Intersection;
 use mat::Mat3x1;
 
 #[derive(Debug, Clone)]
@@ -80,20 +82,23 @@ impl IShape for Line3 {
         &self._bound
     }
     // this shall test for intersection of bounding shapes first before procedding to test intersection using algorithms of higher complexity
-    fn get_intersect(&self, other: &dyn IShape) -> (bool, Option<Mat3x1<f64>>) {
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError> {
         if !self.get_bound().intersect(other.get_bound()) {
-            return (false, None);
+            return Ok(Default::default());
         } else {
             match other.get_type() {
-                ShapeType::TriPrism => other.get_intersect(self),
-                _ => {
-                    unimplemented!();
+                ShapeType::TriPrism | ShapeType::Trig | ShapeType::Rect => {
+                    other.get_intersection(self).map(|x| x.flip())
                 }
+                _ => Err(MazthError::UnsupportedPair(
+                    self.get_type(),
+                    other.get_type(),
+                )),
             }
         }
     }
     fn get_support(&self, _v: &Mat3x1<f64>) -> Option<Mat3x1<f64>> {
-        unimplemented!();
+        None
     }
 }
 
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 #[allow(unused_imports)]
 use std::f32;
 use std::f64;
@@ -431,6 +432,15 @@ macro_rules! define_mat3x1 {
                     ],
                 })
             }
+            pub fn midpoint(&self, other: &Self) -> Option<Self> {
+                self.plus(other).unwrap().scale(0.5 as $v_type)
+            }
+            ///takes the first 3 values of d
+            pub fn init_from_slice(d: &[$v_type]) -> Self {
+                Mat3x1 {
+                    _val: [d[0], d[1], d[2]],
+                }
+            }
             // pub fn is_equal( & self, other: & Self, error: $v_type ) -> Option< bool > {
             //     Some( ( self._val[0] as $v_type >= other._val[0] as $v_type - error ) &&
             //             ( self._val[0] as $v_type <= other._val[0] as $v_type + error ) &&
@@ -1175,6 +1185,66 @@ macro_rules! define_mat3 {
 
                 Some(out)
             }
+            ///eigen decomposition of a symmetric matrix by cyclic jacobi rotations, returns eigenvalues
+            ///in decreasing order and a matrix holding the corresponding unit eigenvectors as columns
+            pub fn eigen_symmetric(&self) -> ([$v_type; 3], Mat3<$v_type>) {
+                let mut a = [[0 as $v_type; 3]; 3];
+                let mut v = [[0 as $v_type; 3]; 3];
+                for i in 0..3 {
+                    for j in 0..3 {
+                        a[i][j] = self.index(i as u64, j as u64);
+                    }
+                    v[i][i] = 1 as $v_type;
+                }
+                for _ in 0..50 {
+                    let off = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+                    if off == 0 as $v_type {
+                        break;
+                    }
+                    for (p, q) in [(0, 1), (0, 2), (1, 2)].iter().cloned() {
+                        if a[p][q] == 0 as $v_type {
+                            continue;
+                        }
+                        //rotation in the p, q plane annihilating a[p][q]
+                        let theta = (a[q][q] - a[p][p]) / (2 as $v_type * a[p][q]);
+                        let t =
+                            theta.signum() / (theta.abs() + (theta * theta + 1 as $v_type).sqrt());
+                        let c = 1 as $v_type / (t * t + 1 as $v_type).sqrt();
+                        let s = t * c;
+                        for k in 0..3 {
+                            let (x, y) = (a[k][p], a[k][q]);
+                            a[k][p] = c * x - s * y;
+                            a[k][q] = s * x + c * y;
+                        }
+                        for k in 0..3 {
+                            let (x, y) = (a[p][k], a[q][k]);
+                            a[p][k] = c * x - s * y;
+                            a[q][k] = s * x + c * y;
+                        }
+                        for row in v.iter_mut() {
+                            let (x, y) = (row[p], row[q]);
+                            row[p] = c * x - s * y;
+                            row[q] = s * x + c * y;
+                        }
+                    }
+                }
+                let mut order = [0usize, 1, 2];
+                order.sort_by(|x, y| a[*y][*y].partial_cmp(&a[*x][*x]).unwrap_or(Ordering::Equal));
+                let mut vectors = Mat3::<$v_type>::iden();
+                for i in 0..3 {
+                    for (j, o) in order.iter().enumerate() {
+                        *vectors.index_mut(i as u64, j as u64) = v[i][*o];
+                    }
+                }
+                (
+                    [
+                        a[order[0]][order[0]],
+                        a[order[1]][order[1]],
+                        a[order[2]][order[2]],
+                    ],
+                    vectors,
+                )
+            }
         }
         impl i_comparable::IComparableError<$v_type> for Mat3<$v_type> {
             fn is_equal(&self, other: &Self, error: $v_type) -> Option<bool> {
@@ -1,3 +1,4 @@
+pub mod error;
 pub mod i_bound;
 pub mod i_comparable;
 pub mod i_intersect;
@@ -9,18 +10,37 @@ pub mod mat;
 pub mod quat;
 
 pub mod bound;
+pub mod bound_obb;
 pub mod bound_sphere;
+pub mod contact;
+pub mod dispatch;
+pub mod intersection;
 
+pub mod bvh;
+pub mod dynamic_tree;
+pub mod octree;
+pub mod spatial_hash;
+pub mod sweep_prune;
+
+pub mod frustum;
 pub mod line;
+pub mod obox;
 pub mod plane;
 pub mod point;
 ///shape implementations
 pub mod ray;
 pub mod rbox;
+pub mod rect;
 pub mod sphere;
+pub mod sweep;
+pub mod triangle;
 pub mod triprism;
 
+pub mod intersect_ccd;
+pub mod intersect_epa;
 pub mod intersect_gjk;
+pub mod intersect_mpr;
+pub mod intersect_sat;
 
 #[cfg(test)]
 mod test;
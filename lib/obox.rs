@@ -0,0 +1,295 @@
+use std::f64;
+
+use i_bound::IBound;
+use i_shape::{IShape, ShapeType};
+use i_vicinity::IVicinity;
+
+use bound_obb::OrientedBBox;
+use error::MazthError;
+use intersect_sat;
+use intersection::{Hit, Intersection};
+use mat::Mat3x1;
+use quat::Quat;
+
+#[derive(Debug, Clone)]
+pub struct OrientedBox {
+    pub _ori: Mat3x1<f64>,
+    ///half of the length of box edges along each of the axes
+    pub _half_extents: Mat3x1<f64>,
+    pub _rot: Quat<f64>,
+    ///world axes rotated by _rot
+    pub _axes: [Mat3x1<f64>; 3],
+    pub _bound: OrientedBBox,
+    pub _vicinity: f64,
+}
+
+impl OrientedBox {
+    pub fn init(origin: &[f64], half_extents: &[f64], rot: &Quat<f64>) -> OrientedBox {
+        let bound = OrientedBBox::init(origin, half_extents, rot);
+        let mut axes: [Mat3x1<f64>; 3] = Default::default();
+        for (a, b) in axes.iter_mut().zip(bound._axes.iter()) {
+            *a = Mat3x1 { _val: *b };
+        }
+        OrientedBox {
+            _ori: Mat3x1 {
+                _val: [origin[0], origin[1], origin[2]],
+            },
+            _half_extents: Mat3x1 {
+                _val: [half_extents[0], half_extents[1], half_extents[2]],
+            },
+            _rot: *rot,
+            _axes: axes,
+            _bound: bound,
+            _vicinity: 0.000001f64,
+        }
+    }
+
+    ///coordinates of p along the axes of the box relative to its center
+    pub fn to_local(&self, p: &Mat3x1<f64>) -> Mat3x1<f64> {
+        let d = p.minus(&self._ori).unwrap();
+        Mat3x1 {
+            _val: [
+                d.dot(&self._axes[0]).unwrap(),
+                d.dot(&self._axes[1]).unwrap(),
+                d.dot(&self._axes[2]).unwrap(),
+            ],
+        }
+    }
+
+    pub fn to_world(&self, p: &Mat3x1<f64>) -> Mat3x1<f64> {
+        let mut q = self._ori;
+        for (i, a) in self._axes.iter().enumerate() {
+            q = q.plus(&a.scale(p[i]).unwrap()).unwrap();
+        }
+        q
+    }
+
+    ///slab method in the frame of the box, returns entry and exit parameters of the ray in multiples of dir,
+    ///entry is negative if the ray starts inside the box
+    pub fn intersect_ray(&self, ori: &Mat3x1<f64>, dir: &Mat3x1<f64>) -> Option<(f64, f64)> {
+        let o = self.to_local(ori);
+        let d = Mat3x1 {
+            _val: [
+                dir.dot(&self._axes[0]).unwrap(),
+                dir.dot(&self._axes[1]).unwrap(),
+                dir.dot(&self._axes[2]).unwrap(),
+            ],
+        };
+        let mut t_enter = f64::NEG_INFINITY;
+        let mut t_exit = f64::INFINITY;
+        for i in 0..3 {
+            let h = self._half_extents[i];
+            if d[i].abs() <= self._vicinity * self._vicinity {
+                //parallel to slab
+                if o[i] < -h || o[i] > h {
+                    return None;
+                }
+            } else {
+                let t0 = (-h - o[i]) / d[i];
+                let t1 = (h - o[i]) / d[i];
+                t_enter = t_enter.max(t0.min(t1));
+                t_exit = t_exit.min(t0.max(t1));
+            }
+        }
+        if t_enter > t_exit || t_exit < 0f64 {
+            None
+        } else {
+            Some((t_enter, t_exit))
+        }
+    }
+
+    ///index of the face nearest to point p, faces are ordered negative then positive side along each axis of the box
+    pub fn get_face(&self, p: &Mat3x1<f64>) -> usize {
+        let l = self.to_local(p);
+        let mut face = 0;
+        let mut nearest = f64::INFINITY;
+        for i in 0..3 {
+            let gap = self._half_extents[i] - l[i].abs();
+            if gap < nearest {
+                nearest = gap;
+                face = i * 2 + if l[i] >= 0f64 { 1 } else { 0 };
+            }
+        }
+        face
+    }
+
+    ///outward unit normal of the face
+    pub fn get_face_normal(&self, face: usize) -> Mat3x1<f64> {
+        let a = self._axes[face / 2];
+        if face % 2 == 1 {
+            a
+        } else {
+            a.scale(-1f64).unwrap()
+        }
+    }
+
+    pub fn get_closest_point(&self, p: &Mat3x1<f64>) -> Mat3x1<f64> {
+        let mut l = self.to_local(p);
+        for i in 0..3 {
+            l[i] = l[i].clamp(-self._half_extents[i], self._half_extents[i]);
+        }
+        self.to_world(&l)
+    }
+}
+
+impl IShape for OrientedBox {
+    fn get_shape_data(&self) -> Vec<f64> {
+        let mut d = vec![];
+        d.extend_from_slice(&self._ori._val);
+        d.extend_from_slice(&self._half_extents._val);
+        for a in self._axes.iter() {
+            d.extend_from_slice(&a._val);
+        }
+        d
+    }
+    fn get_type(&self) -> ShapeType {
+        ShapeType::OrientedBox
+    }
+    fn get_bound(&self) -> &dyn IBound {
+        &self._bound
+    }
+    // this shall test for intersection of bounding shapes first before procedding to test intersection using algorithms of higher complexity
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError> {
+        if !self.get_bound().intersect(other.get_bound()) {
+            Ok(Default::default())
+        } else {
+            let d = other.get_shape_data();
+            match other.get_type() {
+                ShapeType::Point => {
+                    //bound is the box itself
+                    Ok(Intersection::init_point(Mat3x1 {
+                        _val: [d[0], d[1], d[2]],
+                    }))
+                }
+                ShapeType::Ray => {
+                    let ori = Mat3x1 {
+                        _val: [d[0], d[1], d[2]],
+                    };
+                    let dir = Mat3x1 {
+                        _val: [d[3], d[4], d[5]],
+                    };
+                    let (t_enter, t_exit) = match self.intersect_ray(&ori, &dir) {
+                        Some(x) => x,
+                        _ => return Ok(Default::default()),
+                    };
+                    //entry and exit points on the surface, entry is skipped if the ray starts inside the box,
+                    //normals point along the ray into the box
+                    let hits = [t_enter, t_exit]
+                        .iter()
+                        .filter(|t| **t >= 0f64)
+                        .map(|t| {
+                            let p = ori.plus(&dir.scale(*t).unwrap()).unwrap();
+                            let face = self.get_face(&p);
+                            Hit {
+                                _normal: Some(self.get_face_normal(face).scale(-1f64).unwrap()),
+                                _t: Some(*t),
+                                _feature: Some(face),
+                                ..Hit::init(p)
+                            }
+                        })
+                        .collect();
+                    Ok(Intersection {
+                        _is_intersect: true,
+                        _hits: hits,
+                        _depth: None,
+                    })
+                }
+                ShapeType::Sphere => {
+                    let c = Mat3x1 {
+                        _val: [d[0], d[1], d[2]],
+                    };
+                    let p = self.get_closest_point(&c);
+                    let v = p.minus(&c).unwrap();
+                    let dist = v.magnitude().unwrap();
+                    if dist > d[3] {
+                        return Ok(Default::default());
+                    }
+                    let (hit, depth) = if dist > 0f64 {
+                        let hit = Hit {
+                            _normal: Some(v.scale(1f64 / dist).unwrap()),
+                            _feature: Some(self.get_face(&p)),
+                            ..Hit::init(p)
+                        };
+                        (hit, d[3] - dist)
+                    } else {
+                        //center inside the box, push out through the nearest face
+                        let face = self.get_face(&c);
+                        let axis = face / 2;
+                        let mut l = self.to_local(&c);
+                        let gap = self._half_extents[axis] - l[axis].abs();
+                        l[axis] = if face % 2 == 1 {
+                            self._half_extents[axis]
+                        } else {
+                            -self._half_extents[axis]
+                        };
+                        let hit = Hit {
+                            _normal: Some(self.get_face_normal(face).scale(-1f64).unwrap()),
+                            _feature: Some(face),
+                            ..Hit::init(self.to_world(&l))
+                        };
+                        (hit, d[3] + gap)
+                    };
+                    Ok(Intersection {
+                        _is_intersect: true,
+                        _hits: vec![hit],
+                        _depth: Some(depth),
+                    })
+                }
+                ShapeType::Plane => {
+                    //plane bounds the half space behind its normal
+                    let ori = Mat3x1 {
+                        _val: [d[0], d[1], d[2]],
+                    };
+                    let n = Mat3x1 {
+                        _val: [d[3], d[4], d[5]],
+                    };
+                    let deepest = self.get_support(&n.scale(-1f64).unwrap()).unwrap();
+                    let dist = deepest.minus(&ori).unwrap().dot(&n).unwrap();
+                    if dist > self._vicinity {
+                        return Ok(Default::default());
+                    }
+                    Ok(Intersection {
+                        _is_intersect: true,
+                        _hits: vec![Hit {
+                            _normal: Some(n),
+                            ..Hit::init(deepest.minus(&n.scale(dist).unwrap()).unwrap())
+                        }],
+                        _depth: Some(-dist),
+                    })
+                }
+                ShapeType::Box | ShapeType::TriPrism | ShapeType::OrientedBox | ShapeType::Trig => {
+                    intersect_sat::query_intersect(self, other)
+                        .map(|x| x.get_intersection())
+                        .ok_or(MazthError::DegenerateInput)
+                }
+                _ => Err(MazthError::UnsupportedPair(
+                    self.get_type(),
+                    other.get_type(),
+                )),
+            }
+        }
+    }
+    fn get_support(&self, v: &Mat3x1<f64>) -> Option<Mat3x1<f64>> {
+        if v.magnitude() != Some(0f64) {
+            //get a furthest corner in the given direction v
+            let mut l = self._half_extents;
+            for i in 0..3 {
+                if self._axes[i].dot(v).unwrap() < 0f64 {
+                    l[i] = -l[i];
+                }
+            }
+            Some(self.to_world(&l))
+        } else {
+            None
+        }
+    }
+}
+
+impl IVicinity<f64> for OrientedBox {
+    fn set_vicinity(&mut self, epsilon: f64) {
+        self._vicinity = epsilon.abs();
+    }
+    fn within_vicinity(&self, a: f64, b: f64) -> bool {
+        a + self._vicinity >= b && a - self._vicinity <= b
+    }
+}
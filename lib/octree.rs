@@ -0,0 +1,264 @@
+//octree over a static collection of shapes
+use bound::AxisAlignedBBox;
+use frustum::{Containment, Frustum};
+use i_bound::IBound;
+use i_shape::IShape;
+use mat::Mat3x1;
+use point::Point3;
+use ray::Ray3;
+
+#[derive(Debug, Clone)]
+pub struct OctreeNode {
+    pub _bound: AxisAlignedBBox,
+    pub _children: Option<[usize; 8]>,
+    ///shapes of a leaf node, or shapes straddling the children of an interior node
+    pub _shapes: Vec<usize>,
+    pub _depth: usize,
+}
+
+pub struct Octree {
+    pub _shapes: Vec<Box<dyn IShape>>,
+    pub _bounds: Vec<AxisAlignedBBox>,
+    pub _nodes: Vec<OctreeNode>,
+    ///shapes with infinite extents are kept outside of the tree and tested in every query
+    pub _unbounded: Vec<usize>,
+    pub _depth_max: usize,
+    pub _leaf_capacity: usize,
+}
+
+impl Octree {
+    ///builds tree by subdividing nodes holding more than leaf_capacity shapes up to depth_max
+    pub fn init(shapes: Vec<Box<dyn IShape>>, depth_max: usize, leaf_capacity: usize) -> Octree {
+        let bounds: Vec<AxisAlignedBBox> = shapes
+            .iter()
+            .map(|x| AxisAlignedBBox::init_from_bound(x.get_bound()))
+            .collect();
+        let (unbounded, bounded): (Vec<usize>, Vec<usize>) =
+            (0..shapes.len()).partition(|x| bounds[*x].is_unbounded());
+
+        let mut root_bound = AxisAlignedBBox {
+            _bound_lower: [0f64; 3],
+            _bound_upper: [0f64; 3],
+        };
+        if !bounded.is_empty() {
+            let b: Vec<&dyn IBound> = bounded.iter().map(|x| &bounds[*x] as &dyn IBound).collect();
+            root_bound.get_union(&b[..]);
+        }
+
+        let mut octree = Octree {
+            _shapes: shapes,
+            _bounds: bounds,
+            _nodes: vec![OctreeNode {
+                _bound: root_bound,
+                _children: None,
+                _shapes: vec![],
+                _depth: 0,
+            }],
+            _unbounded: unbounded,
+            _depth_max: depth_max,
+            _leaf_capacity: leaf_capacity.max(1),
+        };
+        octree.build(0, bounded);
+        octree
+    }
+
+    fn build(&mut self, n: usize, shapes: Vec<usize>) {
+        if shapes.len() <= self._leaf_capacity || self._nodes[n]._depth >= self._depth_max {
+            self._nodes[n]._shapes = shapes;
+            return;
+        }
+        let lower = self._nodes[n]._bound._bound_lower;
+        let upper = self._nodes[n]._bound._bound_upper;
+        let center = [
+            (lower[0] + upper[0]) / 2f64,
+            (lower[1] + upper[1]) / 2f64,
+            (lower[2] + upper[2]) / 2f64,
+        ];
+
+        let mut children = [0usize; 8];
+        for (i, c) in children.iter_mut().enumerate() {
+            let mut b = AxisAlignedBBox {
+                _bound_lower: lower,
+                _bound_upper: center,
+            };
+            for axis in 0..3 {
+                if i & (1 << axis) != 0 {
+                    b._bound_lower[axis] = center[axis];
+                    b._bound_upper[axis] = upper[axis];
+                }
+            }
+            self._nodes.push(OctreeNode {
+                _bound: b,
+                _children: None,
+                _shapes: vec![],
+                _depth: self._nodes[n]._depth + 1,
+            });
+            *c = self._nodes.len() - 1;
+        }
+        self._nodes[n]._children = Some(children);
+
+        //shapes are pushed down to the child fully containing them, the rest stay at this node
+        let mut child_shapes: Vec<Vec<usize>> = vec![vec![]; 8];
+        let mut straddling = vec![];
+        for s in shapes {
+            match children
+                .iter()
+                .position(|x| self._nodes[*x]._bound.contains(&self._bounds[s]))
+            {
+                Some(i) => child_shapes[i].push(s),
+                _ => straddling.push(s),
+            }
+        }
+        self._nodes[n]._shapes = straddling;
+        for (i, s) in child_shapes.into_iter().enumerate() {
+            self.build(children[i], s);
+        }
+    }
+
+    ///returns index and location of the intersected shape closest to the ray origin,
+    ///nodes are visited front to back and traversal stops once remaining nodes are further than the closest hit
+    pub fn query_ray(&self, ray: &Ray3) -> Option<(usize, Mat3x1<f64>)> {
+        let mut nearest: Option<(usize, Mat3x1<f64>, f64)> = None;
+        for s in self._unbounded.iter() {
+            self.test_ray(*s, ray, &mut nearest);
+        }
+        if let Some((t, _)) = self._nodes[0]._bound.intersect_ray(&ray._ori, &ray._dir) {
+            self.traverse_ray(0, t, ray, &mut nearest);
+        }
+        nearest.map(|x| (x.0, x.1))
+    }
+
+    fn traverse_ray(
+        &self,
+        n: usize,
+        t_enter: f64,
+        ray: &Ray3,
+        nearest: &mut Option<(usize, Mat3x1<f64>, f64)>,
+    ) {
+        if let Some((_, _, t)) = *nearest {
+            if t_enter > t {
+                return;
+            }
+        }
+        let node = &self._nodes[n];
+        for s in node._shapes.iter() {
+            self.test_ray(*s, ray, nearest);
+        }
+        if let Some(children) = node._children {
+            let mut hits: Vec<(usize, f64)> = children
+                .iter()
+                .filter_map(|x| {
+                    self._nodes[*x]
+                        ._bound
+                        .intersect_ray(&ray._ori, &ray._dir)
+                        .map(|(t, _)| (*x, t))
+                })
+                .collect();
+            hits.sort_by(|a, b| a.1.total_cmp(&b.1));
+            for (c, t) in hits {
+                self.traverse_ray(c, t, ray, nearest);
+            }
+        }
+    }
+
+    fn test_ray(&self, s: usize, ray: &Ray3, nearest: &mut Option<(usize, Mat3x1<f64>, f64)>) {
+        //shapes not supported by the ray are skipped
+        let ret = match ray.get_intersection(self._shapes[s].as_ref()) {
+            Ok(ret) => ret,
+            _ => return,
+        };
+        if let (true, Some(loc)) = (ret._is_intersect, ret.get_point()) {
+            let t = loc.minus(&ray._ori).unwrap().magnitude().unwrap();
+            let is_closer = match *nearest {
+                Some((_, _, t_nearest)) => t < t_nearest,
+                _ => true,
+            };
+            if is_closer {
+                *nearest = Some((s, loc, t));
+            }
+        }
+    }
+
+    ///returns indices of shapes containing point p
+    pub fn query_point(&self, p: &[f64]) -> Vec<usize> {
+        let point = Point3::init(p);
+        let mut candidates = self._unbounded.clone();
+        let mut stack = vec![0usize];
+        while let Some(n) = stack.pop() {
+            let node = &self._nodes[n];
+            if !node._bound.intersect(&point._bound) {
+                continue;
+            }
+            candidates.extend(node._shapes.iter());
+            if let Some(children) = node._children {
+                stack.extend(children.iter());
+            }
+        }
+        let mut ret: Vec<usize> = candidates
+            .into_iter()
+            .filter(|x| match self._shapes[*x].get_intersection(&point) {
+                Ok(ret) => ret._is_intersect,
+                //shapes not supporting points are skipped
+                _ => false,
+            })
+            .collect();
+        ret.sort_unstable();
+        ret
+    }
+
+    ///returns indices of shapes whose bounds intersect the given bound
+    pub fn query_bound(&self, bound: &dyn IBound) -> Vec<usize> {
+        let mut ret: Vec<usize> = self
+            ._unbounded
+            .iter()
+            .cloned()
+            .filter(|x| self._bounds[*x].intersect(bound))
+            .collect();
+        let mut stack = vec![0usize];
+        while let Some(n) = stack.pop() {
+            let node = &self._nodes[n];
+            if !node._bound.intersect(bound) {
+                continue;
+            }
+            ret.extend(
+                node._shapes
+                    .iter()
+                    .filter(|x| self._bounds[**x].intersect(bound)),
+            );
+            if let Some(children) = node._children {
+                stack.extend(children.iter());
+            }
+        }
+        ret.sort_unstable();
+        ret
+    }
+
+    ///returns indices of shapes whose bounds are not culled by the frustum,
+    ///shapes below nodes fully inside the frustum are taken without testing their bounds
+    pub fn query_frustum(&self, frustum: &Frustum) -> Vec<usize> {
+        let mut ret: Vec<usize> = self
+            ._unbounded
+            .iter()
+            .cloned()
+            .filter(|x| frustum.cull_aabb(&self._bounds[*x]) != Containment::Outside)
+            .collect();
+        let mut stack = vec![(0usize, false)];
+        while let Some((n, is_inside)) = stack.pop() {
+            let node = &self._nodes[n];
+            let is_inside = is_inside
+                || match frustum.cull_aabb(&node._bound) {
+                    Containment::Outside => continue,
+                    Containment::Inside => true,
+                    Containment::Intersecting => false,
+                };
+            ret.extend(node._shapes.iter().filter(|x| {
+                is_inside || frustum.cull_aabb(&self._bounds[**x]) != Containment::Outside
+            }));
+            if let Some(children) = node._children {
+                stack.extend(children.iter().map(|x| (*x, is_inside)));
+            }
+        }
+        ret.sort_unstable();
+        ret
+    }
+}
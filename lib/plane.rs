@@ -3,6 +3,8 @@ use i_shape::{IShape, ShapeType};
 use i_vicinity::IVicinity;
 
 use bound::AxisAlignedBBox;
+use error::MazthError;
+use intersection::Intersection;
 use mat::Mat3x1;
 
 #[derive(Debug, Clone)]
@@ -53,21 +55,34 @@ impl IShape for Plane {
         &self._bound
     }
     // this shall test for intersection of bounding shapes first before procedding to test intersection using algorithms of higher complexity
-    fn get_intersect(&self, other: &dyn IShape) -> (bool, Option<Mat3x1<f64>>) {
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError> {
         if !self.get_bound().intersect(other.get_bound()) {
-            return (false, None);
+            return Ok(Default::default());
         } else {
             match other.get_type() {
-                ShapeType::Plane => {
-                    unimplemented!();
+                ShapeType::Rect => {
+                    //see Rect3 for rectangle intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::Box => {
+                    //see recbox for box intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::OrientedBox => {
+                    //see OrientedBox for oriented box intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::Trig => {
+                    //see Triangle3 for triangle intersection
+                    other.get_intersection(self).map(|x| x.flip())
                 }
                 ShapeType::Ray => {
                     //see Ray3 for ray plane intersection
-                    return other.get_intersect(self);
+                    return other.get_intersection(self).map(|x| x.flip());
                 }
                 ShapeType::Sphere => {
                     //see sphere for sphere plane intersection
-                    return other.get_intersect(self);
+                    return other.get_intersection(self).map(|x| x.flip());
                 }
                 ShapeType::Point => {
                     let other_shape_data = other.get_shape_data();
@@ -82,13 +97,14 @@ impl IShape for Plane {
                     let c = self._normal.dot(&b_off).unwrap();
                     let d = k - c;
                     if !self.within_vicinity(d, 0f64) {
-                        return (false, None);
+                        return Ok(Default::default());
                     }
-                    return (true, Some(b_off));
-                }
-                _ => {
-                    unimplemented!();
+                    return Ok(Intersection::init_point(b_off));
                 }
+                _ => Err(MazthError::UnsupportedPair(
+                    self.get_type(),
+                    other.get_type(),
+                )),
             }
         }
     }
@@ -3,6 +3,8 @@ use i_shape::{IShape, ShapeType};
 use i_vicinity::IVicinity;
 
 use bound::AxisAlignedBBox;
+use error::MazthError;
+use intersection::Intersection;
 use mat::Mat3x1;
 
 #[derive(Debug, Clone)]
@@ -36,45 +38,62 @@ impl IShape for Point3 {
         &self._bound
     }
     // this shall test for intersection of bounding shapes first before procedding to test intersection using algorithms of higher complexity
-    fn get_intersect(&self, other: &dyn IShape) -> (bool, Option<Mat3x1<f64>>) {
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError> {
         if !self.get_bound().intersect(other.get_bound()) {
-            return (false, None);
+            return Ok(Default::default());
         } else {
             match other.get_type() {
+                ShapeType::Frustum => {
+                    //see Frustum for frustum point intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::Rect => {
+                    //see Rect3 for rectangle intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::OrientedBox => {
+                    //see OrientedBox for oriented box intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::Trig => {
+                    //see Triangle3 for triangle intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
                 ShapeType::Point => {
                     let other_shape_data = other.get_shape_data();
                     if !self.within_vicinity(self._ori[0], other_shape_data[0])
                         || !self.within_vicinity(self._ori[1], other_shape_data[1])
                         || !self.within_vicinity(self._ori[2], other_shape_data[2])
                     {
-                        return (false, None);
+                        return Ok(Default::default());
                     } else {
-                        return (true, Some(self._ori));
+                        return Ok(Intersection::init_point(self._ori));
                     }
                 }
                 ShapeType::Ray => {
                     //see Ray3 for ray point intersection
-                    other.get_intersect(self)
+                    other.get_intersection(self).map(|x| x.flip())
                 }
                 ShapeType::Sphere => {
                     //see sphere for sphere point intersection
-                    other.get_intersect(self)
+                    other.get_intersection(self).map(|x| x.flip())
                 }
                 ShapeType::Plane => {
                     //see plane for plane point intersection
-                    other.get_intersect(self)
+                    other.get_intersection(self).map(|x| x.flip())
                 }
                 ShapeType::Box => {
                     //see recbox for box point intersection
-                    other.get_intersect(self)
+                    other.get_intersection(self).map(|x| x.flip())
                 }
                 ShapeType::TriPrism => {
                     //see tri prism for intersection
-                    other.get_intersect(self)
-                }
-                _ => {
-                    unimplemented!();
+                    other.get_intersection(self).map(|x| x.flip())
                 }
+                _ => Err(MazthError::UnsupportedPair(
+                    self.get_type(),
+                    other.get_type(),
+                )),
             }
         }
     }
@@ -3,7 +3,9 @@ use i_shape::{IShape, ShapeType};
 use i_vicinity::IVicinity;
 
 use bound::AxisAlignedBBox;
+use error::MazthError;
 use i_comparable::IComparableError;
+use intersection::{Hit, Intersection};
 use mat::Mat3x1;
 
 #[derive(Debug, Clone)]
@@ -31,6 +33,19 @@ impl Ray3 {
             _vicinity: 0.000001f64,
         }
     }
+
+    ///returns intersection at a single point along the ray
+    fn get_hit(&self, t: f64, normal: Option<Mat3x1<f64>>) -> Intersection {
+        Intersection {
+            _is_intersect: true,
+            _hits: vec![Hit {
+                _normal: normal,
+                _t: Some(t),
+                ..Hit::init(self._dir.scale(t).unwrap().plus(&self._ori).unwrap())
+            }],
+            _depth: None,
+        }
+    }
 }
 
 impl IShape for Ray3 {
@@ -51,11 +66,31 @@ impl IShape for Ray3 {
         &self._bound
     }
     // this shall test for intersection of bounding shapes first before procedding to test intersection using algorithms of higher complexity
-    fn get_intersect(&self, other: &dyn IShape) -> (bool, Option<Mat3x1<f64>>) {
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError> {
         if !self.get_bound().intersect(other.get_bound()) {
-            return (false, None);
+            return Ok(Default::default());
         } else {
             match other.get_type() {
+                ShapeType::Rect => {
+                    //see Rect3 for rectangle intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::Box => {
+                    //see recbox for box intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::OrientedBox => {
+                    //see OrientedBox for oriented box intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::Trig => {
+                    //see Triangle3 for triangle intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::TriPrism => {
+                    //see TriPrism for prism intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
                 ShapeType::Ray => {
                     let other_shape_data = other.get_shape_data();
                     let a_dir = self._dir;
@@ -81,7 +116,7 @@ impl IShape for Ray3 {
                     let dot_v_c = v.dot(&c).unwrap();
                     if !self.within_vicinity(dot_v_c, 0f64) {
                         //they are not in the same place, so no intersection occurs
-                        return (false, None);
+                        return Ok(Default::default());
                     }
                     //test for colinearity
                     let zero: Mat3x1<f64> = Default::default();
@@ -96,7 +131,7 @@ impl IShape for Ray3 {
                         if !self.within_vicinity(triangle_area, 0f64) {
                             //no overlap
                             // println!( "parallel but non-overlapping lines" );
-                            return (false, None);
+                            return Ok(Default::default());
                         } else {
                             //lines are colinear
                             let direction = if d.dot(&a_dir).unwrap() < 0f64 {
@@ -109,19 +144,10 @@ impl IShape for Ray3 {
                             // println!( "colinear lines, distance: {}", distance );
                             if distance < 0f64 {
                                 //intersection at offset of ray a, so clamp t to 0
-                                return (true, Some(a_off));
+                                return Ok(self.get_hit(0f64, None));
                             } else {
                                 //intersection at offset of ray b
-                                return (
-                                    true,
-                                    Some(
-                                        self._dir
-                                            .scale(distance)
-                                            .unwrap()
-                                            .plus(&self._ori)
-                                            .unwrap(),
-                                    ),
-                                );
+                                return Ok(self.get_hit(distance, None));
                             }
                         }
                     } else {
@@ -129,12 +155,9 @@ impl IShape for Ray3 {
                         let numerator = d.cross(&b_dir).unwrap();
                         let t = numerator.magnitude().unwrap() / v.magnitude().unwrap();
                         if t < 0f64 {
-                            return (false, None);
+                            return Ok(Default::default());
                         } else {
-                            return (
-                                true,
-                                Some(self._dir.scale(t).unwrap().plus(&self._ori).unwrap()),
-                            );
+                            return Ok(self.get_hit(t, None));
                         }
                     }
                 }
@@ -153,13 +176,13 @@ impl IShape for Ray3 {
                     //t = (b_off - a_off) / a_dir
                     let t = b_off.minus(&a_off).unwrap().div(&a_dir).unwrap();
                     if !self.within_vicinity(t[0], t[1]) || !self.within_vicinity(t[1], t[2]) {
-                        return (false, None);
+                        return Ok(Default::default());
                     } else {
                         if t[0] >= 0f64 {
-                            return (true, Some(a_dir.scale(t[0]).unwrap().plus(&a_off).unwrap()));
+                            return Ok(self.get_hit(t[0], None));
                         } else {
                             //the point is behind the ray origin and direction
-                            return (false, None);
+                            return Ok(Default::default());
                         }
                     }
                 }
@@ -187,29 +210,41 @@ impl IShape for Ray3 {
                     if b > 0f64 && c > 0f64 {
                         //ray is outside of the sphere and points away from sphere
                         //thus no intersection occurs
-                        return (false, None);
+                        return Ok(Default::default());
                     }
 
                     let d = b * b - c;
                     if d < 0f64 {
                         //ray misses sphere
-                        return (false, None);
+                        return Ok(Default::default());
                     }
 
+                    //entry and exit points, entry is behind the ray origin if it starts inside the sphere
                     let t1 = -b - d.sqrt();
                     let t2 = -b + d.sqrt();
 
-                    let t = if t1 < 0f64 {
-                        t2
-                    } else if t2 < 0f64 {
-                        t1
-                    } else if t1 < t2 {
-                        t1
-                    } else {
-                        t2
-                    };
+                    let hits = [t1, t2]
+                        .iter()
+                        .filter(|t| **t >= 0f64)
+                        .map(|t| {
+                            let p = a_dir.scale(*t).unwrap().plus(&a_off).unwrap();
+                            let n = p.minus(&b_off).unwrap();
+                            let m = n.magnitude().unwrap();
+                            Hit {
+                                _point: p,
+                                _normal: if m > 0f64 { n.scale(1f64 / m) } else { None },
+                                _t: Some(*t),
+                                _feature: None,
+                                _feature_other: None,
+                            }
+                        })
+                        .collect();
 
-                    return (true, Some(a_dir.scale(t).unwrap().plus(&a_off).unwrap()));
+                    return Ok(Intersection {
+                        _is_intersect: true,
+                        _hits: hits,
+                        _depth: None,
+                    });
                 }
                 ShapeType::Plane => {
                     let other_shape_data = other.get_shape_data();
@@ -240,26 +275,24 @@ impl IShape for Ray3 {
                     if denominator == 0f64 {
                         //ray direction is colplaner to the plane
                         if constant == self._ori.dot(&b_nor).unwrap() {
-                            return (true, Some(self._ori));
+                            return Ok(self.get_hit(0f64, Some(b_nor)));
                         } else {
-                            return (false, None);
+                            return Ok(Default::default());
                         }
                     } else if denominator > 0f64 {
                         //ray direction is not facing plane normal
-                        return (false, None);
+                        return Ok(Default::default());
                     }
                     let t = numerator / denominator;
                     if t < 0f64 {
-                        return (false, None);
+                        return Ok(Default::default());
                     }
-                    return (
-                        true,
-                        Some(self._dir.scale(t).unwrap().plus(&self._ori).unwrap()),
-                    );
-                }
-                _ => {
-                    unimplemented!();
+                    return Ok(self.get_hit(t, Some(b_nor)));
                 }
+                _ => Err(MazthError::UnsupportedPair(
+                    self.get_type(),
+                    other.get_type(),
+                )),
             }
         }
     }
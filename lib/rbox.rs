@@ -1,35 +1,120 @@
+use std::f64;
+
 use i_bound::IBound;
 use i_shape::{IShape, ShapeType};
 use i_vicinity::IVicinity;
 
 use bound::AxisAlignedBBox;
+use error::MazthError;
+use intersect_sat;
+use intersection::{Hit, Intersection};
 use mat::Mat3x1;
 
 #[derive(Debug, Clone)]
 pub struct RecBox {
     pub _ori: Mat3x1<f64>,
-    pub _size: f64,
+    ///half of the length of box edges along each axis
+    pub _half_extents: Mat3x1<f64>,
     pub _bound: AxisAlignedBBox,
     pub _vicinity: f64,
 }
 
 impl RecBox {
+    ///cube with half edge length of size
     pub fn init(origin: &[f64], size: f64) -> RecBox {
+        RecBox::init_extents(origin, &[size, size, size])
+    }
+
+    pub fn init_extents(origin: &[f64], half_extents: &[f64]) -> RecBox {
         assert!(origin.len() == 3);
+        assert!(half_extents.len() == 3);
         RecBox {
             _ori: Mat3x1 {
                 _val: [origin[0], origin[1], origin[2]],
             },
-            _size: size, //half of the length of box edge
-            _bound: AxisAlignedBBox::init(ShapeType::Box, &[&origin[0..3], &[size]].concat()),
+            _half_extents: Mat3x1 {
+                _val: [half_extents[0], half_extents[1], half_extents[2]],
+            },
+            _bound: AxisAlignedBBox::init(
+                ShapeType::Box,
+                &[&origin[0..3], &half_extents[0..3]].concat(),
+            ),
             _vicinity: 0.000001f64,
         }
     }
+
+    ///slab method, returns entry and exit parameters of the ray in multiples of dir,
+    ///entry is negative if the ray starts inside the box
+    pub fn intersect_ray(&self, ori: &Mat3x1<f64>, dir: &Mat3x1<f64>) -> Option<(f64, f64)> {
+        let mut t_enter = f64::NEG_INFINITY;
+        let mut t_exit = f64::INFINITY;
+        for i in 0..3 {
+            let lower = self._ori[i] - self._half_extents[i];
+            let upper = self._ori[i] + self._half_extents[i];
+            if dir[i] == 0f64 {
+                //parallel to slab
+                if ori[i] < lower || ori[i] > upper {
+                    return None;
+                }
+            } else {
+                let t0 = (lower - ori[i]) / dir[i];
+                let t1 = (upper - ori[i]) / dir[i];
+                t_enter = t_enter.max(t0.min(t1));
+                t_exit = t_exit.min(t0.max(t1));
+            }
+        }
+        if t_enter > t_exit || t_exit < 0f64 {
+            None
+        } else {
+            Some((t_enter, t_exit))
+        }
+    }
+
+    ///index of the face nearest to point p, faces are ordered negative then positive side along x, y and z
+    pub fn get_face(&self, p: &Mat3x1<f64>) -> usize {
+        let mut face = 0;
+        let mut nearest = f64::INFINITY;
+        for i in 0..3 {
+            let d = p[i] - self._ori[i];
+            let gap = self._half_extents[i] - d.abs();
+            if gap < nearest {
+                nearest = gap;
+                face = i * 2 + if d >= 0f64 { 1 } else { 0 };
+            }
+        }
+        face
+    }
+
+    ///outward unit normal of the face
+    pub fn get_face_normal(&self, face: usize) -> Mat3x1<f64> {
+        let mut n: Mat3x1<f64> = Default::default();
+        n[face / 2] = if face % 2 == 1 { 1f64 } else { -1f64 };
+        n
+    }
+
+    pub fn get_closest_point(&self, p: &Mat3x1<f64>) -> Mat3x1<f64> {
+        let mut c = *p;
+        for i in 0..3 {
+            c[i] = c[i].clamp(
+                self._ori[i] - self._half_extents[i],
+                self._ori[i] + self._half_extents[i],
+            );
+        }
+        c
+    }
 }
 
 impl IShape for RecBox {
+    //center followed by half extents along x, y and z, also for cubes which used to report a single size
     fn get_shape_data(&self) -> Vec<f64> {
-        vec![self._ori[0], self._ori[1], self._ori[2], self._size]
+        vec![
+            self._ori[0],
+            self._ori[1],
+            self._ori[2],
+            self._half_extents[0],
+            self._half_extents[1],
+            self._half_extents[2],
+        ]
     }
     fn get_type(&self) -> ShapeType {
         ShapeType::Box
@@ -38,11 +123,15 @@ impl IShape for RecBox {
         &self._bound
     }
     // this shall test for intersection of bounding shapes first before procedding to test intersection using algorithms of higher complexity
-    fn get_intersect(&self, other: &dyn IShape) -> (bool, Option<Mat3x1<f64>>) {
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError> {
         if !self.get_bound().intersect(other.get_bound()) {
-            return (false, None);
+            return Ok(Default::default());
         } else {
             match other.get_type() {
+                ShapeType::Trig => {
+                    //see Triangle3 for triangle intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
                 ShapeType::Point => {
                     //covered by bbox test
                     let other_shape_data = other.get_shape_data();
@@ -53,54 +142,133 @@ impl IShape for RecBox {
                             other_shape_data[2],
                         ],
                     };
-                    return (true, Some(b_off));
+                    return Ok(Intersection::init_point(b_off));
+                }
+                ShapeType::Ray => {
+                    let d = other.get_shape_data();
+                    let ori = Mat3x1 {
+                        _val: [d[0], d[1], d[2]],
+                    };
+                    let dir = Mat3x1 {
+                        _val: [d[3], d[4], d[5]],
+                    };
+                    let (t_enter, t_exit) = match self.intersect_ray(&ori, &dir) {
+                        Some(x) => x,
+                        _ => return Ok(Default::default()),
+                    };
+                    //entry and exit points on the surface, entry is skipped if the ray starts inside the box,
+                    //normals point along the ray into the box
+                    let hits = [t_enter, t_exit]
+                        .iter()
+                        .filter(|t| **t >= 0f64)
+                        .map(|t| {
+                            let p = ori.plus(&dir.scale(*t).unwrap()).unwrap();
+                            let face = self.get_face(&p);
+                            Hit {
+                                _normal: Some(self.get_face_normal(face).scale(-1f64).unwrap()),
+                                _t: Some(*t),
+                                _feature: Some(face),
+                                ..Hit::init(p)
+                            }
+                        })
+                        .collect();
+                    Ok(Intersection {
+                        _is_intersect: true,
+                        _hits: hits,
+                        _depth: None,
+                    })
+                }
+                ShapeType::Sphere => {
+                    let d = other.get_shape_data();
+                    let c = Mat3x1 {
+                        _val: [d[0], d[1], d[2]],
+                    };
+                    let p = self.get_closest_point(&c);
+                    let v = p.minus(&c).unwrap();
+                    let dist = v.magnitude().unwrap();
+                    if dist > d[3] {
+                        return Ok(Default::default());
+                    }
+                    let (hit, depth) = if dist > 0f64 {
+                        let hit = Hit {
+                            _normal: Some(v.scale(1f64 / dist).unwrap()),
+                            _feature: Some(self.get_face(&p)),
+                            ..Hit::init(p)
+                        };
+                        (hit, d[3] - dist)
+                    } else {
+                        //center inside the box, push out through the nearest face
+                        let face = self.get_face(&c);
+                        let (axis, n) = (face / 2, self.get_face_normal(face));
+                        let mut p = c;
+                        p[axis] = self._ori[axis] + n[axis] * self._half_extents[axis];
+                        let hit = Hit {
+                            _normal: Some(n.scale(-1f64).unwrap()),
+                            _feature: Some(face),
+                            ..Hit::init(p)
+                        };
+                        (hit, d[3] + (p[axis] - c[axis]).abs())
+                    };
+                    Ok(Intersection {
+                        _is_intersect: true,
+                        _hits: vec![hit],
+                        _depth: Some(depth),
+                    })
+                }
+                ShapeType::Plane => {
+                    //plane bounds the half space behind its normal
+                    let d = other.get_shape_data();
+                    let ori = Mat3x1 {
+                        _val: [d[0], d[1], d[2]],
+                    };
+                    let n = Mat3x1 {
+                        _val: [d[3], d[4], d[5]],
+                    };
+                    let mut deepest = self._ori;
+                    for i in 0..3 {
+                        if n[i] > 0f64 {
+                            deepest[i] -= self._half_extents[i];
+                        } else {
+                            deepest[i] += self._half_extents[i];
+                        }
+                    }
+                    let dist = deepest.minus(&ori).unwrap().dot(&n).unwrap();
+                    if dist > self._vicinity {
+                        return Ok(Default::default());
+                    }
+                    Ok(Intersection {
+                        _is_intersect: true,
+                        _hits: vec![Hit {
+                            _normal: Some(n),
+                            ..Hit::init(deepest.minus(&n.scale(dist).unwrap()).unwrap())
+                        }],
+                        _depth: Some(-dist),
+                    })
                 }
-                _ => {
-                    unimplemented!();
+                ShapeType::Box | ShapeType::TriPrism | ShapeType::OrientedBox => {
+                    intersect_sat::query_intersect(self, other)
+                        .map(|x| x.get_intersection())
+                        .ok_or(MazthError::DegenerateInput)
                 }
+                _ => Err(MazthError::UnsupportedPair(
+                    self.get_type(),
+                    other.get_type(),
+                )),
             }
         }
     }
     fn get_support(&self, v: &Mat3x1<f64>) -> Option<Mat3x1<f64>> {
         if v.magnitude() != Some(0f64) {
-            //get a furthest point in the given direction v
-            let points = [
-                Mat3x1 {
-                    _val: [self._size, self._size, self._size],
-                },
-                Mat3x1 {
-                    _val: [-self._size, self._size, self._size],
-                },
-                Mat3x1 {
-                    _val: [self._size, -self._size, self._size],
-                },
-                Mat3x1 {
-                    _val: [-self._size, -self._size, self._size],
-                },
-                Mat3x1 {
-                    _val: [self._size, self._size, -self._size],
-                },
-                Mat3x1 {
-                    _val: [-self._size, self._size, -self._size],
-                },
-                Mat3x1 {
-                    _val: [self._size, -self._size, -self._size],
-                },
-                Mat3x1 {
-                    _val: [-self._size, -self._size, -self._size],
-                },
-            ];
-
-            let furthest = points
-                .iter()
-                .map(|x| x.dot(v).unwrap())
-                .enumerate()
-                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
-                .unwrap();
-
+            //get a furthest corner in the given direction v
+            let mut offset = self._half_extents;
+            for i in 0..3 {
+                if v[i] < 0f64 {
+                    offset[i] = -offset[i];
+                }
+            }
             let o = self
                 ._ori
-                .plus(&points[furthest.0])
+                .plus(&offset)
                 .expect("support operation unsuccessful.");
             Some(o)
         } else {
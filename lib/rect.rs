@@ -0,0 +1,267 @@
+use i_bound::IBound;
+use i_shape::{IShape, ShapeType};
+use i_vicinity::IVicinity;
+
+use bound::AxisAlignedBBox;
+use error::MazthError;
+use intersection::{Hit, Intersection};
+use mat::Mat3x1;
+
+///oriented planar rectangle spanned by two perpendicular half axes around its center
+#[derive(Debug, Clone)]
+pub struct Rect3 {
+    pub _ori: Mat3x1<f64>,
+    pub _half_u: Mat3x1<f64>,
+    pub _half_v: Mat3x1<f64>,
+    ///unit normal along the cross product of the half axes
+    pub _normal: Mat3x1<f64>,
+    pub _bound: AxisAlignedBBox,
+    pub _vicinity: f64,
+}
+
+impl Rect3 {
+    pub fn init(center: &[f64], half_u: &[f64], half_v: &[f64]) -> Rect3 {
+        assert!(center.len() == 3);
+        assert!(half_u.len() == 3);
+        assert!(half_v.len() == 3);
+        let c = Mat3x1::<f64>::init_from_slice(center);
+        let u = Mat3x1::<f64>::init_from_slice(half_u);
+        let v = Mat3x1::<f64>::init_from_slice(half_v);
+        debug_assert!(
+            u.dot(&v).unwrap().abs()
+                <= 0.000001f64 * u.magnitude().unwrap() * v.magnitude().unwrap(),
+            "half axes of a rectangle are required to be perpendicular"
+        );
+        let n = u.cross(&v).unwrap();
+        let m = n.magnitude().unwrap();
+        let data: Vec<f64> = [center, half_u, half_v].concat();
+        Rect3 {
+            _ori: c,
+            _half_u: u,
+            _half_v: v,
+            //degenerate rectangles keep a zero normal
+            _normal: if m > 0f64 {
+                n.scale(1f64 / m).unwrap()
+            } else {
+                n
+            },
+            _bound: AxisAlignedBBox::init(ShapeType::Rect, &data),
+            _vicinity: 0.000001f64,
+        }
+    }
+
+    ///corners in ccw order around _normal
+    pub fn get_corners(&self) -> [Mat3x1<f64>; 4] {
+        let (c, u, v) = (self._ori, self._half_u, self._half_v);
+        [
+            c.minus(&u).unwrap().minus(&v).unwrap(),
+            c.plus(&u).unwrap().minus(&v).unwrap(),
+            c.plus(&u).unwrap().plus(&v).unwrap(),
+            c.minus(&u).unwrap().plus(&v).unwrap(),
+        ]
+    }
+
+    ///coordinates of point p projected onto the plane of the rectangle in multiples of the half axes,
+    ///points of the rectangle have both coordinates in [-1, 1]
+    pub fn get_local(&self, p: &Mat3x1<f64>) -> (f64, f64) {
+        let d = p.minus(&self._ori).unwrap();
+        let uu = self._half_u.dot(&self._half_u).unwrap();
+        let vv = self._half_v.dot(&self._half_v).unwrap();
+        let s = if uu > 0f64 {
+            d.dot(&self._half_u).unwrap() / uu
+        } else {
+            0f64
+        };
+        let t = if vv > 0f64 {
+            d.dot(&self._half_v).unwrap() / vv
+        } else {
+            0f64
+        };
+        (s, t)
+    }
+
+    fn at_local(&self, s: f64, t: f64) -> Mat3x1<f64> {
+        self._ori
+            .plus(&self._half_u.scale(s).unwrap())
+            .unwrap()
+            .plus(&self._half_v.scale(t).unwrap())
+            .unwrap()
+    }
+
+    ///returns ray parameter t and local coordinates of the point of intersection,
+    ///dir is not required to be normalized and t is in multiples of it
+    pub fn intersect_ray(&self, ori: &Mat3x1<f64>, dir: &Mat3x1<f64>) -> Option<(f64, f64, f64)> {
+        let denom = self._normal.dot(dir).unwrap();
+        if denom.abs() <= self._vicinity * self._vicinity {
+            //ray is parallel to the rectangle
+            return None;
+        }
+        let t = self._ori.minus(ori).unwrap().dot(&self._normal).unwrap() / denom;
+        if t < 0f64 {
+            return None;
+        }
+        let (s, r) = self.get_local(&ori.plus(&dir.scale(t).unwrap()).unwrap());
+        if !self.is_local_inside(s) || !self.is_local_inside(r) {
+            return None;
+        }
+        Some((t, s, r))
+    }
+
+    ///closest point of the rectangle to p
+    pub fn get_closest_point(&self, p: &Mat3x1<f64>) -> Mat3x1<f64> {
+        let (s, t) = self.get_local(p);
+        self.at_local(s.clamp(-1f64, 1f64), t.clamp(-1f64, 1f64))
+    }
+
+    fn is_local_inside(&self, s: f64) -> bool {
+        (-1f64 - self._vicinity..=1f64 + self._vicinity).contains(&s)
+    }
+}
+
+impl IShape for Rect3 {
+    fn get_shape_data(&self) -> Vec<f64> {
+        [self._ori, self._half_u, self._half_v]
+            .iter()
+            .flat_map(|x| x._val.to_vec())
+            .collect()
+    }
+    fn get_type(&self) -> ShapeType {
+        ShapeType::Rect
+    }
+    fn get_bound(&self) -> &dyn IBound {
+        &self._bound
+    }
+    // this shall test for intersection of bounding shapes first before procedding to test intersection using algorithms of higher complexity
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError> {
+        if !self.get_bound().intersect(other.get_bound()) {
+            Ok(Default::default())
+        } else {
+            let d = other.get_shape_data();
+            match other.get_type() {
+                ShapeType::Point => {
+                    let p = Mat3x1::<f64>::init_from_slice(&d);
+                    let dist = p.minus(&self._ori).unwrap().dot(&self._normal).unwrap();
+                    let (s, t) = self.get_local(&p);
+                    if !self.within_vicinity(dist, 0f64)
+                        || !self.is_local_inside(s)
+                        || !self.is_local_inside(t)
+                    {
+                        return Ok(Default::default());
+                    }
+                    Ok(Intersection::init_point(p))
+                }
+                ShapeType::Ray | ShapeType::Line => {
+                    let ori = Mat3x1::<f64>::init_from_slice(&d[0..3]);
+                    let dir = match other.get_type() {
+                        ShapeType::Ray => Mat3x1::<f64>::init_from_slice(&d[3..6]),
+                        _ => Mat3x1::<f64>::init_from_slice(&d[3..6])
+                            .minus(&ori)
+                            .unwrap(),
+                    };
+                    if dir.magnitude().unwrap() == 0f64 {
+                        return Err(MazthError::DegenerateInput);
+                    }
+                    match self.intersect_ray(&ori, &dir) {
+                        Some((t, _, _)) if other.get_type() == ShapeType::Ray || t <= 1f64 => {
+                            //normal of the rectangle facing the ray, seen from the rectangle it points away from it
+                            let facing = if self._normal.dot(&dir).unwrap() > 0f64 {
+                                self._normal
+                            } else {
+                                self._normal.scale(-1f64).unwrap()
+                            };
+                            Ok(Intersection {
+                                _is_intersect: true,
+                                _hits: vec![Hit {
+                                    _normal: Some(facing),
+                                    _t: Some(t),
+                                    ..Hit::init(ori.plus(&dir.scale(t).unwrap()).unwrap())
+                                }],
+                                _depth: None,
+                            })
+                        }
+                        _ => Ok(Default::default()),
+                    }
+                }
+                ShapeType::Sphere => {
+                    let c = Mat3x1::<f64>::init_from_slice(&d);
+                    let p = self.get_closest_point(&c);
+                    let v = p.minus(&c).unwrap();
+                    let dist = v.magnitude().unwrap();
+                    if dist > d[3] {
+                        return Ok(Default::default());
+                    }
+                    let normal = if dist > 0f64 {
+                        v.scale(1f64 / dist).unwrap()
+                    } else {
+                        self._normal
+                    };
+                    Ok(Intersection {
+                        _is_intersect: true,
+                        _hits: vec![Hit {
+                            _normal: Some(normal),
+                            ..Hit::init(p)
+                        }],
+                        _depth: Some(d[3] - dist),
+                    })
+                }
+                ShapeType::Plane => {
+                    //plane bounds the half space behind its normal
+                    let ori = Mat3x1::<f64>::init_from_slice(&d[0..3]);
+                    let n = Mat3x1::<f64>::init_from_slice(&d[3..6]);
+                    let (deepest, dist) = self
+                        .get_corners()
+                        .iter()
+                        .map(|x| (*x, x.minus(&ori).unwrap().dot(&n).unwrap()))
+                        .fold((self._ori, f64::INFINITY), |acc, x| {
+                            if x.1 < acc.1 {
+                                x
+                            } else {
+                                acc
+                            }
+                        });
+                    if dist > self._vicinity {
+                        return Ok(Default::default());
+                    }
+                    Ok(Intersection {
+                        _is_intersect: true,
+                        _hits: vec![Hit {
+                            _normal: Some(n),
+                            ..Hit::init(deepest.minus(&n.scale(dist).unwrap()).unwrap())
+                        }],
+                        _depth: Some(-dist),
+                    })
+                }
+                _ => Err(MazthError::UnsupportedPair(
+                    self.get_type(),
+                    other.get_type(),
+                )),
+            }
+        }
+    }
+    fn get_support(&self, v: &Mat3x1<f64>) -> Option<Mat3x1<f64>> {
+        if v.magnitude() != Some(0f64) {
+            let s = if self._half_u.dot(v).unwrap() >= 0f64 {
+                1f64
+            } else {
+                -1f64
+            };
+            let t = if self._half_v.dot(v).unwrap() >= 0f64 {
+                1f64
+            } else {
+                -1f64
+            };
+            Some(self.at_local(s, t))
+        } else {
+            None
+        }
+    }
+}
+
+impl IVicinity<f64> for Rect3 {
+    fn set_vicinity(&mut self, epsilon: f64) {
+        self._vicinity = epsilon.abs();
+    }
+    fn within_vicinity(&self, a: f64, b: f64) -> bool {
+        a + self._vicinity >= b && a - self._vicinity <= b
+    }
+}
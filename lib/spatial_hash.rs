@@ -0,0 +1,308 @@
+//uniform grid hashed on cell coordinates for neighbour queries
+use std::collections::{HashMap, HashSet};
+
+use bound::AxisAlignedBBox;
+use bound_sphere::BoundSphere;
+use i_bound::{BoundType, IBound};
+
+///bounds spanning more cells are kept with the unbounded ones instead of being hashed
+pub const CELL_COUNT_MAX: f64 = 4096f64;
+
+///stable reference to a bound stored in the grid, valid until the bound is removed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle(pub usize);
+
+#[derive(Debug, Clone)]
+pub struct SpatialHash {
+    pub _cell_size: f64,
+    pub _cells: HashMap<(i64, i64, i64), Vec<usize>>,
+    pub _bounds: Vec<Option<AxisAlignedBBox>>,
+    ///bounds inserted as spheres, used for exact distances in place of their boxes
+    pub _spheres: Vec<Option<BoundSphere>>,
+    ///indices of bounds available for reuse
+    pub _free: Vec<usize>,
+    ///bounds with infinite extents or spanning more than CELL_COUNT_MAX cells are not hashed
+    ///and are tested in every query
+    pub _unbounded: Vec<usize>,
+}
+
+fn as_sphere(bound: &dyn IBound) -> Option<BoundSphere> {
+    match bound.get_type() {
+        BoundType::Sphere => {
+            let b = bound.get_bound_data();
+            Some(BoundSphere {
+                _ori: [b[0], b[1], b[2]],
+                _radius: b[3],
+            })
+        }
+        _ => None,
+    }
+}
+
+impl SpatialHash {
+    pub fn init(cell_size: f64) -> SpatialHash {
+        assert!(cell_size > 0f64);
+        SpatialHash {
+            _cell_size: cell_size,
+            _cells: HashMap::new(),
+            _bounds: vec![],
+            _spheres: vec![],
+            _free: vec![],
+            _unbounded: vec![],
+        }
+    }
+
+    pub fn get_cell(&self, p: &[f64]) -> (i64, i64, i64) {
+        assert!(p.len() == 3);
+        (
+            (p[0] / self._cell_size).floor() as i64,
+            (p[1] / self._cell_size).floor() as i64,
+            (p[2] / self._cell_size).floor() as i64,
+        )
+    }
+
+    fn get_cell_range(&self, b: &AxisAlignedBBox) -> ((i64, i64, i64), (i64, i64, i64)) {
+        (
+            self.get_cell(&b._bound_lower),
+            self.get_cell(&b._bound_upper),
+        )
+    }
+
+    fn get_cell_count(&self, b: &AxisAlignedBBox) -> f64 {
+        let (lo, hi) = self.get_cell_range(b);
+        (hi.0 as f64 - lo.0 as f64 + 1f64)
+            * (hi.1 as f64 - lo.1 as f64 + 1f64)
+            * (hi.2 as f64 - lo.2 as f64 + 1f64)
+    }
+
+    fn is_oversized(&self, b: &AxisAlignedBBox) -> bool {
+        b.is_unbounded() || self.get_cell_count(b) > CELL_COUNT_MAX
+    }
+
+    pub fn insert(&mut self, bound: &dyn IBound) -> Handle {
+        let b = AxisAlignedBBox::init_from_bound(bound);
+        let h = match self._free.pop() {
+            Some(h) => h,
+            _ => {
+                self._bounds.push(None);
+                self._spheres.push(None);
+                self._bounds.len() - 1
+            }
+        };
+        self.insert_cells(h, &b);
+        self._bounds[h] = Some(b);
+        self._spheres[h] = as_sphere(bound);
+        Handle(h)
+    }
+
+    pub fn remove(&mut self, handle: Handle) -> bool {
+        let b = match self._bounds.get_mut(handle.0) {
+            Some(b) if b.is_some() => b.take().unwrap(),
+            _ => return false,
+        };
+        self.remove_cells(handle.0, &b);
+        self._spheres[handle.0] = None;
+        self._free.push(handle.0);
+        true
+    }
+
+    pub fn update(&mut self, handle: Handle, bound: &dyn IBound) -> bool {
+        let b_old = match self._bounds.get(handle.0) {
+            Some(Some(b)) => b.clone(),
+            _ => return false,
+        };
+        let b = AxisAlignedBBox::init_from_bound(bound);
+        let is_moved = self.get_cell_range(&b_old) != self.get_cell_range(&b)
+            || self.is_oversized(&b_old)
+            || self.is_oversized(&b);
+        if is_moved {
+            self.remove_cells(handle.0, &b_old);
+            self.insert_cells(handle.0, &b);
+        }
+        self._bounds[handle.0] = Some(b);
+        self._spheres[handle.0] = as_sphere(bound);
+        true
+    }
+
+    pub fn get_bound(&self, handle: Handle) -> Option<&AxisAlignedBBox> {
+        match self._bounds.get(handle.0) {
+            Some(Some(b)) => Some(b),
+            _ => None,
+        }
+    }
+
+    ///bound as inserted for spheres, enclosing box otherwise
+    fn get_stored(&self, h: usize) -> &dyn IBound {
+        match self._spheres[h] {
+            Some(ref s) => s,
+            _ => self._bounds[h].as_ref().unwrap(),
+        }
+    }
+
+    fn get_distance(&self, h: usize, p: &[f64]) -> f64 {
+        let q = BoundSphere {
+            _ori: [p[0], p[1], p[2]],
+            _radius: 0f64,
+        };
+        q.get_shortest_separation(self.get_stored(h))
+    }
+
+    ///returns handles of bounds within radius of point p
+    pub fn query_radius(&self, p: &[f64], radius: f64) -> Vec<Handle> {
+        assert!(p.len() == 3);
+        let query = AxisAlignedBBox {
+            _bound_lower: [p[0] - radius, p[1] - radius, p[2] - radius],
+            _bound_upper: [p[0] + radius, p[1] + radius, p[2] + radius],
+        };
+        let mut ret: Vec<Handle> = self
+            .query_candidates(&query)
+            .into_iter()
+            .filter(|x| self.get_distance(*x, p) <= radius)
+            .map(Handle)
+            .collect();
+        ret.sort_unstable();
+        ret
+    }
+
+    ///returns handles of bounds intersecting the given bound
+    pub fn query_bound(&self, bound: &dyn IBound) -> Vec<Handle> {
+        let query = AxisAlignedBBox::init_from_bound(bound);
+        let mut ret: Vec<Handle> = self
+            .query_candidates(&query)
+            .into_iter()
+            .filter(|x| self.get_stored(*x).intersect(bound))
+            .map(Handle)
+            .collect();
+        ret.sort_unstable();
+        ret
+    }
+
+    ///returns up to k handles with their distances closest to point p, in increasing distance
+    pub fn query_nearest(&self, p: &[f64], k: usize) -> Vec<(Handle, f64)> {
+        assert!(p.len() == 3);
+        let count = self._bounds.iter().filter(|x| x.is_some()).count();
+        let mut visited: HashSet<usize> = HashSet::new();
+        let mut found: Vec<(Handle, f64)> = vec![];
+        let mut add = |h: usize, found: &mut Vec<(Handle, f64)>| {
+            if visited.insert(h) {
+                found.push((Handle(h), self.get_distance(h, p)));
+            }
+        };
+        for h in self._unbounded.iter() {
+            add(*h, &mut found);
+        }
+
+        //search cells in shells of increasing distance from the cell containing p,
+        //a bound not yet visited after shell r is at least r cells away from p
+        let c = self.get_cell(p);
+        let mut r = 0i64;
+        loop {
+            let side = (2 * r + 1) as f64;
+            if side * side * side > self._cells.len() as f64 {
+                //shells now span more cells than are occupied, scan the remaining bounds directly
+                for (h, b) in self._bounds.iter().enumerate() {
+                    if b.is_some() {
+                        add(h, &mut found);
+                    }
+                }
+                found.sort_by(|a, b| a.1.total_cmp(&b.1));
+                break;
+            }
+            for x in c.0 - r..c.0 + r + 1 {
+                for y in c.1 - r..c.1 + r + 1 {
+                    for z in c.2 - r..c.2 + r + 1 {
+                        let on_shell =
+                            (x - c.0).abs() == r || (y - c.1).abs() == r || (z - c.2).abs() == r;
+                        if !on_shell {
+                            continue;
+                        }
+                        if let Some(v) = self._cells.get(&(x, y, z)) {
+                            for h in v.iter() {
+                                add(*h, &mut found);
+                            }
+                        }
+                    }
+                }
+            }
+            found.sort_by(|a, b| a.1.total_cmp(&b.1));
+            let reach = r as f64 * self._cell_size;
+            let is_done =
+                k == 0 || found.len() == count || (found.len() >= k && found[k - 1].1 <= reach);
+            if is_done {
+                break;
+            }
+            r += 1;
+        }
+        found.truncate(k);
+        found
+    }
+
+    fn query_candidates(&self, query: &AxisAlignedBBox) -> Vec<usize> {
+        let mut ret: HashSet<usize> = self._unbounded.iter().cloned().collect();
+        let (lo, hi) = self.get_cell_range(query);
+        if self.get_cell_count(query) > self._cells.len() as f64 {
+            //query spans more cells than are occupied
+            for (k, v) in self._cells.iter() {
+                if k.0 >= lo.0
+                    && k.0 <= hi.0
+                    && k.1 >= lo.1
+                    && k.1 <= hi.1
+                    && k.2 >= lo.2
+                    && k.2 <= hi.2
+                {
+                    ret.extend(v.iter());
+                }
+            }
+        } else {
+            for x in lo.0..hi.0 + 1 {
+                for y in lo.1..hi.1 + 1 {
+                    for z in lo.2..hi.2 + 1 {
+                        if let Some(v) = self._cells.get(&(x, y, z)) {
+                            ret.extend(v.iter());
+                        }
+                    }
+                }
+            }
+        }
+        ret.into_iter().collect()
+    }
+
+    fn insert_cells(&mut self, h: usize, b: &AxisAlignedBBox) {
+        if self.is_oversized(b) {
+            self._unbounded.push(h);
+            return;
+        }
+        let (lo, hi) = self.get_cell_range(b);
+        for x in lo.0..hi.0 + 1 {
+            for y in lo.1..hi.1 + 1 {
+                for z in lo.2..hi.2 + 1 {
+                    self._cells.entry((x, y, z)).or_default().push(h);
+                }
+            }
+        }
+    }
+
+    fn remove_cells(&mut self, h: usize, b: &AxisAlignedBBox) {
+        if self.is_oversized(b) {
+            self._unbounded.retain(|x| *x != h);
+            return;
+        }
+        let (lo, hi) = self.get_cell_range(b);
+        for x in lo.0..hi.0 + 1 {
+            for y in lo.1..hi.1 + 1 {
+                for z in lo.2..hi.2 + 1 {
+                    let is_empty = match self._cells.get_mut(&(x, y, z)) {
+                        Some(v) => {
+                            v.retain(|i| *i != h);
+                            v.is_empty()
+                        }
+                        _ => false,
+                    };
+                    if is_empty {
+                        self._cells.remove(&(x, y, z));
+                    }
+                }
+            }
+        }
+    }
+}
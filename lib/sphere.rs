@@ -3,6 +3,8 @@ use i_shape::{IShape, ShapeType};
 use i_vicinity::IVicinity;
 
 use bound::AxisAlignedBBox;
+use error::MazthError;
+use intersection::{Hit, Intersection};
 use mat::Mat3x1;
 
 #[derive(Debug, Clone)]
@@ -38,11 +40,31 @@ impl IShape for Sphere {
         &self._bound
     }
     // this shall test for intersection of bounding shapes first before procedding to test intersection using algorithms of higher complexity
-    fn get_intersect(&self, other: &dyn IShape) -> (bool, Option<Mat3x1<f64>>) {
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError> {
         if !self.get_bound().intersect(other.get_bound()) {
-            return (false, None);
+            return Ok(Default::default());
         } else {
             match other.get_type() {
+                ShapeType::Rect => {
+                    //see Rect3 for rectangle intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::Box => {
+                    //see recbox for box intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::OrientedBox => {
+                    //see OrientedBox for oriented box intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::Trig => {
+                    //see Triangle3 for triangle intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
+                ShapeType::TriPrism => {
+                    //see TriPrism for prism intersection
+                    other.get_intersection(self).map(|x| x.flip())
+                }
                 ShapeType::Sphere => {
                     let other_shape_data = other.get_shape_data();
                     let b_off = Mat3x1 {
@@ -59,17 +81,29 @@ impl IShape for Sphere {
                     let c = b_off.minus(&a_off).unwrap();
                     let d = c.magnitude().unwrap();
                     if d > b_r + a_r {
-                        return (false, None);
+                        return Ok(Default::default());
                     } else {
                         //calculate a mid point average
                         let f = a_r / (a_r + b_r);
                         let g = c.scale(f).unwrap();
-                        return (true, Some(a_off.plus(&g).unwrap()));
+                        let normal = if d > 0f64 {
+                            Some(c.scale(-1f64 / d).unwrap())
+                        } else {
+                            None
+                        };
+                        return Ok(Intersection {
+                            _is_intersect: true,
+                            _hits: vec![Hit {
+                                _normal: normal,
+                                ..Hit::init(a_off.plus(&g).unwrap())
+                            }],
+                            _depth: Some(a_r + b_r - d),
+                        });
                     }
                 }
                 ShapeType::Ray => {
                     //see Ray3 for ray sphere intersection
-                    return other.get_intersect(self);
+                    return other.get_intersection(self).map(|x| x.flip());
                 }
                 ShapeType::Point => {
                     let other_shape_data = other.get_shape_data();
@@ -83,10 +117,10 @@ impl IShape for Sphere {
                     let d = b_off.minus(&self._ori).unwrap();
                     for i in 0..3 {
                         if d[i] > self._radius {
-                            return (false, None);
+                            return Ok(Default::default());
                         }
                     }
-                    return (true, Some(b_off));
+                    return Ok(Intersection::init_point(b_off));
                 }
                 ShapeType::Plane => {
                     let other_shape_data = other.get_shape_data();
@@ -114,17 +148,22 @@ impl IShape for Sphere {
                     let k = b_nor.dot(&b_off).unwrap();
                     let t = b_nor.dot(&self._ori).unwrap() - k;
                     if t > self._radius {
-                        return (false, None);
+                        return Ok(Default::default());
                     } else {
-                        return (
-                            true,
-                            Some(b_nor.scale(-t).unwrap().plus(&self._ori).unwrap()),
-                        );
+                        return Ok(Intersection {
+                            _is_intersect: true,
+                            _hits: vec![Hit {
+                                _normal: Some(b_nor),
+                                ..Hit::init(b_nor.scale(-t).unwrap().plus(&self._ori).unwrap())
+                            }],
+                            _depth: Some(self._radius - t),
+                        });
                     }
                 }
-                _ => {
-                    unimplemented!();
-                }
+                _ => Err(MazthError::UnsupportedPair(
+                    self.get_type(),
+                    other.get_type(),
+                )),
             }
         }
     }
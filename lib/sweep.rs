@@ -0,0 +1,435 @@
+//analytic sweep tests of a shape translated by a displacement against a static shape, based on Ericson, Real-Time Collision Detection, 5.5
+
+use bound::AxisAlignedBBox;
+use intersect_sat::Polyhedron;
+use mat::Mat3x1;
+use plane::Plane;
+use rbox::RecBox;
+use sphere::Sphere;
+use triprism::TriPrism;
+
+///tolerance for point in polygon tests and for parallel motion
+pub const TOLERANCE: f64 = 0.000001f64;
+
+#[derive(Debug, Clone)]
+pub struct Sweep {
+    ///fraction of the displacement travelled until first contact, 0 if the shapes overlap initially
+    pub _time: f64,
+    ///contact point on the surface of the static shape
+    pub _point: Mat3x1<f64>,
+    ///unit surface normal of the static shape at the contact, pointing towards the moving shape
+    pub _normal: Mat3x1<f64>,
+}
+
+///returns v normalized, falling back to the direction opposing the displacement d for a vanishing v
+fn direction(v: &Mat3x1<f64>, d: &Mat3x1<f64>) -> Mat3x1<f64> {
+    if v.magnitude().unwrap() > TOLERANCE {
+        v.normalize().unwrap()
+    } else if d.magnitude().unwrap() > TOLERANCE {
+        d.scale(-1f64).unwrap().normalize().unwrap()
+    } else {
+        Mat3x1 {
+            _val: [0f64, 0f64, 1f64],
+        }
+    }
+}
+
+///returns earliest non-negative parameter at which ray o + d * t reaches distance r from center
+fn ray_sphere(o: &Mat3x1<f64>, d: &Mat3x1<f64>, center: &Mat3x1<f64>, r: f64) -> Option<f64> {
+    let m = o.minus(center).unwrap();
+    let a = d.dot(d).unwrap();
+    let b = m.dot(d).unwrap();
+    let c = m.dot(&m).unwrap() - r * r;
+    if c <= 0f64 {
+        return Some(0f64);
+    }
+    if b >= 0f64 || a == 0f64 {
+        //outside and moving away
+        return None;
+    }
+    let disc = b * b - a * c;
+    if disc < 0f64 {
+        return None;
+    }
+    Some((-b - disc.sqrt()) / a)
+}
+
+///returns earliest parameter at which ray o + d * t reaches distance r from segment p0, p1 at a point strictly
+///between its end points, the ray origin is expected to be outside of the capsule
+fn ray_cylinder(
+    o: &Mat3x1<f64>,
+    d: &Mat3x1<f64>,
+    p0: &Mat3x1<f64>,
+    p1: &Mat3x1<f64>,
+    r: f64,
+) -> Option<f64> {
+    let e = p1.minus(p0).unwrap();
+    let ee = e.dot(&e).unwrap();
+    let m = o.minus(p0).unwrap();
+    //components perpendicular to the segment
+    let m_perp = m.minus(&e.scale(m.dot(&e).unwrap() / ee).unwrap()).unwrap();
+    let d_perp = d.minus(&e.scale(d.dot(&e).unwrap() / ee).unwrap()).unwrap();
+    let a = d_perp.dot(&d_perp).unwrap();
+    if a <= TOLERANCE * TOLERANCE {
+        //moving parallel to the segment, covered by the end points
+        return None;
+    }
+    let b = m_perp.dot(&d_perp).unwrap();
+    let c = m_perp.dot(&m_perp).unwrap() - r * r;
+    let disc = b * b - a * c;
+    if c <= 0f64 || b >= 0f64 || disc < 0f64 {
+        return None;
+    }
+    let t = (-b - disc.sqrt()) / a;
+    let s = o
+        .plus(&d.scale(t).unwrap())
+        .unwrap()
+        .minus(p0)
+        .unwrap()
+        .dot(&e)
+        .unwrap()
+        / ee;
+    if (0f64..=1f64).contains(&s) {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+fn closest_point_segment(p: &Mat3x1<f64>, a: &Mat3x1<f64>, b: &Mat3x1<f64>) -> Mat3x1<f64> {
+    let e = b.minus(a).unwrap();
+    let ee = e.dot(&e).unwrap();
+    if ee == 0f64 {
+        return *a;
+    }
+    let s = (p.minus(a).unwrap().dot(&e).unwrap() / ee).clamp(0f64, 1f64);
+    a.plus(&e.scale(s).unwrap()).unwrap()
+}
+
+///unit normal of a planar polygon by newell's method
+fn polygon_normal(verts: &[Mat3x1<f64>]) -> Mat3x1<f64> {
+    let mut n: Mat3x1<f64> = Default::default();
+    for i in 0..verts.len() {
+        n = n
+            .plus(&verts[i].cross(&verts[(i + 1) % verts.len()]).unwrap())
+            .unwrap();
+    }
+    n.normalize().unwrap()
+}
+
+///tests whether point p on the plane of a convex polygon lies inside it, in either winding
+fn is_inside_polygon(p: &Mat3x1<f64>, verts: &[Mat3x1<f64>], n: &Mat3x1<f64>) -> bool {
+    let mut is_pos = true;
+    let mut is_neg = true;
+    for i in 0..verts.len() {
+        let a = verts[i];
+        let b = verts[(i + 1) % verts.len()];
+        let s = b
+            .minus(&a)
+            .unwrap()
+            .cross(&p.minus(&a).unwrap())
+            .unwrap()
+            .dot(n)
+            .unwrap();
+        is_pos = is_pos && s >= -TOLERANCE;
+        is_neg = is_neg && s <= TOLERANCE;
+    }
+    is_pos || is_neg
+}
+
+fn closest_point_polygon(p: &Mat3x1<f64>, verts: &[Mat3x1<f64>]) -> Mat3x1<f64> {
+    let n = polygon_normal(verts);
+    let dist = p.minus(&verts[0]).unwrap().dot(&n).unwrap();
+    let q = p.minus(&n.scale(dist).unwrap()).unwrap();
+    if is_inside_polygon(&q, verts, &n) {
+        return q;
+    }
+    let mut best = verts[0];
+    let mut best_d = f64::INFINITY;
+    for i in 0..verts.len() {
+        let x = closest_point_segment(p, &verts[i], &verts[(i + 1) % verts.len()]);
+        let d = x.minus(p).unwrap().magnitude().unwrap();
+        if d < best_d {
+            best_d = d;
+            best = x;
+        }
+    }
+    best
+}
+
+///initial contact of a sphere with center c and radius r given the closest point of the static shape
+fn overlap(c: &Mat3x1<f64>, r: f64, closest: &Mat3x1<f64>, d: &Mat3x1<f64>) -> Option<Sweep> {
+    let v = c.minus(closest).unwrap();
+    if v.magnitude().unwrap() > r {
+        return None;
+    }
+    Some(Sweep {
+        _time: 0f64,
+        _point: *closest,
+        _normal: direction(&v, d),
+    })
+}
+
+fn earliest(a: Option<Sweep>, b: Option<Sweep>) -> Option<Sweep> {
+    match (a, b) {
+        (Some(x), Some(y)) => {
+            if y._time < x._time {
+                Some(y)
+            } else {
+                Some(x)
+            }
+        }
+        (x, None) => x,
+        (None, y) => y,
+    }
+}
+
+///sweeps a sphere against a convex polygon, the sphere is expected not to touch the polygon initially
+fn sweep_polygon(c: &Mat3x1<f64>, r: f64, d: &Mat3x1<f64>, verts: &[Mat3x1<f64>]) -> Option<Sweep> {
+    let mut n = polygon_normal(verts);
+    let mut dist = c.minus(&verts[0]).unwrap().dot(&n).unwrap();
+    if dist < 0f64 {
+        n = n.scale(-1f64).unwrap();
+        dist = -dist;
+    }
+    //face interior
+    let denom = n.dot(d).unwrap();
+    if denom < 0f64 && dist >= r {
+        let t = (dist - r) / -denom;
+        if t > 1f64 {
+            return None;
+        }
+        let p = c
+            .plus(&d.scale(t).unwrap())
+            .unwrap()
+            .minus(&n.scale(r).unwrap())
+            .unwrap();
+        if is_inside_polygon(&p, verts, &n) {
+            //no other feature can be reached earlier
+            return Some(Sweep {
+                _time: t,
+                _point: p,
+                _normal: n,
+            });
+        }
+    }
+    //edges and vertices
+    let mut best = None;
+    for i in 0..verts.len() {
+        let a = verts[i];
+        let b = verts[(i + 1) % verts.len()];
+        if let Some(t) = ray_cylinder(c, d, &a, &b, r) {
+            if t <= 1f64 {
+                let center = c.plus(&d.scale(t).unwrap()).unwrap();
+                let p = closest_point_segment(&center, &a, &b);
+                best = earliest(
+                    best,
+                    Some(Sweep {
+                        _time: t,
+                        _point: p,
+                        _normal: direction(&center.minus(&p).unwrap(), d),
+                    }),
+                );
+            }
+        }
+        if let Some(t) = ray_sphere(c, d, &a, r) {
+            if t <= 1f64 {
+                let center = c.plus(&d.scale(t).unwrap()).unwrap();
+                best = earliest(
+                    best,
+                    Some(Sweep {
+                        _time: t,
+                        _point: a,
+                        _normal: direction(&center.minus(&a).unwrap(), d),
+                    }),
+                );
+            }
+        }
+    }
+    best
+}
+
+///sweeps sphere a by displacement d against the half space bounded by plane b
+pub fn sweep_sphere_plane(a: &Sphere, d: &Mat3x1<f64>, b: &Plane) -> Option<Sweep> {
+    let n = b._normal;
+    let dist = a._ori.minus(&b._offset).unwrap().dot(&n).unwrap();
+    if dist <= a._radius {
+        return Some(Sweep {
+            _time: 0f64,
+            _point: a._ori.minus(&n.scale(dist).unwrap()).unwrap(),
+            _normal: n,
+        });
+    }
+    let denom = n.dot(d).unwrap();
+    if denom >= 0f64 {
+        return None;
+    }
+    let t = (dist - a._radius) / -denom;
+    if t > 1f64 {
+        return None;
+    }
+    Some(Sweep {
+        _time: t,
+        _point: a
+            ._ori
+            .plus(&d.scale(t).unwrap())
+            .unwrap()
+            .minus(&n.scale(a._radius).unwrap())
+            .unwrap(),
+        _normal: n,
+    })
+}
+
+///sweeps sphere a by displacement d against sphere b
+pub fn sweep_sphere_sphere(a: &Sphere, d: &Mat3x1<f64>, b: &Sphere) -> Option<Sweep> {
+    let t = ray_sphere(&a._ori, d, &b._ori, a._radius + b._radius)?;
+    if t > 1f64 {
+        return None;
+    }
+    let center = a._ori.plus(&d.scale(t).unwrap()).unwrap();
+    let n = direction(&center.minus(&b._ori).unwrap(), d);
+    Some(Sweep {
+        _time: t,
+        _point: b._ori.plus(&n.scale(b._radius).unwrap()).unwrap(),
+        _normal: n,
+    })
+}
+
+///sweeps sphere a by displacement d against a two sided triangle
+pub fn sweep_sphere_triangle(a: &Sphere, d: &Mat3x1<f64>, tri: &[Mat3x1<f64>; 3]) -> Option<Sweep> {
+    let closest = closest_point_polygon(&a._ori, tri);
+    if let Some(s) = overlap(&a._ori, a._radius, &closest, d) {
+        return Some(s);
+    }
+    sweep_polygon(&a._ori, a._radius, d, tri)
+}
+
+///sweeps sphere a by displacement d against a convex polyhedron
+pub fn sweep_sphere_polyhedron(a: &Sphere, d: &Mat3x1<f64>, b: &Polyhedron) -> Option<Sweep> {
+    let faces: Vec<Vec<Mat3x1<f64>>> = b
+        ._faces
+        .iter()
+        .map(|f| f.iter().map(|i| b._verts[*i]).collect())
+        .collect();
+    //initial overlap
+    let mut best_face = 0;
+    let mut best_d = f64::NEG_INFINITY;
+    for (i, (f, n)) in faces.iter().zip(b._normals.iter()).enumerate() {
+        let dist = a._ori.minus(&f[0]).unwrap().dot(n).unwrap();
+        if dist > best_d {
+            best_d = dist;
+            best_face = i;
+        }
+    }
+    if best_d <= 0f64 {
+        //center is inside, report the closest face
+        let n = b._normals[best_face];
+        return Some(Sweep {
+            _time: 0f64,
+            _point: a._ori.minus(&n.scale(best_d).unwrap()).unwrap(),
+            _normal: n,
+        });
+    }
+    let mut closest = a._ori;
+    let mut closest_d = f64::INFINITY;
+    for f in faces.iter() {
+        let x = closest_point_polygon(&a._ori, f);
+        let dist = x.minus(&a._ori).unwrap().magnitude().unwrap();
+        if dist < closest_d {
+            closest_d = dist;
+            closest = x;
+        }
+    }
+    if let Some(s) = overlap(&a._ori, a._radius, &closest, d) {
+        return Some(s);
+    }
+    //only faces turned towards the sphere can be reached from outside
+    let mut best = None;
+    for (f, n) in faces.iter().zip(b._normals.iter()) {
+        if a._ori.minus(&f[0]).unwrap().dot(n).unwrap() > -TOLERANCE {
+            best = earliest(best, sweep_polygon(&a._ori, a._radius, d, f));
+        }
+    }
+    best
+}
+
+///sweeps sphere a by displacement d against box b
+pub fn sweep_sphere_box(a: &Sphere, d: &Mat3x1<f64>, b: &RecBox) -> Option<Sweep> {
+    sweep_sphere_polyhedron(a, d, &Polyhedron::init_from_shape(b).unwrap())
+}
+
+///sweeps sphere a by displacement d against the faces of prism b
+pub fn sweep_sphere_triprism(a: &Sphere, d: &Mat3x1<f64>, b: &TriPrism) -> Option<Sweep> {
+    sweep_sphere_polyhedron(a, d, &Polyhedron::init_from_shape(b).unwrap())
+}
+
+///sweeps box a by displacement d against box b
+pub fn sweep_aabb(a: &AxisAlignedBBox, d: &Mat3x1<f64>, b: &AxisAlignedBBox) -> Option<Sweep> {
+    let is_overlap = (0..3)
+        .all(|i| a._bound_lower[i] <= b._bound_upper[i] && a._bound_upper[i] >= b._bound_lower[i]);
+    let mut t_enter = 0f64;
+    let mut t_exit = 1f64;
+    let mut normal: Mat3x1<f64> = Default::default();
+    if is_overlap {
+        //push out along the axis of least penetration
+        let mut best = f64::INFINITY;
+        for i in 0..3 {
+            let lower = b._bound_upper[i] - a._bound_lower[i];
+            let upper = a._bound_upper[i] - b._bound_lower[i];
+            let (depth, sign) = if lower < upper {
+                (lower, 1f64)
+            } else {
+                (upper, -1f64)
+            };
+            if depth < best {
+                best = depth;
+                normal = Default::default();
+                normal._val[i] = sign;
+            }
+        }
+    } else {
+        //slab test of the relative motion
+        for i in 0..3 {
+            if d[i] == 0f64 {
+                if a._bound_upper[i] < b._bound_lower[i] || a._bound_lower[i] > b._bound_upper[i] {
+                    return None;
+                }
+                continue;
+            }
+            let (enter, exit, sign) = if d[i] > 0f64 {
+                (
+                    (b._bound_lower[i] - a._bound_upper[i]) / d[i],
+                    (b._bound_upper[i] - a._bound_lower[i]) / d[i],
+                    -1f64,
+                )
+            } else {
+                (
+                    (b._bound_upper[i] - a._bound_lower[i]) / d[i],
+                    (b._bound_lower[i] - a._bound_upper[i]) / d[i],
+                    1f64,
+                )
+            };
+            if enter > t_enter {
+                t_enter = enter;
+                normal = Default::default();
+                normal._val[i] = sign;
+            }
+            t_exit = t_exit.min(exit);
+            if t_enter > t_exit {
+                return None;
+            }
+        }
+    }
+    //center of the touching region
+    let mut point: Mat3x1<f64> = Default::default();
+    for i in 0..3 {
+        let lower = (a._bound_lower[i] + d[i] * t_enter).max(b._bound_lower[i]);
+        let upper = (a._bound_upper[i] + d[i] * t_enter).min(b._bound_upper[i]);
+        point._val[i] = (lower + upper) * 0.5f64;
+    }
+    Some(Sweep {
+        _time: t_enter,
+        _point: point,
+        _normal: normal,
+    })
+}
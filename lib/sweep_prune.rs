@@ -0,0 +1,149 @@
+//sort and sweep broadphase maintaining sorted bound endpoints along each axis
+use std::collections::HashSet;
+
+use bound::AxisAlignedBBox;
+use i_bound::IBound;
+
+///stable reference to a bound stored in the broadphase, valid until the bound is removed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle(pub usize);
+
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    pub _val: f64,
+    pub _handle: usize,
+    pub _is_min: bool,
+}
+
+impl Endpoint {
+    ///min endpoints sort before max endpoints of equal value so touching bounds are reported as overlapping
+    fn is_after(&self, other: &Endpoint) -> bool {
+        self._val > other._val || (self._val == other._val && !self._is_min && other._is_min)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SweepPrune {
+    pub _bounds: Vec<Option<AxisAlignedBBox>>,
+    ///indices of bounds available for reuse
+    pub _free: Vec<usize>,
+    ///endpoints sorted along x, y and z axis
+    pub _endpoints: [Vec<Endpoint>; 3],
+    pub _pairs: HashSet<(usize, usize)>,
+}
+
+impl SweepPrune {
+    pub fn init() -> SweepPrune {
+        Default::default()
+    }
+
+    pub fn insert(&mut self, bound: &dyn IBound) -> Handle {
+        let b = AxisAlignedBBox::init_from_bound(bound);
+        let h = match self._free.pop() {
+            Some(h) => {
+                self._bounds[h] = Some(b);
+                h
+            }
+            _ => {
+                self._bounds.push(Some(b));
+                self._bounds.len() - 1
+            }
+        };
+        //new endpoints are appended and moved into place by the next sort
+        for axis in self._endpoints.iter_mut() {
+            for is_min in [true, false].iter() {
+                axis.push(Endpoint {
+                    _val: 0f64,
+                    _handle: h,
+                    _is_min: *is_min,
+                });
+            }
+        }
+        self.sort();
+        Handle(h)
+    }
+
+    pub fn remove(&mut self, handle: Handle) -> bool {
+        match self._bounds.get(handle.0) {
+            Some(Some(_)) => {}
+            _ => return false,
+        }
+        self._bounds[handle.0] = None;
+        self._free.push(handle.0);
+        for axis in self._endpoints.iter_mut() {
+            axis.retain(|x| x._handle != handle.0);
+        }
+        self._pairs.retain(|x| x.0 != handle.0 && x.1 != handle.0);
+        true
+    }
+
+    ///sets a new bound, sorted order and pairs are updated on the next pair query
+    pub fn update(&mut self, handle: Handle, bound: &dyn IBound) -> bool {
+        match self._bounds.get_mut(handle.0) {
+            Some(Some(b)) => {
+                *b = AxisAlignedBBox::init_from_bound(bound);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn get_bound(&self, handle: Handle) -> Option<&AxisAlignedBBox> {
+        match self._bounds.get(handle.0) {
+            Some(Some(b)) => Some(b),
+            _ => None,
+        }
+    }
+
+    ///returns pairs of handles with overlapping bounds
+    pub fn query_pairs(&mut self) -> impl Iterator<Item = (Handle, Handle)> {
+        self.sort();
+        let mut ret: Vec<(Handle, Handle)> = self
+            ._pairs
+            .iter()
+            .map(|x| (Handle(x.0), Handle(x.1)))
+            .collect();
+        ret.sort_unstable();
+        ret.into_iter()
+    }
+
+    ///refreshes endpoint values and restores sorted order with insertion sort, which is near linear for coherent motion;
+    ///each swap of a min and max endpoint marks the start or the end of an overlap on that axis
+    fn sort(&mut self) {
+        for axis in 0..3 {
+            for e in self._endpoints[axis].iter_mut() {
+                let b = self._bounds[e._handle].as_ref().unwrap();
+                e._val = if e._is_min {
+                    b._bound_lower[axis]
+                } else {
+                    b._bound_upper[axis]
+                };
+            }
+            for i in 1..self._endpoints[axis].len() {
+                let mut j = i;
+                while j > 0 && self._endpoints[axis][j - 1].is_after(&self._endpoints[axis][j]) {
+                    let moving = self._endpoints[axis][j].clone();
+                    let passed = self._endpoints[axis][j - 1].clone();
+                    let pair = if moving._handle < passed._handle {
+                        (moving._handle, passed._handle)
+                    } else {
+                        (passed._handle, moving._handle)
+                    };
+                    if moving._handle == passed._handle {
+                        //endpoints of the same bound
+                    } else if moving._is_min && !passed._is_min {
+                        let a = self._bounds[pair.0].as_ref().unwrap();
+                        let b = self._bounds[pair.1].as_ref().unwrap();
+                        if a.intersect(b) {
+                            self._pairs.insert(pair);
+                        }
+                    } else if !moving._is_min && passed._is_min {
+                        self._pairs.remove(&pair);
+                    }
+                    self._endpoints[axis].swap(j - 1, j);
+                    j -= 1;
+                }
+            }
+        }
+    }
+}
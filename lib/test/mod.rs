@@ -1,21 +1,42 @@
 pub mod test_bound;
+pub mod test_bound_obb;
+pub mod test_bound_sphere;
+pub mod test_bvh;
+pub mod test_contact;
+pub mod test_dispatch;
+pub mod test_dynamic_tree;
+pub mod test_error;
+pub mod test_frustum;
+pub mod test_intersect_box;
 pub mod test_intersect_box_point;
+pub mod test_intersect_ccd;
+pub mod test_intersect_epa;
 pub mod test_intersect_gjk;
+pub mod test_intersect_mpr;
+pub mod test_intersect_obox;
 pub mod test_intersect_plane_point;
 pub mod test_intersect_point_point;
 pub mod test_intersect_ray_plane;
 pub mod test_intersect_ray_point;
 pub mod test_intersect_ray_ray;
 pub mod test_intersect_ray_sphere;
+pub mod test_intersect_rect;
+pub mod test_intersect_sat;
 pub mod test_intersect_sphere_plane;
 pub mod test_intersect_sphere_point;
 pub mod test_intersect_sphere_sphere;
+pub mod test_intersect_triangle;
 pub mod test_intersect_triprism_line;
 pub mod test_intersect_triprism_point;
+pub mod test_intersect_triprism_ray;
 pub mod test_mat1x3;
 pub mod test_mat1x4;
 pub mod test_mat3;
 pub mod test_mat3x1;
 pub mod test_mat4;
 pub mod test_mat4x1;
+pub mod test_octree;
 pub mod test_quat;
+pub mod test_spatial_hash;
+pub mod test_sweep;
+pub mod test_sweep_prune;
@@ -1,5 +1,7 @@
 use bound::AxisAlignedBBox;
+use bound_sphere::BoundSphere;
 use i_bound::IBound;
+use i_shape::ShapeType;
 
 #[test]
 fn test_bound_aabb() {
@@ -109,3 +111,222 @@ fn test_bound_aabb() {
         assert!(aabb_union._bound_upper[2] == 40f64);
     }
 }
+
+#[test]
+fn test_bound_aabb_centroid() {
+    let a = AxisAlignedBBox::init(ShapeType::Box, &[1f64, 2f64, 3f64, 1f64]);
+    assert!(a.get_centroid() == [1f64, 2f64, 3f64]);
+    //unbounded boxes keep a finite reference point
+    let b = AxisAlignedBBox::init(ShapeType::Plane, &[5f64, 5f64, 5f64, 0f64, 1f64, 0f64]);
+    assert!(b.get_centroid() == [0f64, 0f64, 0f64]);
+    let c = AxisAlignedBBox::init(ShapeType::Ray, &[1f64, 2f64, 3f64, 1f64, 0f64, -1f64]);
+    assert!(c.get_centroid() == [1f64, 2f64, 3f64]);
+    assert!(!a.is_unbounded());
+    assert!(b.is_unbounded());
+    assert!(c.is_unbounded());
+}
+
+#[test]
+fn test_bound_aabb_contains() {
+    let outer = AxisAlignedBBox::init(ShapeType::Box, &[0f64, 0f64, 0f64, 2f64]);
+    let inner = AxisAlignedBBox::init(ShapeType::Box, &[1f64, 1f64, 1f64, 1f64]);
+    let across = AxisAlignedBBox::init(ShapeType::Box, &[2f64, 0f64, 0f64, 1f64]);
+    assert!(outer.contains(&inner));
+    assert!(!inner.contains(&outer));
+    assert!(!outer.contains(&across));
+}
+
+#[test]
+fn test_bound_aabb_sphere() {
+    {
+        let aabb0 = AxisAlignedBBox {
+            _bound_lower: [0f64, 0f64, 0f64],
+            _bound_upper: [10f64, 20f64, 30f64],
+        };
+
+        let sphere0 = BoundSphere::init(ShapeType::Sphere, &[12f64, 10f64, 10f64, 2f64]);
+
+        assert!(aabb0.intersect(&sphere0));
+        assert!(sphere0.intersect(&aabb0));
+    }
+    {
+        let aabb0 = AxisAlignedBBox {
+            _bound_lower: [0f64, 0f64, 0f64],
+            _bound_upper: [10f64, 20f64, 30f64],
+        };
+
+        let sphere0 = BoundSphere::init(ShapeType::Sphere, &[12f64, 22f64, 10f64, 2f64]);
+
+        assert!(!aabb0.intersect(&sphere0));
+        assert!(!sphere0.intersect(&aabb0));
+    }
+    {
+        let aabb0 = AxisAlignedBBox {
+            _bound_lower: [0f64, 0f64, 0f64],
+            _bound_upper: [10f64, 20f64, 30f64],
+        };
+
+        let sphere0 = BoundSphere::init(ShapeType::Sphere, &[12f64, 22f64, 10f64, 2f64]);
+
+        let d = aabb0.get_shortest_separation(&sphere0);
+        assert!((d - (8f64.sqrt() - 2f64)).abs() < 0.0001f64);
+        assert!(d == sphere0.get_shortest_separation(&aabb0));
+    }
+    {
+        let aabb0 = AxisAlignedBBox {
+            _bound_lower: [0f64, -9f64, -18f64],
+            _bound_upper: [10f64, 20f64, 40f64],
+        };
+
+        let sphere0 = BoundSphere::init(ShapeType::Sphere, &[15f64, -10f64, 0f64, 5f64]);
+
+        let mut aabb_union: AxisAlignedBBox = Default::default();
+        let bounds = [&aabb0 as &dyn IBound, &sphere0 as &dyn IBound];
+        aabb_union.get_union(&bounds[..]);
+
+        assert!(aabb_union._bound_lower[0] == 0f64);
+        assert!(aabb_union._bound_lower[1] == -15f64);
+        assert!(aabb_union._bound_lower[2] == -18f64);
+
+        assert!(aabb_union._bound_upper[0] == 20f64);
+        assert!(aabb_union._bound_upper[1] == 20f64);
+        assert!(aabb_union._bound_upper[2] == 40f64);
+    }
+    {
+        //mixed bound kinds behind trait objects
+        let bounds: Vec<Box<dyn IBound>> = vec![
+            Box::new(AxisAlignedBBox {
+                _bound_lower: [0f64, 0f64, 0f64],
+                _bound_upper: [1f64, 1f64, 1f64],
+            }),
+            Box::new(BoundSphere::init(
+                ShapeType::Sphere,
+                &[5f64, 0f64, 0f64, 1f64],
+            )),
+            Box::new(BoundSphere::init(
+                ShapeType::Sphere,
+                &[0f64, 5f64, 0f64, 1f64],
+            )),
+        ];
+
+        let query = AxisAlignedBBox {
+            _bound_lower: [0.5f64, -1f64, -1f64],
+            _bound_upper: [4.5f64, 1f64, 1f64],
+        };
+
+        let hits: Vec<bool> = bounds.iter().map(|x| query.intersect(x.as_ref())).collect();
+        assert!(hits == vec![true, true, false]);
+    }
+}
+
+#[test]
+fn test_bound_shortest_separation() {
+    {
+        //overlapping boxes
+        let aabb0 = AxisAlignedBBox {
+            _bound_lower: [0f64, 0f64, 0f64],
+            _bound_upper: [10f64, 20f64, 30f64],
+        };
+
+        let aabb1 = AxisAlignedBBox {
+            _bound_lower: [5f64, 5f64, 5f64],
+            _bound_upper: [20f64, 30f64, 40f64],
+        };
+
+        assert!(aabb0.get_shortest_separation(&aabb1) == 0f64);
+        assert!(aabb1.get_shortest_separation(&aabb0) == 0f64);
+    }
+    {
+        //separated along a single axis
+        let aabb0 = AxisAlignedBBox {
+            _bound_lower: [0f64, 0f64, 0f64],
+            _bound_upper: [10f64, 20f64, 30f64],
+        };
+
+        let aabb1 = AxisAlignedBBox {
+            _bound_lower: [13f64, 5f64, 5f64],
+            _bound_upper: [20f64, 30f64, 40f64],
+        };
+
+        assert!(aabb0.get_shortest_separation(&aabb1) == 3f64);
+        assert!(aabb1.get_shortest_separation(&aabb0) == 3f64);
+    }
+    {
+        //separated along all axes, closest features are corners
+        let aabb0 = AxisAlignedBBox {
+            _bound_lower: [0f64, 0f64, 0f64],
+            _bound_upper: [10f64, 20f64, 30f64],
+        };
+
+        let aabb1 = AxisAlignedBBox {
+            _bound_lower: [-3f64, -6f64, 32f64],
+            _bound_upper: [-1f64, -2f64, 40f64],
+        };
+
+        assert!(aabb0.get_shortest_separation(&aabb1) == 3f64);
+        assert!(aabb1.get_shortest_separation(&aabb0) == 3f64);
+    }
+    {
+        let a = BoundSphere::init(ShapeType::Sphere, &[0f64, 0f64, 0f64, 1f64]);
+        let b = BoundSphere::init(ShapeType::Sphere, &[0f64, 3f64, 4f64, 2f64]);
+        let c = AxisAlignedBBox::init(ShapeType::Sphere, &[0f64, 3f64, 4f64, 2f64]);
+
+        assert!((a.get_shortest_separation(&b) - 2f64).abs() < 0.0001f64);
+        assert!((b.get_shortest_separation(&a) - 2f64).abs() < 0.0001f64);
+
+        //box enclosing sphere b is closer than sphere b itself
+        let d = c.get_shortest_separation(&a);
+        assert!(d < 2f64);
+        assert!((d - (5f64.sqrt() - 1f64)).abs() < 0.0001f64);
+        assert!(d == a.get_shortest_separation(&c));
+    }
+    {
+        //infinite extents from ray bounds
+        let ray_bound =
+            AxisAlignedBBox::init(ShapeType::Ray, &[0f64, 0f64, 0f64, 1f64, 0f64, 0f64]);
+
+        let ahead = AxisAlignedBBox {
+            _bound_lower: [100f64, 2f64, -1f64],
+            _bound_upper: [200f64, 3f64, 1f64],
+        };
+
+        let behind = AxisAlignedBBox {
+            _bound_lower: [-5f64, -1f64, -1f64],
+            _bound_upper: [-4f64, 1f64, 1f64],
+        };
+
+        assert!(ray_bound.get_shortest_separation(&ahead) == 2f64);
+        assert!(ahead.get_shortest_separation(&ray_bound) == 2f64);
+        assert!(ray_bound.get_shortest_separation(&behind) == 4f64);
+
+        let ray_bound2 =
+            AxisAlignedBBox::init(ShapeType::Ray, &[0f64, 5f64, 0f64, -1f64, 0f64, 0f64]);
+        assert!(ray_bound.get_shortest_separation(&ray_bound2) == 5f64);
+
+        let s = BoundSphere::init(ShapeType::Sphere, &[1000f64, 0f64, 10f64, 4f64]);
+        assert!(ray_bound.get_shortest_separation(&s) == 6f64);
+        assert!(s.get_shortest_separation(&ray_bound) == 6f64);
+    }
+    {
+        //infinite extents from plane bounds
+        let plane_bound =
+            AxisAlignedBBox::init(ShapeType::Plane, &[0f64, 0f64, 0f64, 0f64, 1f64, 0f64]);
+        let plane_sphere =
+            BoundSphere::init(ShapeType::Plane, &[0f64, 0f64, 0f64, 0f64, 1f64, 0f64]);
+
+        let aabb0 = AxisAlignedBBox {
+            _bound_lower: [1e10f64, -1e10f64, 0f64],
+            _bound_upper: [2e10f64, -1e9f64, 1f64],
+        };
+
+        let s = BoundSphere::init(ShapeType::Sphere, &[1e10f64, 1e10f64, 0f64, 1f64]);
+
+        assert!(plane_bound.get_shortest_separation(&aabb0) == 0f64);
+        assert!(plane_bound.get_shortest_separation(&plane_bound) == 0f64);
+        assert!(plane_bound.get_shortest_separation(&s) == 0f64);
+        assert!(plane_sphere.get_shortest_separation(&aabb0) == 0f64);
+        assert!(plane_sphere.get_shortest_separation(&plane_bound) == 0f64);
+        assert!(plane_sphere.get_shortest_separation(&plane_sphere) == 0f64);
+        assert!(!plane_bound.get_shortest_separation(&s).is_nan());
+    }
+}
@@ -0,0 +1,156 @@
+use bound::AxisAlignedBBox;
+use bound_obb::OrientedBBox;
+use bound_sphere::BoundSphere;
+use frustum::{Containment, Frustum};
+use i_bound::IBound;
+use i_shape::ShapeType;
+use mat::Mat3x1;
+use quat::Quat;
+
+fn init_rotated() -> OrientedBBox {
+    //long thin box along the diagonal of the xy plane
+    let rot = Quat::<f64>::init_from_axis_angle_degree((
+        Mat3x1 {
+            _val: [0f64, 0f64, 1f64],
+        },
+        45f64,
+    ));
+    OrientedBBox::init(&[0f64, 0f64, 0f64], &[4f64, 0.5f64, 0.5f64], &rot)
+}
+
+#[test]
+fn test_bound_obb_init() {
+    let a = init_rotated();
+    let n = 0.5f64.sqrt();
+    assert!((a._axes[0][0] - n).abs() < 0.0001f64);
+    assert!((a._axes[0][1] - n).abs() < 0.0001f64);
+    assert!(a.get_corners().len() == 8);
+
+    //enclosing axis aligned box
+    let b = AxisAlignedBBox::init_from_bound(&a);
+    let r = 4.5f64 * n;
+    assert!((b._bound_upper[0] - r).abs() < 0.0001f64);
+    assert!((b._bound_lower[1] + r).abs() < 0.0001f64);
+    assert!((b._bound_upper[2] - 0.5f64).abs() < 0.0001f64);
+
+    let c = OrientedBBox::init_from_bound(&b);
+    assert!(c._axes[0] == [1f64, 0f64, 0f64]);
+    assert!((c._half_extents[0] - r).abs() < 0.0001f64);
+}
+
+#[test]
+fn test_bound_obb_init_from_points() {
+    //corners of the rotated box are recovered by the covariance fit
+    let a = init_rotated();
+    let b = OrientedBBox::init_from_points(&a.get_corners());
+    for i in 0..3 {
+        assert!((b._ori[i] - a._ori[i]).abs() < 0.0001f64);
+    }
+    let mut h = b._half_extents;
+    h.sort_by(|x, y| y.partial_cmp(x).unwrap());
+    assert!((h[0] - 4f64).abs() < 0.0001f64);
+    assert!((h[1] - 0.5f64).abs() < 0.0001f64);
+    assert!((h[2] - 0.5f64).abs() < 0.0001f64);
+
+    //much tighter than the axis aligned box of the same points
+    let volume = 8f64 * b._half_extents.iter().product::<f64>();
+    let c = AxisAlignedBBox::init_from_bound(&a);
+    let volume_aabb: f64 = (0..3)
+        .map(|i| c._bound_upper[i] - c._bound_lower[i])
+        .product();
+    assert!(volume < volume_aabb / 4f64);
+}
+
+#[test]
+fn test_bound_obb_intersect() {
+    let a = init_rotated();
+    {
+        //inside the axis aligned box of a but clear of a itself
+        let b = AxisAlignedBBox::init(ShapeType::Box, &[2f64, -2f64, 0f64, 0.5f64]);
+        assert!(AxisAlignedBBox::init_from_bound(&a).intersect(&b));
+        assert!(!a.intersect(&b));
+        assert!(!b.intersect(&a));
+        assert!(a.get_shortest_separation(&b) > 0f64);
+        let c = AxisAlignedBBox::init(ShapeType::Box, &[2f64, 2f64, 0f64, 0.5f64]);
+        assert!(a.intersect(&c));
+        assert!(c.intersect(&a));
+        assert!(a.get_shortest_separation(&c) == 0f64);
+    }
+    {
+        let b = BoundSphere::init(ShapeType::Sphere, &[2f64, -2f64, 0f64, 1f64]);
+        assert!(!a.intersect(&b));
+        assert!(!b.intersect(&a));
+        let sep = 8f64.sqrt() - 0.5f64 - 1f64;
+        assert!((a.get_shortest_separation(&b) - sep).abs() < 0.0001f64);
+        assert!((b.get_shortest_separation(&a) - sep).abs() < 0.0001f64);
+        let c = BoundSphere::init(ShapeType::Sphere, &[2f64, 2f64, 0f64, 1f64]);
+        assert!(a.intersect(&c));
+    }
+    {
+        //crossing boxes along the other diagonal
+        let rot = Quat::<f64>::init_from_axis_angle_degree((
+            Mat3x1 {
+                _val: [0f64, 0f64, 1f64],
+            },
+            -45f64,
+        ));
+        let b = OrientedBBox::init(&[0f64, 0f64, 0f64], &[4f64, 0.5f64, 0.5f64], &rot);
+        assert!(a.intersect(&b));
+        let c = OrientedBBox::init(&[3f64, -3f64, 0f64], &[1f64, 0.5f64, 0.5f64], &rot);
+        assert!(!a.intersect(&c));
+        assert!(
+            AxisAlignedBBox::init_from_bound(&a).intersect(&AxisAlignedBBox::init_from_bound(&c))
+        );
+    }
+    {
+        //unbounded
+        let b = AxisAlignedBBox::init(ShapeType::Plane, &[0f64; 6]);
+        assert!(a.intersect(&b));
+        assert!(b.intersect(&a));
+    }
+}
+
+#[test]
+fn test_bound_obb_union() {
+    let a = init_rotated();
+    let b = OrientedBBox::init(
+        &[10f64, 10f64, 0f64],
+        &[1f64, 1f64, 1f64],
+        &Quat::<f64>::init(),
+    );
+    let mut c = OrientedBBox::init(&[0f64; 3], &[0f64; 3], &Quat::<f64>::init());
+    c.get_union(&[&a, &b]);
+    for p in a.get_corners().iter().chain(b.get_corners().iter()) {
+        let q = c.get_closest_point(p);
+        for i in 0..3 {
+            assert!((p[i] - q[i]).abs() < 0.0001f64);
+        }
+    }
+    //empty union keeps the bound
+    let mut d = b.clone();
+    d.get_union(&[]);
+    assert!(d._ori == b._ori);
+    assert!(d._half_extents == b._half_extents);
+}
+
+#[test]
+fn test_bound_obb_frustum() {
+    let f = Frustum::init_perspective(
+        &[0f64, 0f64, 10f64],
+        &[0f64, 0f64, -1f64],
+        &[0f64, 1f64, 0f64],
+        1f64,
+        1f64,
+        1f64,
+        20f64,
+    );
+    let a = init_rotated();
+    assert!(f.cull_obb(&a) == Containment::Inside);
+    assert!(f.cull_bound(&a) == Containment::Inside);
+    let b = OrientedBBox::init(
+        &[0f64, 0f64, 20f64],
+        &[1f64, 1f64, 1f64],
+        &Quat::<f64>::init(),
+    );
+    assert!(f.cull_bound(&b) == Containment::Outside);
+}
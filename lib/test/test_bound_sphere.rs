@@ -0,0 +1,217 @@
+use bound::AxisAlignedBBox;
+use bound_sphere::BoundSphere;
+use i_bound::IBound;
+use i_shape::ShapeType;
+
+#[test]
+fn test_bound_sphere_init() {
+    {
+        let a = BoundSphere::init(ShapeType::Sphere, &[1f64, 2f64, 3f64, 4f64]);
+        assert!(a.get_centroid() == [1f64, 2f64, 3f64]);
+        assert!(a.get_bound_data()[3] == 4f64);
+    }
+    {
+        let a = BoundSphere::init(ShapeType::Point, &[1f64, 2f64, 3f64]);
+        assert!(a.get_centroid() == [1f64, 2f64, 3f64]);
+        assert!(a.get_bound_data()[3] == 0f64);
+    }
+    {
+        //box of half size 1 is tightly enclosed by a sphere of radius sqrt(3)
+        let a = BoundSphere::init(ShapeType::Box, &[1f64, 1f64, 1f64, 1f64]);
+        let c = a.get_centroid();
+        assert!(c.iter().all(|x| (x - 1f64).abs() < 0.0001f64));
+        assert!((a._radius - 3f64.sqrt()).abs() < 0.0001f64);
+    }
+    {
+        let a = BoundSphere::init(ShapeType::Rect, &[0f64, 0f64, 0f64, 2f64, 4f64, 4f64]);
+        let c = a.get_centroid();
+        assert!((c[0] - 1f64).abs() < 0.0001f64);
+        assert!((c[1] - 2f64).abs() < 0.0001f64);
+        assert!((c[2] - 2f64).abs() < 0.0001f64);
+        assert!((a._radius - 3f64).abs() < 0.0001f64);
+    }
+    {
+        let a = BoundSphere::init(
+            ShapeType::Trig,
+            &[0f64, 0f64, 0f64, 2f64, 0f64, 0f64, 0f64, 2f64, 0f64],
+        );
+        let c = a.get_centroid();
+        assert!((c[0] - 1f64).abs() < 0.0001f64);
+        assert!((c[1] - 1f64).abs() < 0.0001f64);
+        assert!(c[2].abs() < 0.0001f64);
+        assert!((a._radius - 2f64.sqrt()).abs() < 0.0001f64);
+    }
+    {
+        let a = BoundSphere::init(ShapeType::Ray, &[0f64, 0f64, 0f64, 1f64, 0f64, 0f64]);
+        assert!(a._radius.is_infinite());
+        let b = BoundSphere::init(ShapeType::Plane, &[0f64, 0f64, 0f64, 0f64, 1f64, 0f64]);
+        assert!(b._radius.is_infinite());
+        assert!(a.intersect(&b));
+    }
+    {
+        //all points are enclosed by the computed bound
+        let points = [
+            [0f64, 0f64, 0f64],
+            [5f64, 1f64, -2f64],
+            [-3f64, 4f64, 1f64],
+            [2f64, -6f64, 3f64],
+            [1f64, 1f64, 7f64],
+        ];
+        let a = BoundSphere::init_from_points(&points[..]);
+        for p in points.iter() {
+            let d = ((p[0] - a._ori[0]).powi(2)
+                + (p[1] - a._ori[1]).powi(2)
+                + (p[2] - a._ori[2]).powi(2))
+            .sqrt();
+            assert!(d <= a._radius + 0.0001f64);
+        }
+    }
+}
+
+#[test]
+fn test_bound_sphere_init_shapes() {
+    {
+        let a = BoundSphere::init(ShapeType::Line, &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64]);
+        assert!(a.get_centroid() == [2f64, 0f64, 0f64]);
+        assert!(a._radius == 2f64);
+    }
+    {
+        //unit right triangle extruded by 2 along z
+        let a = BoundSphere::init(
+            ShapeType::TriPrism,
+            &[
+                0f64, 0f64, 0f64, 1f64, 0f64, 0f64, 0f64, 1f64, 0f64, 0f64, 0f64, 2f64,
+            ],
+        );
+        for p in [[1f64, 0f64, 2f64], [0f64, 1f64, 2f64], [0f64, 0f64, 0f64]].iter() {
+            let q = BoundSphere::init(ShapeType::Point, &p[..]);
+            assert!(a.intersect(&q));
+        }
+    }
+    {
+        let a = BoundSphere::init(
+            ShapeType::OrientedBox,
+            &[
+                1f64, 1f64, 1f64, 1f64, 2f64, 2f64, 1f64, 0f64, 0f64, 0f64, 1f64, 0f64, 0f64, 0f64,
+                1f64,
+            ],
+        );
+        assert!(a.get_centroid() == [1f64, 1f64, 1f64]);
+        assert!(a._radius == 3f64);
+    }
+    {
+        //custom shapes are bounded by their points
+        let a = BoundSphere::init(
+            ShapeType::Complex,
+            &[-1f64, 0f64, 0f64, 1f64, 0f64, 0f64, 0f64, 0.5f64, 0f64],
+        );
+        assert!(a.get_centroid() == [0f64, 0f64, 0f64]);
+        assert!(a._radius == 1f64);
+    }
+}
+
+#[test]
+fn test_bound_sphere_intersect() {
+    {
+        let a = BoundSphere::init(ShapeType::Sphere, &[0f64, 0f64, 0f64, 5f64]);
+        let b = BoundSphere::init(ShapeType::Sphere, &[10f64, 0f64, 0f64, 5f64]);
+        assert!(a.intersect(&b));
+        assert!(b.intersect(&a));
+        assert!(a.get_shortest_separation(&b) == 0f64);
+    }
+    {
+        let a = BoundSphere::init(ShapeType::Sphere, &[0f64, 0f64, 0f64, 5f64]);
+        let b = BoundSphere::init(ShapeType::Sphere, &[10f64, 0f64, 0f64, 4f64]);
+        assert!(!a.intersect(&b));
+        assert!(!b.intersect(&a));
+        assert!((a.get_shortest_separation(&b) - 1f64).abs() < 0.0001f64);
+    }
+}
+
+#[test]
+fn test_bound_sphere_aabb_mixed() {
+    let aabb = AxisAlignedBBox {
+        _bound_lower: [0f64, 0f64, 0f64],
+        _bound_upper: [10f64, 10f64, 10f64],
+    };
+    {
+        let a = BoundSphere::init(ShapeType::Sphere, &[12f64, 5f64, 5f64, 2f64]);
+        assert!(a.intersect(&aabb));
+        assert!(a.get_shortest_separation(&aabb) == 0f64);
+    }
+    {
+        //closest point of box is its corner
+        let a = BoundSphere::init(ShapeType::Sphere, &[12f64, 12f64, 5f64, 2f64]);
+        assert!(!a.intersect(&aabb));
+        assert!((a.get_shortest_separation(&aabb) - (8f64.sqrt() - 2f64)).abs() < 0.0001f64);
+    }
+    {
+        let a = BoundSphere::init(ShapeType::Sphere, &[5f64, 5f64, 5f64, 1f64]);
+        assert!(a.intersect(&aabb));
+    }
+    {
+        //usable behind trait objects
+        let bounds: Vec<Box<dyn IBound>> = vec![
+            Box::new(BoundSphere::init(
+                ShapeType::Sphere,
+                &[-2f64, 0f64, 0f64, 1f64],
+            )),
+            Box::new(aabb.clone()),
+        ];
+        let q = BoundSphere::init(ShapeType::Point, &[-1f64, 0f64, 0f64]);
+        let hits: Vec<bool> = bounds.iter().map(|x| q.intersect(x.as_ref())).collect();
+        assert!(hits == vec![true, false]);
+    }
+}
+
+#[test]
+fn test_bound_sphere_union() {
+    {
+        let a = BoundSphere::init(ShapeType::Sphere, &[0f64, 0f64, 0f64, 1f64]);
+        let b = BoundSphere::init(ShapeType::Sphere, &[10f64, 0f64, 0f64, 1f64]);
+        let mut u: BoundSphere = Default::default();
+        u.get_union(&[&a as &dyn IBound, &b as &dyn IBound]);
+        let c = u.get_centroid();
+        assert!((c[0] - 5f64).abs() < 0.0001f64);
+        assert!(c[1].abs() < 0.0001f64);
+        assert!(c[2].abs() < 0.0001f64);
+        assert!((u._radius - 6f64).abs() < 0.0001f64);
+    }
+    {
+        //empty union keeps the bound
+        let mut u = BoundSphere::init(ShapeType::Sphere, &[1f64, 2f64, 3f64, 4f64]);
+        u.get_union(&[]);
+        assert!(u.get_centroid() == [1f64, 2f64, 3f64]);
+        assert!(u._radius == 4f64);
+    }
+    {
+        //enclosed sphere does not grow the union
+        let a = BoundSphere::init(ShapeType::Sphere, &[0f64, 0f64, 0f64, 10f64]);
+        let b = BoundSphere::init(ShapeType::Sphere, &[1f64, 1f64, 0f64, 1f64]);
+        let mut u: BoundSphere = Default::default();
+        u.get_union(&[&a as &dyn IBound, &b as &dyn IBound]);
+        assert!(u.get_centroid() == [0f64, 0f64, 0f64]);
+        assert!(u._radius == 10f64);
+    }
+    {
+        let a = BoundSphere::init(ShapeType::Sphere, &[0f64, 0f64, 0f64, 1f64]);
+        let b = AxisAlignedBBox {
+            _bound_lower: [2f64, -1f64, -1f64],
+            _bound_upper: [4f64, 1f64, 1f64],
+        };
+        let mut u: BoundSphere = Default::default();
+        u.get_union(&[&a as &dyn IBound, &b as &dyn IBound]);
+        assert!(u.intersect(&a));
+        assert!(u.intersect(&b));
+        for i in [
+            [4f64, 1f64, 1f64],
+            [4f64, -1f64, -1f64],
+            [-1f64, 0f64, 0f64],
+        ]
+        .iter()
+        {
+            let p = BoundSphere::init(ShapeType::Point, &i[..]);
+            assert!(u.get_shortest_separation(&p) < 0.0001f64);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use bound::AxisAlignedBBox;
+use bvh::Bvh;
+use frustum::Frustum;
+use i_comparable::IComparableError;
+use i_shape::IShape;
+use line::Line3;
+use mat::Mat3x1;
+use plane::Plane;
+use ray::Ray3;
+use sphere::Sphere;
+
+fn init_spheres() -> Vec<Box<dyn IShape>> {
+    let mut shapes: Vec<Box<dyn IShape>> = vec![];
+    for i in 0..5 {
+        for j in 0..5 {
+            for k in 0..4 {
+                let r = 0.3f64 + 0.2f64 * (((i + j * 3 + k * 7) % 5) as f64);
+                shapes.push(Box::new(Sphere::init(
+                    &[2f64 * i as f64, 2f64 * j as f64, 2f64 * k as f64],
+                    r,
+                )));
+            }
+        }
+    }
+    shapes
+}
+
+#[test]
+fn test_bvh_query_bound() {
+    let bvh = Bvh::init(init_spheres());
+    let expect_shapes = init_spheres();
+
+    let query = AxisAlignedBBox {
+        _bound_lower: [1f64, 1f64, 1f64],
+        _bound_upper: [4.5f64, 3f64, 3f64],
+    };
+    let ret = bvh.query_bound(&query);
+    let expect: Vec<usize> = expect_shapes
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| x.get_bound().intersect(&query))
+        .map(|(i, _)| i)
+        .collect();
+    assert!(!expect.is_empty());
+    assert!(ret == expect);
+
+    let query_far = AxisAlignedBBox {
+        _bound_lower: [100f64, 100f64, 100f64],
+        _bound_upper: [101f64, 101f64, 101f64],
+    };
+    assert!(bvh.query_bound(&query_far).is_empty());
+
+    //narrowphase against a query shape
+    let s = Sphere::init(&[3f64, 2f64, 2f64], 0.8f64);
+    let ret: Vec<usize> = bvh.query_intersect(&s).iter().map(|x| x.0).collect();
+    let expect: Vec<usize> = expect_shapes
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| s.get_intersect(x.as_ref()).0)
+        .map(|(i, _)| i)
+        .collect();
+    assert!(!expect.is_empty());
+    assert!(ret == expect);
+}
+
+#[test]
+fn test_bvh_query_ray() {
+    let bvh = Bvh::init(init_spheres());
+    {
+        let r = Ray3::init(&[-5f64, 2f64, 4f64], &[1f64, 0f64, 0f64]);
+        match bvh.query_ray(&r) {
+            Some((i, loc)) => {
+                assert!(i == 6);
+                let radius = bvh._shapes[i].get_shape_data()[3];
+                assert!(loc
+                    .is_equal(
+                        &Mat3x1 {
+                            _val: [-radius, 2f64, 4f64]
+                        },
+                        0.0001f64
+                    )
+                    .unwrap());
+            }
+            _ => panic!("unexpected result for bvh ray query"),
+        }
+    }
+    {
+        //ray from the opposite side hits the last sphere along the row
+        let r = Ray3::init(&[20f64, 2f64, 4f64], &[-1f64, 0f64, 0f64]);
+        match bvh.query_ray(&r) {
+            Some((i, _)) => assert!(i == 86),
+            _ => panic!("unexpected result for bvh ray query"),
+        }
+    }
+    {
+        let r = Ray3::init(&[-5f64, 2f64, 4f64], &[-1f64, 0f64, 0f64]);
+        assert!(bvh.query_ray(&r).is_none());
+    }
+    {
+        let r = Ray3::init(&[-5f64, -5f64, -5f64], &[1f64, 1f64, 1f64]);
+        match bvh.query_ray(&r) {
+            Some((i, _)) => assert!(i == 0),
+            _ => panic!("unexpected result for bvh ray query"),
+        }
+    }
+}
+
+#[test]
+fn test_bvh_query_self_intersect() {
+    let bvh = Bvh::init(init_spheres());
+    let shapes = init_spheres();
+
+    let mut expect = vec![];
+    for i in 0..shapes.len() {
+        for j in i + 1..shapes.len() {
+            if shapes[i].get_intersect(shapes[j].as_ref()).0 {
+                expect.push((i, j));
+            }
+        }
+    }
+    assert!(!expect.is_empty());
+    assert!(bvh.query_self_intersect() == expect);
+
+    let empty = Bvh::init(vec![]);
+    assert!(empty.query_self_intersect().is_empty());
+    assert!(empty
+        .query_ray(&Ray3::init(&[0f64, 0f64, 0f64], &[1f64, 0f64, 0f64]))
+        .is_none());
+}
+
+#[test]
+fn test_bvh_unsupported_pairs() {
+    //crossing planes, a sphere inside a frustum and a line through both are not supported pairs
+    let shapes: Vec<Box<dyn IShape>> = vec![
+        Box::new(Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64])),
+        Box::new(Plane::init(&[0f64, 0f64, 0f64], &[1f64, 0f64, 0f64])),
+        Box::new(Frustum::init_perspective(
+            &[1f64, 1f64, -5f64],
+            &[0f64, 0f64, 1f64],
+            &[0f64, 1f64, 0f64],
+            std::f64::consts::FRAC_PI_2,
+            1f64,
+            0.1f64,
+            10f64,
+        )),
+        Box::new(Sphere::init(&[1f64, 1f64, 1f64], 0.5f64)),
+        Box::new(Sphere::init(&[1.5f64, 1f64, 1f64], 0.5f64)),
+        Box::new(Line3::init(&[-5f64, 1f64, 1f64], &[5f64, 1f64, 1f64])),
+    ];
+    let bvh = Bvh::init(shapes);
+    let pairs = bvh.query_self_intersect();
+    assert!(pairs.contains(&(3, 4)));
+    assert!(!pairs.contains(&(0, 1)));
+    assert!(!pairs.contains(&(2, 3)));
+
+    let ret = bvh.query_intersect(&Sphere::init(&[1f64, 1f64, 1f64], 0.1f64));
+    assert!(ret.iter().any(|x| x.0 == 3));
+
+    let (i, _) = bvh
+        .query_ray(&Ray3::init(&[-5f64, 1f64, 1f64], &[1f64, 0f64, 0f64]))
+        .expect("bvh ray query unexpected");
+    assert!(i == 3);
+}
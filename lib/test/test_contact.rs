@@ -0,0 +1,250 @@
+use contact;
+use error::MazthError;
+use i_shape::ShapeType;
+use mat::Mat3x1;
+use obox::OrientedBox;
+use plane::Plane;
+use point::Point3;
+use quat::Quat;
+use rbox::RecBox;
+use sphere::Sphere;
+use triprism::TriPrism;
+
+#[test]
+fn test_contact_box_box() {
+    let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    let b = RecBox::init(&[0.3f64, 1.9f64, 0.2f64], 1f64);
+    {
+        let m = contact::query_manifold(&a, &b).expect("manifold unexpected");
+        assert!((m._normal._val[1] - 1f64).abs() < 0.000001f64);
+        assert_eq!(m._points.len(), 4);
+        for p in m._points.iter() {
+            assert!((p._depth - 0.1f64).abs() < 0.000001f64);
+            assert!((p._point._val[1] - 0.95f64).abs() < 0.000001f64);
+            assert!(p._point._val[0] >= -0.7f64 - 0.000001f64 && p._point._val[0] <= 1.000001f64);
+            assert!(p._point._val[2] >= -0.8f64 - 0.000001f64 && p._point._val[2] <= 1.000001f64);
+        }
+    }
+    {
+        let m = contact::query_manifold(&b, &a).expect("manifold unexpected");
+        assert!((m._normal._val[1] + 1f64).abs() < 0.000001f64);
+        assert_eq!(m._points.len(), 4);
+    }
+    {
+        let c = RecBox::init(&[0.3f64, 2.1f64, 0.2f64], 1f64);
+        assert!(contact::query_manifold(&a, &c).unwrap()._points.is_empty());
+    }
+}
+
+#[test]
+fn test_contact_box_prism() {
+    let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    //prism base crossing every edge of the box top face clips to a hexagon, reduced to 4 points
+    let b = TriPrism::init(
+        &[
+            0f64, 0.9f64, -1.6f64, -1.6f64, 0.9f64, 1.2f64, 1.6f64, 0.9f64, 1.2f64,
+        ],
+        1f64,
+    );
+    let m = contact::query_manifold(&a, &b).expect("manifold unexpected");
+    assert!((m._normal._val[1] - 1f64).abs() < 0.000001f64);
+    assert_eq!(m._points.len(), contact::POINTS_MAX);
+    for p in m._points.iter() {
+        assert!((p._depth - 0.1f64).abs() < 0.000001f64);
+    }
+    //prism resting on a single edge
+    let c = TriPrism::init(
+        &[0f64, 0.9f64, 0f64, 1f64, 2f64, 0f64, -1f64, 2f64, 0f64],
+        1f64,
+    );
+    let m = contact::query_manifold(&a, &c).expect("manifold unexpected");
+    assert_eq!(m._points.len(), 2);
+    for p in m._points.iter() {
+        assert!((p._depth - 0.1f64).abs() < 0.000001f64);
+    }
+}
+
+#[test]
+fn test_contact_plane() {
+    let ground = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64]);
+    {
+        let b = RecBox::init(&[5f64, 0.9f64, 0f64], 1f64);
+        let m = contact::query_manifold(&ground, &b).expect("manifold unexpected");
+        assert!((m._normal._val[1] - 1f64).abs() < 0.000001f64);
+        assert_eq!(m._points.len(), 4);
+        for p in m._points.iter() {
+            assert!((p._depth - 0.1f64).abs() < 0.000001f64);
+            assert!((p._point._val[1] + 0.05f64).abs() < 0.000001f64);
+        }
+        let m = contact::query_manifold(&b, &ground).expect("manifold unexpected");
+        assert!((m._normal._val[1] + 1f64).abs() < 0.000001f64);
+    }
+    {
+        let b = Sphere::init(&[0f64, 0.8f64, 3f64], 1f64);
+        let m = contact::query_manifold(&b, &ground).expect("manifold unexpected");
+        assert!((m._normal._val[1] + 1f64).abs() < 0.000001f64);
+        assert_eq!(m._points.len(), 1);
+        assert!((m._points[0]._depth - 0.2f64).abs() < 0.000001f64);
+        assert!((m._points[0]._point._val[1] + 0.1f64).abs() < 0.000001f64);
+    }
+    {
+        let b = Sphere::init(&[0f64, 1.1f64, 3f64], 1f64);
+        assert!(contact::query_manifold(&ground, &b)
+            .unwrap()
+            ._points
+            .is_empty());
+    }
+}
+
+#[test]
+fn test_contact_sphere() {
+    {
+        let a = Sphere::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = Sphere::init(&[0f64, 0f64, 1.5f64], 1f64);
+        let m = contact::query_manifold(&a, &b).expect("manifold unexpected");
+        assert!((m._normal._val[2] - 1f64).abs() < 0.000001f64);
+        assert!((m._points[0]._depth - 0.5f64).abs() < 0.000001f64);
+        assert!((m._points[0]._point._val[2] - 0.75f64).abs() < 0.000001f64);
+    }
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = Sphere::init(&[0.5f64, 1.5f64, 0f64], 1f64);
+        let m = contact::query_manifold(&a, &b).expect("manifold unexpected");
+        assert!((m._normal._val[1] - 1f64).abs() < 0.0001f64);
+        assert!((m._points[0]._depth - 0.5f64).abs() < 0.0001f64);
+        let m = contact::query_manifold(&b, &a).expect("manifold unexpected");
+        assert!((m._normal._val[1] + 1f64).abs() < 0.0001f64);
+    }
+    {
+        //center inside of the box
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = Sphere::init(&[0.2f64, 0f64, 0.8f64], 0.5f64);
+        let m = contact::query_manifold(&a, &b).expect("manifold unexpected");
+        assert!((m._normal._val[2] - 1f64).abs() < 0.000001f64);
+        assert!((m._points[0]._depth - 0.7f64).abs() < 0.000001f64);
+    }
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = Sphere::init(&[2f64, 2f64, 0f64], 1f64);
+        assert!(contact::query_manifold(&a, &b).unwrap()._points.is_empty());
+    }
+}
+
+#[test]
+fn test_contact_persistence() {
+    let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    let mut previous =
+        contact::query_manifold(&a, &RecBox::init(&[0.3f64, 1.9f64, 0.2f64], 1f64)).unwrap();
+    for (i, p) in previous._points.iter_mut().enumerate() {
+        p._impulse = i as f64 + 1f64;
+    }
+    let mut current =
+        contact::query_manifold(&a, &RecBox::init(&[0.31f64, 1.91f64, 0.2f64], 1f64)).unwrap();
+    current.match_previous(&previous);
+    for p in current._points.iter() {
+        let old = previous._points.iter().find(|x| x._id == p._id).unwrap();
+        assert_eq!(p._impulse, old._impulse);
+        assert_eq!(p._age, 1);
+    }
+    //contact along a different face does not match
+    let mut other =
+        contact::query_manifold(&a, &RecBox::init(&[1.9f64, 0.3f64, 0.2f64], 1f64)).unwrap();
+    other.match_previous(&previous);
+    assert!(other
+        ._points
+        .iter()
+        .all(|x| x._age == 0 && x._impulse == 0f64));
+}
+
+#[test]
+fn test_contact_oriented_box() {
+    let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    //turned about the contact normal, the faces still meet
+    let rot = Quat::<f64>::init_from_axis_angle_degree((
+        Mat3x1 {
+            _val: [0f64, 1f64, 0f64],
+        },
+        45f64,
+    ));
+    let b = OrientedBox::init(&[0f64, 1.9f64, 0f64], &[1f64, 1f64, 1f64], &rot);
+    let m = contact::query_manifold(&a, &b).expect("manifold unexpected");
+    assert!((m._normal._val[1] - 1f64).abs() < 0.000001f64);
+    assert_eq!(m._points.len(), contact::POINTS_MAX);
+    for p in m._points.iter() {
+        assert!((p._depth - 0.1f64).abs() < 0.000001f64);
+    }
+    let ground = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64]);
+    let b = OrientedBox::init(&[0f64, 0.9f64, 0f64], &[1f64, 1f64, 1f64], &rot);
+    let m = contact::query_manifold(&b, &ground).expect("manifold unexpected");
+    assert_eq!(m._points.len(), 4);
+    let s = Sphere::init(&[0f64, 2.5f64, 0f64], 1f64);
+    let m = contact::query_manifold(&b, &s).expect("manifold unexpected");
+    assert!((m._points[0]._depth - 0.4f64).abs() < 0.0001f64);
+}
+
+#[test]
+fn test_contact_unsupported() {
+    let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    let b = Point3::init(&[0f64, 0f64, 0f64]);
+    assert_eq!(
+        contact::query_manifold(&a, &b).unwrap_err(),
+        MazthError::UnsupportedPair(ShapeType::Box, ShapeType::Point)
+    );
+}
+
+#[test]
+fn test_contact_feature_id() {
+    let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    //prism ridge crossing the vertical edge of the box at x = y = 1
+    let h = 0.5f64.sqrt();
+    let b = TriPrism::init(
+        &[
+            0.95f64 - h,
+            0.95f64 + h,
+            0f64,
+            3f64 - h,
+            3f64 + h,
+            -1f64,
+            2f64 - h,
+            2f64 + h,
+            1.5f64,
+        ],
+        2f64,
+    );
+    let m = contact::query_manifold(&a, &b).expect("manifold unexpected");
+    assert_eq!(m._points.len(), 1);
+    let edge = m._points[0]._id;
+    assert_eq!(edge._kind, contact::FeatureKind::Edge);
+    //face contact with the same indices is told apart by kind
+    let face = contact::FeatureId {
+        _kind: contact::FeatureKind::Face,
+        ..edge
+    };
+    assert!(face != edge);
+
+    let c = RecBox::init(&[0.3f64, 1.9f64, 0.2f64], 1f64);
+    let m = contact::query_manifold(&a, &c).expect("manifold unexpected");
+    assert!(m
+        ._points
+        .iter()
+        .all(|x| x._id._kind == contact::FeatureKind::Face));
+}
+
+#[test]
+fn test_contact_reduce_duplicates() {
+    //flat prism has coincident vertex pairs on a triangular hull
+    let ground = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64]);
+    let b = TriPrism::init(
+        &[
+            0f64, -0.1f64, 0f64, 0f64, -0.1f64, 1f64, 1f64, -0.1f64, 0f64,
+        ],
+        0f64,
+    );
+    let m = contact::query_manifold(&ground, &b).expect("manifold unexpected");
+    assert_eq!(m._points.len(), 3);
+    for (i, p) in m._points.iter().enumerate() {
+        for q in m._points[i + 1..].iter() {
+            assert!(p._point.minus(&q._point).unwrap().magnitude().unwrap() > 0.5f64);
+        }
+    }
+}
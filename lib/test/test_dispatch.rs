@@ -0,0 +1,306 @@
+use i_comparable::IComparableError;
+
+use bound::AxisAlignedBBox;
+use dispatch::{Dispatcher, ShapeKey, NATIVE_PAIRS};
+use error::MazthError;
+use frustum::Frustum;
+use i_bound::IBound;
+use i_shape::{IShape, ShapeType};
+use i_vicinity::IVicinity;
+use intersection::Intersection;
+use line::Line3;
+use mat::Mat3x1;
+use obox::OrientedBox;
+use plane::Plane;
+use point::Point3;
+use quat::Quat;
+use ray::Ray3;
+use rbox::RecBox;
+use rect::Rect3;
+use sphere::Sphere;
+use triangle::Triangle3;
+use triprism::TriPrism;
+
+///user defined tetrahedron known to the dispatcher only through its support function
+struct Tetra {
+    _verts: [Mat3x1<f64>; 4],
+    _bound: AxisAlignedBBox,
+    _id: u32,
+}
+
+impl Tetra {
+    fn init(ori: &[f64], size: f64, id: u32) -> Tetra {
+        let offsets = [
+            [0f64, 0f64, 0f64],
+            [size, 0f64, 0f64],
+            [0f64, size, 0f64],
+            [0f64, 0f64, size],
+        ];
+        let mut verts: [Mat3x1<f64>; 4] = Default::default();
+        for (v, o) in verts.iter_mut().zip(offsets.iter()) {
+            *v = Mat3x1 {
+                _val: [ori[0] + o[0], ori[1] + o[1], ori[2] + o[2]],
+            };
+        }
+        Tetra {
+            _verts: verts,
+            _bound: AxisAlignedBBox::init(
+                ShapeType::Rect,
+                &[
+                    ori[0],
+                    ori[1],
+                    ori[2],
+                    ori[0] + size,
+                    ori[1] + size,
+                    ori[2] + size,
+                ],
+            ),
+            _id: id,
+        }
+    }
+}
+
+impl IShape for Tetra {
+    fn get_shape_data(&self) -> Vec<f64> {
+        self._verts.iter().flat_map(|x| x._val.to_vec()).collect()
+    }
+    fn get_type(&self) -> ShapeType {
+        ShapeType::Complex
+    }
+    fn get_bound(&self) -> &dyn IBound {
+        &self._bound
+    }
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError> {
+        Err(MazthError::UnsupportedPair(
+            self.get_type(),
+            other.get_type(),
+        ))
+    }
+    fn get_support(&self, v: &Mat3x1<f64>) -> Option<Mat3x1<f64>> {
+        self._verts
+            .iter()
+            .max_by(|a, b| a.dot(v).unwrap().partial_cmp(&b.dot(v).unwrap()).unwrap())
+            .cloned()
+    }
+    fn get_custom_id(&self) -> u32 {
+        self._id
+    }
+}
+
+impl IVicinity<f64> for Tetra {
+    fn set_vicinity(&mut self, _epsilon: f64) {}
+    fn within_vicinity(&self, a: f64, b: f64) -> bool {
+        a == b
+    }
+}
+
+///one overlapping shape of each native type, all containing or passing through origin
+fn init_shapes() -> Vec<Box<dyn IShape>> {
+    vec![
+        Box::new(Point3::init(&[0f64, 0f64, 0f64])),
+        Box::new(Ray3::init(&[-5f64, 0f64, 0f64], &[1f64, 0f64, 0f64])),
+        Box::new(Sphere::init(&[0f64, 0f64, 0f64], 1f64)),
+        Box::new(Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64])),
+        Box::new(Triangle3::init(&[
+            -1f64, 0f64, -1f64, 1f64, 0f64, -1f64, 0f64, 0f64, 1f64,
+        ])),
+        Box::new(RecBox::init(&[0f64, 0f64, 0f64], 1f64)),
+        Box::new(OrientedBox::init(
+            &[0f64, 0f64, 0f64],
+            &[1f64, 1f64, 1f64],
+            &Quat::<f64>::init_from_axis_angle_degree((
+                Mat3x1 {
+                    _val: [0f64, 1f64, 0f64],
+                },
+                30f64,
+            )),
+        )),
+        Box::new(Rect3::init(
+            &[0f64, 0f64, 0f64],
+            &[1f64, 0f64, 0f64],
+            &[0f64, 0f64, 1f64],
+        )),
+        Box::new(TriPrism::init(
+            &[-1f64, -1f64, -1f64, 1f64, -1f64, -1f64, 0f64, 1f64, -1f64],
+            2f64,
+        )),
+        Box::new(Line3::init(&[-2f64, 0f64, 0f64], &[2f64, 0f64, 0f64])),
+        Box::new(Frustum::init_perspective(
+            &[0f64, 0f64, -5f64],
+            &[0f64, 0f64, 1f64],
+            &[0f64, 1f64, 0f64],
+            1f64,
+            1f64,
+            0.1f64,
+            10f64,
+        )),
+    ]
+}
+
+#[test]
+fn test_dispatch_native() {
+    let d = Dispatcher::init();
+    let a = Ray3::init(&[5f64, 0f64, 0f64], &[1f64, 0f64, 0f64]);
+    let b = Sphere::init(&[20f64, 0f64, 0f64], 5f64);
+    assert!(d.is_registered(ShapeType::Sphere, ShapeType::Ray));
+    let ret = d.query_intersection(&a, &b).unwrap();
+    assert_eq!(ret._hits.len(), 2);
+    assert!(ret._hits[0]
+        ._normal
+        .unwrap()
+        .is_equal(
+            &Mat3x1 {
+                _val: [-1f64, 0f64, 0f64]
+            },
+            0.0001f64
+        )
+        .unwrap());
+    //swapped pair reports normals seen from the sphere
+    let ret = d.query_intersection(&b, &a).unwrap();
+    assert!(ret._hits[0]
+        ._normal
+        .unwrap()
+        .is_equal(
+            &Mat3x1 {
+                _val: [1f64, 0f64, 0f64]
+            },
+            0.0001f64
+        )
+        .unwrap());
+}
+
+#[test]
+fn test_dispatch_gjk_fallback() {
+    let mut d = Dispatcher::init();
+    //frustum with its far face at x = 1
+    let a = Frustum::init_perspective(
+        &[-9f64, 0f64, 0f64],
+        &[1f64, 0f64, 0f64],
+        &[0f64, 1f64, 0f64],
+        std::f64::consts::FRAC_PI_2,
+        1f64,
+        0.1f64,
+        10f64,
+    );
+    let b = Sphere::init(&[1.5f64, 0f64, 0f64], 1f64);
+    assert!(!d.is_registered(ShapeType::Frustum, ShapeType::Sphere));
+    let ret = d.query_intersection(&a, &b).unwrap();
+    assert!(ret._is_intersect);
+    assert!((ret._depth.unwrap() - 0.5f64).abs() < 0.001f64);
+    assert!(ret._hits[0]
+        ._normal
+        .unwrap()
+        .is_equal(
+            &Mat3x1 {
+                _val: [-1f64, 0f64, 0f64]
+            },
+            0.001f64
+        )
+        .unwrap());
+    let c = Sphere::init(&[3f64, 2f64, 2f64], 1f64);
+    assert!(!d.query_intersection(&a, &c).unwrap()._is_intersect);
+
+    //no support function for planes
+    let p = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64]);
+    assert_eq!(
+        d.query_intersection(&a, &p).unwrap_err(),
+        MazthError::UnsupportedPair(ShapeType::Frustum, ShapeType::Plane)
+    );
+
+    d._is_gjk_fallback = false;
+    assert_eq!(
+        d.query_intersection(&b, &a).unwrap_err(),
+        MazthError::UnsupportedPair(ShapeType::Sphere, ShapeType::Frustum)
+    );
+}
+
+#[test]
+fn test_dispatch_complex() {
+    let mut d = Dispatcher::init();
+    let t = Tetra::init(&[0f64, 0f64, 0f64], 2f64, 1);
+    let other = Tetra::init(&[0f64, 0f64, 0f64], 2f64, 2);
+    let a = RecBox::init(&[1.5f64, 0.5f64, 0.5f64], 1f64);
+    let b = Sphere::init(&[-0.5f64, 0.5f64, 0.5f64], 1f64);
+
+    //user shape resolved through its support function
+    assert!(d.query_intersection(&t, &a).unwrap()._is_intersect);
+    assert!(d.query_intersection(&a, &t).unwrap()._is_intersect);
+    let far = RecBox::init(&[5f64, 5f64, 5f64], 1f64);
+    assert!(!d.query_intersection(&far, &t).unwrap()._is_intersect);
+
+    //user handler takes precedence and serves both orders
+    d.register(ShapeKey::Custom(1), ShapeType::Sphere, |x, y| {
+        //distance of the sphere to the first vertex only
+        let v = x.get_shape_data();
+        let s = y.get_shape_data();
+        let d = Mat3x1 {
+            _val: [s[0] - v[0], s[1] - v[1], s[2] - v[2]],
+        }
+        .magnitude()
+        .unwrap();
+        Ok(Intersection {
+            _is_intersect: d <= s[3],
+            _hits: vec![],
+            _depth: Some(s[3] - d),
+        })
+    });
+    let ret = d.query_intersection(&b, &t).unwrap();
+    assert!(ret._is_intersect);
+    assert!((ret._depth.unwrap() - (1f64 - 0.75f64.sqrt())).abs() < 0.0001f64);
+    //custom shapes with another id keep using the support function
+    let ret = d.query_intersection(&b, &other).unwrap();
+    assert!((ret._depth.unwrap() - 0.5f64).abs() < 0.001f64);
+    assert!(d
+        .unregister(ShapeKey::Custom(1), ShapeType::Sphere)
+        .is_some());
+    assert!(!d.is_registered(ShapeType::Sphere, ShapeKey::Custom(1)));
+}
+
+#[test]
+fn test_dispatch_native_pairs() {
+    let shapes = init_shapes();
+    let find = |t: ShapeType| shapes.iter().find(|x| x.get_type() == t).unwrap();
+    //every listed pair is implemented by its first shape
+    for (a, b) in NATIVE_PAIRS.iter() {
+        assert!(
+            find(*a).get_intersection(find(*b).as_ref()).is_ok(),
+            "{:?} {:?}",
+            a,
+            b
+        );
+    }
+    //and no implemented pair is missing from the list
+    for a in shapes.iter() {
+        for b in shapes.iter() {
+            let (ta, tb) = (a.get_type(), b.get_type());
+            if a.get_intersection(b.as_ref()).is_ok() {
+                assert!(
+                    NATIVE_PAIRS.contains(&(ta, tb)) || NATIVE_PAIRS.contains(&(tb, ta)),
+                    "{:?} {:?}",
+                    ta,
+                    tb
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_dispatch_native_pairs_symmetric() {
+    let shapes = init_shapes();
+    let find = |t: ShapeType| shapes.iter().find(|x| x.get_type() == t).unwrap();
+    //reversed order of every native pair is served by the shapes as well and agrees on the result
+    for (a, b) in NATIVE_PAIRS.iter() {
+        let (sa, sb) = (find(*a), find(*b));
+        let ab = sa.get_intersection(sb.as_ref());
+        let ba = sb.get_intersection(sa.as_ref());
+        assert!(ab.is_ok() && ba.is_ok(), "{:?} {:?}", a, b);
+        assert_eq!(
+            ab.unwrap()._is_intersect,
+            ba.unwrap()._is_intersect,
+            "{:?} {:?}",
+            a,
+            b
+        );
+    }
+}
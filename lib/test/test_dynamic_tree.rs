@@ -0,0 +1,142 @@
+use bound::AxisAlignedBBox;
+use dynamic_tree::{DynamicTree, Handle};
+use i_bound::IBound;
+use i_shape::ShapeType;
+use sphere::Sphere;
+
+fn expect_pairs(tree: &DynamicTree, handles: &[Handle]) -> Vec<(Handle, Handle)> {
+    let mut expect = vec![];
+    for i in 0..handles.len() {
+        for j in i + 1..handles.len() {
+            let a = tree.get_fat_bound(handles[i]).unwrap();
+            let b = tree.get_fat_bound(handles[j]).unwrap();
+            if a.intersect(b) {
+                expect.push(if handles[i] < handles[j] {
+                    (handles[i], handles[j])
+                } else {
+                    (handles[j], handles[i])
+                });
+            }
+        }
+    }
+    expect.sort();
+    expect
+}
+
+#[test]
+fn test_dynamic_tree_insert_remove() {
+    let mut tree = DynamicTree::init(0.1f64);
+    let mut handles = vec![];
+    for i in 0..64 {
+        handles.push(tree.insert(Box::new(Sphere::init(&[3f64 * i as f64, 0f64, 0f64], 1f64))));
+    }
+
+    //sequential insertion along a line stays balanced
+    assert!(tree.get_height() <= 12);
+
+    let query = AxisAlignedBBox {
+        _bound_lower: [5f64, -1f64, -1f64],
+        _bound_upper: [10f64, 1f64, 1f64],
+    };
+    let ret = tree.query_bound(&query);
+    let mut expect = vec![handles[2], handles[3]];
+    expect.sort();
+    assert!(ret == expect);
+
+    let removed = tree.remove(handles[2]).expect("remove unsuccessful");
+    assert!(removed.get_shape_data() == vec![6f64, 0f64, 0f64, 1f64]);
+    assert!(tree.remove(handles[2]).is_none());
+    assert!(tree.get_shape(handles[2]).is_none());
+    assert!(tree.query_bound(&query) == vec![handles[3]]);
+
+    for h in handles.iter() {
+        tree.remove(*h);
+    }
+    assert!(tree._root.is_none());
+    assert!(tree.query_bound(&query).is_empty());
+
+    //removed nodes are reused
+    let count = tree._nodes.len();
+    tree.insert(Box::new(Sphere::init(&[0f64, 0f64, 0f64], 1f64)));
+    tree.insert(Box::new(Sphere::init(&[1f64, 0f64, 0f64], 1f64)));
+    assert!(tree._nodes.len() == count);
+}
+
+#[test]
+fn test_dynamic_tree_update() {
+    let mut tree = DynamicTree::init(0.5f64);
+    let h = tree.insert(Box::new(Sphere::init(&[0f64, 0f64, 0f64], 1f64)));
+    let other = tree.insert(Box::new(Sphere::init(&[10f64, 0f64, 0f64], 1f64)));
+    assert!(tree.query_pairs().is_empty());
+
+    //small movement stays within fattened bound
+    let b = AxisAlignedBBox::init(ShapeType::Sphere, &[0.3f64, 0f64, 0f64, 1f64]);
+    assert!(!tree.update(h, &b));
+
+    //large movement reinserts the leaf
+    let b = AxisAlignedBBox::init(ShapeType::Sphere, &[9f64, 0f64, 0f64, 1f64]);
+    assert!(tree.update(h, &b));
+    *tree.get_shape_mut(h).unwrap() = Box::new(Sphere::init(&[9f64, 0f64, 0f64], 1f64));
+    assert!(tree.get_shape(h).unwrap().get_shape_data()[0] == 9f64);
+
+    let pairs = tree.query_pairs();
+    assert!(pairs == vec![(h, other)]);
+
+    //pair is dropped once the shapes separate
+    let b = AxisAlignedBBox::init(ShapeType::Sphere, &[-9f64, 0f64, 0f64, 1f64]);
+    assert!(tree.update(h, &b));
+    assert!(tree.query_pairs().is_empty());
+}
+
+#[test]
+fn test_dynamic_tree_query_pairs() {
+    let mut tree = DynamicTree::init(0.1f64);
+    let mut handles = vec![];
+    for i in 0..8 {
+        for j in 0..8 {
+            handles.push(tree.insert(Box::new(Sphere::init(
+                &[1.5f64 * i as f64, 1.5f64 * j as f64, 0f64],
+                0.5f64 + 0.1f64 * ((i + j) % 3) as f64,
+            ))));
+        }
+    }
+    assert!(tree.query_pairs() == expect_pairs(&tree, &handles[..]));
+
+    //move a subset of shapes over several frames
+    for frame in 1..6 {
+        for (k, h) in handles.iter().enumerate() {
+            if k % 3 == 0 {
+                let data = tree.get_shape(*h).unwrap().get_shape_data();
+                let offset = 0.4f64 * frame as f64;
+                let b = AxisAlignedBBox::init(
+                    ShapeType::Sphere,
+                    &[data[0] + offset, data[1] - offset, data[2], data[3]],
+                );
+                tree.update(*h, &b);
+            }
+        }
+        let pairs = tree.query_pairs();
+        assert!(pairs == expect_pairs(&tree, &handles[..]));
+    }
+
+    //remove shapes and verify cached pairs are updated
+    for h in handles.iter().step_by(2) {
+        tree.remove(*h);
+    }
+    let remaining: Vec<Handle> = handles.iter().skip(1).step_by(2).cloned().collect();
+    assert!(tree.query_pairs() == expect_pairs(&tree, &remaining[..]));
+    assert!(tree.get_height() <= 10);
+
+    let all = AxisAlignedBBox {
+        _bound_lower: [-100f64; 3],
+        _bound_upper: [100f64; 3],
+    };
+    let mut remaining_sorted = remaining.clone();
+    remaining_sorted.sort();
+    assert!(tree.query_bound(&all) == remaining_sorted);
+    assert!(tree
+        .get_shape(remaining[0])
+        .unwrap()
+        .get_bound()
+        .intersect(&all));
+}
@@ -0,0 +1,79 @@
+use error::MazthError;
+use frustum::Frustum;
+use i_shape::{IShape, ShapeType};
+use intersect_gjk;
+
+use line::Line3;
+use plane::Plane;
+use rbox::RecBox;
+use sphere::Sphere;
+use triprism::TriPrism;
+
+#[test]
+fn test_error_unsupported_pair() {
+    {
+        let a = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64]);
+        let b = Plane::init(&[0f64, 1f64, 0f64], &[1f64, 0f64, 0f64]);
+        match a.get_intersection(&b) {
+            Err(MazthError::UnsupportedPair(ShapeType::Plane, ShapeType::Plane)) => (),
+            _ => panic!("unexpected result for plane plane intersection"),
+        }
+    }
+    {
+        let a = Frustum::init_perspective(
+            &[0f64, 0f64, -5f64],
+            &[0f64, 0f64, 1f64],
+            &[0f64, 1f64, 0f64],
+            1f64,
+            1f64,
+            0.1f64,
+            10f64,
+        );
+        let b = Sphere::init(&[0f64, 0f64, 0f64], 1f64);
+        assert_eq!(
+            a.get_intersection(&b).unwrap_err(),
+            MazthError::UnsupportedPair(ShapeType::Frustum, ShapeType::Sphere)
+        );
+        assert_eq!(
+            format!("{}", a.get_intersection(&b).unwrap_err()),
+            "unsupported shape pair: Frustum, Sphere"
+        );
+    }
+    {
+        //box sphere used to be unsupported, it is implemented by boxes with half extents
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = Sphere::init(&[1f64, 0f64, 0f64], 1f64);
+        assert!(a.get_intersection(&b).unwrap()._is_intersect);
+        assert!(b.get_intersection(&a).unwrap()._is_intersect);
+    }
+}
+
+#[test]
+fn test_error_degenerate_input() {
+    let a = TriPrism::init(
+        &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+        2f64,
+    );
+    //zero length line within bounds of the prism but outside of it
+    let b = Line3::init(&[3f64, 3f64, 1f64], &[3f64, 3f64, 1f64]);
+    let c = Line3::init(&[1f64, 1f64, 1.5f64], &[1f64, 1f64, 1.5f64]);
+    assert_eq!(
+        a.get_intersection(&b).unwrap_err(),
+        MazthError::DegenerateInput
+    );
+    assert!(a.get_intersection(&c).unwrap()._is_intersect);
+}
+
+#[test]
+fn test_error_no_support_function() {
+    let a = Sphere::init(&[0f64, 0f64, 0f64], 5f64);
+    let p = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64]);
+    assert_eq!(
+        intersect_gjk::query_intersect(&a, &p),
+        Err(MazthError::NoSupportFunction(ShapeType::Plane))
+    );
+    assert_eq!(
+        intersect_gjk::query_distance(&p, &a).unwrap_err(),
+        MazthError::NoSupportFunction(ShapeType::Plane)
+    );
+}
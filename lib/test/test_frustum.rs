@@ -0,0 +1,133 @@
+use i_comparable::IComparableError;
+use i_shape::{IShape, ShapeType};
+
+use bound::AxisAlignedBBox;
+use bound_sphere::BoundSphere;
+use dispatch::Dispatcher;
+use frustum::{Containment, Frustum};
+use mat::{Mat3x1, Mat4};
+use point::Point3;
+use sphere::Sphere;
+
+fn init_frustum() -> Frustum {
+    Frustum::init_perspective(
+        &[0f64, 0f64, 0f64],
+        &[0f64, 0f64, -1f64],
+        &[0f64, 1f64, 0f64],
+        std::f64::consts::FRAC_PI_2,
+        1f64,
+        1f64,
+        10f64,
+    )
+}
+
+#[test]
+fn test_frustum_init() {
+    let a = init_frustum();
+    let corners = a.get_corners();
+    assert!(corners[0]
+        .is_equal(
+            &Mat3x1 {
+                _val: [-1f64, -1f64, -1f64]
+            },
+            0.0001f64
+        )
+        .unwrap());
+    assert!(corners[6]
+        .is_equal(
+            &Mat3x1 {
+                _val: [10f64, 10f64, -10f64]
+            },
+            0.0001f64
+        )
+        .unwrap());
+
+    //projection matrix of the same frustum with the camera at the origin looking down the negative z axis
+    let m = Mat4::<f64>::init(
+        [
+            1f64,
+            0f64,
+            0f64,
+            0f64,
+            0f64,
+            1f64,
+            0f64,
+            0f64,
+            0f64,
+            0f64,
+            -11f64 / 9f64,
+            -20f64 / 9f64,
+            0f64,
+            0f64,
+            -1f64,
+            0f64,
+        ],
+        true,
+    );
+    let b = Frustum::init(&m);
+    for (x, y) in a.get_corners().iter().zip(b.get_corners().iter()) {
+        assert!(x.is_equal(y, 0.0001f64).unwrap());
+    }
+    for (x, y) in a._planes.iter().zip(b._planes.iter()) {
+        assert!(x._normal.is_equal(&y._normal, 0.0001f64).unwrap());
+    }
+
+    let lower = [-10f64, -10f64, -10f64];
+    let upper = [10f64, 10f64, -1f64];
+    for i in 0..3 {
+        assert!((a._bound._bound_lower[i] - lower[i]).abs() < 0.0001f64);
+        assert!((a._bound._bound_upper[i] - upper[i]).abs() < 0.0001f64);
+    }
+    let vals: Vec<f64> = corners.iter().flat_map(|x| x._val.to_vec()).collect();
+    let s = BoundSphere::init(ShapeType::Frustum, &vals);
+    for x in corners.iter() {
+        let d = Mat3x1 { _val: s._ori }
+            .minus(x)
+            .unwrap()
+            .magnitude()
+            .unwrap();
+        assert!(d <= s._radius + 0.0001f64);
+    }
+}
+
+#[test]
+fn test_frustum_cull() {
+    let a = init_frustum();
+    assert!(a.cull_point(&Point3::init(&[0f64, 0f64, -5f64])) == Containment::Inside);
+    assert!(a.cull_point(&Point3::init(&[0f64, 0f64, 5f64])) == Containment::Outside);
+    assert!(a.cull_point(&Point3::init(&[6f64, 0f64, -5f64])) == Containment::Outside);
+    assert!(a.cull_point(&Point3::init(&[0f64, 0f64, -1f64])) == Containment::Intersecting);
+
+    assert!(a.cull_sphere(&Sphere::init(&[0f64, 0f64, -5f64], 1f64)) == Containment::Inside);
+    assert!(a.cull_sphere(&Sphere::init(&[0f64, 0f64, -10f64], 1f64)) == Containment::Intersecting);
+    assert!(a.cull_sphere(&Sphere::init(&[20f64, 0f64, -5f64], 1f64)) == Containment::Outside);
+
+    let b = BoundSphere::init(ShapeType::Sphere, &[0f64, 0f64, -5f64, 1f64]);
+    assert!(a.cull_bound_sphere(&b) == Containment::Inside);
+    assert!(a.cull_bound(&b) == Containment::Inside);
+
+    let b = AxisAlignedBBox::init(ShapeType::Box, &[0f64, 0f64, -5f64, 1f64]);
+    assert!(a.cull_aabb(&b) == Containment::Inside);
+    let b = AxisAlignedBBox::init(ShapeType::Box, &[5f64, 0f64, -5f64, 1f64]);
+    assert!(a.cull_aabb(&b) == Containment::Intersecting);
+    let b = AxisAlignedBBox::init(ShapeType::Box, &[0f64, 0f64, 5f64, 1f64]);
+    assert!(a.cull_aabb(&b) == Containment::Outside);
+    assert!(a.cull_bound(&b) == Containment::Outside);
+    let b = AxisAlignedBBox::init(ShapeType::Plane, &[0f64; 6]);
+    assert!(a.cull_aabb(&b) == Containment::Intersecting);
+}
+
+#[test]
+fn test_frustum_intersect() {
+    let a = init_frustum();
+    assert!(a.get_intersect(&Point3::init(&[0f64, 0f64, -5f64])).0);
+    assert!(Point3::init(&[0f64, 0f64, -5f64]).get_intersect(&a).0);
+    assert!(!a.get_intersect(&Point3::init(&[6f64, 0f64, -5f64])).0);
+
+    //pairs without handler fall back to the support function of the frustum
+    let d = Dispatcher::init();
+    let b = Sphere::init(&[0f64, 0f64, -5f64], 1f64);
+    assert!(d.query_intersection(&a, &b).unwrap()._is_intersect);
+    let b = Sphere::init(&[0f64, 0f64, 5f64], 1f64);
+    assert!(!d.query_intersection(&a, &b).unwrap()._is_intersect);
+}
@@ -0,0 +1,154 @@
+use i_comparable::IComparableError;
+use i_shape::IShape;
+
+use bound::AxisAlignedBBox;
+use mat::Mat3x1;
+use plane::Plane;
+use point::Point3;
+use ray::Ray3;
+use rbox::RecBox;
+use sphere::Sphere;
+
+fn vec3(x: f64, y: f64, z: f64) -> Mat3x1<f64> {
+    Mat3x1 { _val: [x, y, z] }
+}
+
+fn init_box() -> RecBox {
+    RecBox::init_extents(&[0f64, 0f64, 0f64], &[2f64, 1f64, 0.5f64])
+}
+
+#[test]
+fn test_intersect_box_extents() {
+    let a = init_box();
+    assert!(a.get_shape_data() == vec![0f64, 0f64, 0f64, 2f64, 1f64, 0.5f64]);
+    let c = RecBox::init(&[1f64, 2f64, 3f64], 0.5f64);
+    assert!(c._half_extents._val == [0.5f64; 3]);
+    assert!(c.get_shape_data() == vec![1f64, 2f64, 3f64, 0.5f64, 0.5f64, 0.5f64]);
+    let b = AxisAlignedBBox::init_from_bound(a.get_bound());
+    assert!(b._bound_lower == [-2f64, -1f64, -0.5f64]);
+    assert!(b._bound_upper == [2f64, 1f64, 0.5f64]);
+    assert!(a
+        .get_support(&vec3(1f64, -1f64, 1f64))
+        .unwrap()
+        .is_equal(&vec3(2f64, -1f64, 0.5f64), 0.0001f64)
+        .unwrap());
+    assert!(a.get_intersect(&Point3::init(&[1.9f64, 0.9f64, 0.4f64])).0);
+    assert!(!a.get_intersect(&Point3::init(&[1.9f64, 0.9f64, 0.6f64])).0);
+}
+
+#[test]
+fn test_intersect_box_ray() {
+    let a = init_box();
+    {
+        let b = Ray3::init(&[-5f64, 0.5f64, 0f64], &[1f64, 0f64, 0f64]);
+        let (t_enter, t_exit) = a
+            .intersect_ray(&b._ori, &b._dir)
+            .expect("box ray return unexpected");
+        assert!((t_enter - 3f64).abs() < 0.0001f64);
+        assert!((t_exit - 7f64).abs() < 0.0001f64);
+        let ret = b.get_intersection(&a).unwrap();
+        assert!(ret._is_intersect);
+        assert!(ret._hits.len() == 2);
+        assert!(ret._hits[0]
+            ._point
+            .is_equal(&vec3(-2f64, 0.5f64, 0f64), 0.0001f64)
+            .unwrap());
+        //outward normals of the box at entry and exit
+        assert!(ret._hits[0]
+            ._normal
+            .unwrap()
+            .is_equal(&vec3(-1f64, 0f64, 0f64), 0.0001f64)
+            .unwrap());
+        assert!(ret._hits[1]
+            ._normal
+            .unwrap()
+            .is_equal(&vec3(1f64, 0f64, 0f64), 0.0001f64)
+            .unwrap());
+        assert!(ret._hits[1]._feature_other == Some(1));
+    }
+    {
+        //starting inside only reports the exit
+        let b = Ray3::init(&[0f64, 0f64, 0f64], &[0f64, 0f64, 1f64]);
+        let (t_enter, _) = a.intersect_ray(&b._ori, &b._dir).unwrap();
+        assert!(t_enter < 0f64);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._hits.len() == 1);
+        assert!((ret._hits[0]._t.unwrap() - 0.5f64).abs() < 0.0001f64);
+    }
+    {
+        let b = Ray3::init(&[-5f64, 1.5f64, 0f64], &[1f64, 0f64, 0f64]);
+        assert!(!b.get_intersect(&a).0);
+        let b = Ray3::init(&[-5f64, 0f64, 0f64], &[-1f64, 0f64, 0f64]);
+        assert!(!b.get_intersect(&a).0);
+    }
+}
+
+#[test]
+fn test_intersect_box_sphere() {
+    let a = init_box();
+    {
+        let b = Sphere::init(&[0f64, 0f64, 1f64], 1f64);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._depth.unwrap() - 0.5f64).abs() < 0.0001f64);
+        assert!(ret._hits[0]
+            ._point
+            .is_equal(&vec3(0f64, 0f64, 0.5f64), 0.0001f64)
+            .unwrap());
+        assert!(ret._hits[0]
+            ._normal
+            .unwrap()
+            .is_equal(&vec3(0f64, 0f64, -1f64), 0.0001f64)
+            .unwrap());
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        //center inside the box leaves through the nearest face
+        let b = Sphere::init(&[1.8f64, 0f64, 0f64], 0.5f64);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!((ret._depth.unwrap() - 0.7f64).abs() < 0.0001f64);
+        assert!(ret._hits[0]
+            ._point
+            .is_equal(&vec3(2f64, 0f64, 0f64), 0.0001f64)
+            .unwrap());
+        assert!(ret._hits[0]._feature == Some(1));
+    }
+    {
+        //near the corner but outside of it
+        let b = Sphere::init(&[2.6f64, 1.6f64, 0f64], 0.8f64);
+        assert!(!a.get_intersect(&b).0);
+    }
+}
+
+#[test]
+fn test_intersect_box_plane() {
+    let a = init_box();
+    {
+        let b = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 0f64, 1f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._depth.unwrap() - 0.5f64).abs() < 0.0001f64);
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        let b = Plane::init(&[0f64, -1.5f64, 0f64], &[0f64, 1f64, 0f64]);
+        assert!(!a.get_intersect(&b).0);
+        let b = Plane::init(&[0f64, -1.5f64, 0f64], &[0f64, -1f64, 0f64]);
+        assert!(a.get_intersect(&b).0);
+    }
+}
+
+#[test]
+fn test_intersect_box_box() {
+    let a = init_box();
+    {
+        let b = RecBox::init_extents(&[3f64, 0f64, 0f64], &[1.5f64, 0.5f64, 0.5f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._depth.unwrap() - 0.5f64).abs() < 0.0001f64);
+    }
+    {
+        let b = RecBox::init_extents(&[3f64, 0f64, 0f64], &[0.5f64, 5f64, 5f64]);
+        assert!(!a.get_intersect(&b).0);
+    }
+}
@@ -0,0 +1,126 @@
+use intersect_ccd::{self, Impact, Motion, TimeOfImpact};
+use intersect_gjk;
+use plane::Plane;
+use rbox::RecBox;
+use sphere::Sphere;
+use triprism::TriPrism;
+
+fn expect_impact(ret: Option<TimeOfImpact>) -> Impact {
+    match ret {
+        Some(TimeOfImpact::Impact(x)) => x,
+        _ => panic!("ccd return unexpected"),
+    }
+}
+
+#[test]
+fn test_intersect_ccd_sphere_plane() {
+    let ground = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64]);
+    let still = Motion::init_linear(&[0f64, 0f64, 0f64]);
+    {
+        let a = Sphere::init(&[0f64, 10f64, 0f64], 1f64);
+        let ma = Motion::init_linear(&[0f64, -100f64, 0f64]);
+        let ret = expect_impact(intersect_ccd::query_time_of_impact(
+            &a, &ma, &ground, &still, 1f64,
+        ));
+        assert!((ret._time - 0.09f64).abs() < 0.0001f64);
+        assert!((ret._normal._val[1] + 1f64).abs() < 0.0001f64);
+        assert!(ret._point._val[1].abs() < 0.0001f64);
+    }
+    {
+        let a = Sphere::init(&[0f64, 10f64, 0f64], 1f64);
+        let ma = Motion::init_linear(&[0f64, -5f64, 0f64]);
+        assert!(intersect_ccd::query_time_of_impact(&a, &ma, &ground, &still, 1f64).is_none());
+        assert!(intersect_ccd::query_time_of_impact(&ground, &still, &a, &ma, 2f64).is_some());
+    }
+    {
+        let a = Sphere::init(&[0f64, 10f64, 0f64], 1f64);
+        let ma = Motion::init_linear(&[0f64, 5f64, 0f64]);
+        assert!(intersect_ccd::query_time_of_impact(&a, &ma, &ground, &still, 100f64).is_none());
+    }
+}
+
+#[test]
+fn test_intersect_ccd_sphere_prism() {
+    let prism = TriPrism::init(
+        &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+        2f64,
+    );
+    let still = Motion::init_linear(&[0f64, 0f64, 0f64]);
+    {
+        let a = Sphere::init(&[-10f64, 1f64, 1f64], 0.5f64);
+        let ma = Motion::init_linear(&[100f64, 0f64, 0f64]);
+        //discrete test at the end of the interval misses the contact
+        let end = Sphere::init(&[90f64, 1f64, 1f64], 0.5f64);
+        assert!(!intersect_gjk::query_intersect(&prism, &end).unwrap());
+        let ret = expect_impact(intersect_ccd::query_time_of_impact(
+            &prism, &still, &a, &ma, 1f64,
+        ));
+        assert!((ret._time - 0.095f64).abs() < 0.0001f64);
+        assert!((ret._normal._val[0] + 1f64).abs() < 0.001f64);
+    }
+    {
+        let a = Sphere::init(&[-10f64, 1f64, 5f64], 0.5f64);
+        let ma = Motion::init_linear(&[100f64, 0f64, 0f64]);
+        assert!(intersect_ccd::query_time_of_impact(&prism, &still, &a, &ma, 1f64).is_none());
+    }
+    {
+        let a = Sphere::init(&[1f64, 1f64, 1f64], 0.5f64);
+        let ma = Motion::init_linear(&[100f64, 0f64, 0f64]);
+        let ret = expect_impact(intersect_ccd::query_time_of_impact(
+            &prism, &still, &a, &ma, 1f64,
+        ));
+        assert_eq!(ret._time, 0f64);
+    }
+}
+
+#[test]
+fn test_intersect_ccd_angular() {
+    let wall = Plane::init(&[1.2f64, 0f64, 0f64], &[-1f64, 0f64, 0f64]);
+    let still = Motion::init_linear(&[0f64, 0f64, 0f64]);
+    let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    let ma = Motion::init(
+        &[0f64, 0f64, 0f64],
+        &[0f64, 0f64, 1f64],
+        &[0f64, 0f64, 0f64],
+    );
+    //corner at distance sqrt(2) from the center first reaches the wall after rotating by 45 degrees - acos(1.2 / sqrt(2))
+    let expected = std::f64::consts::FRAC_PI_4 - (1.2f64 / 2f64.sqrt()).acos();
+    let ret = expect_impact(intersect_ccd::query_time_of_impact(
+        &a, &ma, &wall, &still, 1f64,
+    ));
+    assert!((ret._time - expected).abs() < 0.001f64);
+    assert!((ret._point._val[0] - 1.2f64).abs() < 0.001f64);
+
+    let b = RecBox::init(&[-0.5f64, 0f64, 0f64], 1f64);
+    let mb = Motion::init(
+        &[0f64, 0f64, 0f64],
+        &[0f64, 0f64, 1f64],
+        &[-0.5f64, 0f64, 0f64],
+    );
+    assert!(intersect_ccd::query_time_of_impact(&b, &mb, &wall, &still, 1f64).is_none());
+}
+
+#[test]
+fn test_intersect_ccd_not_converged() {
+    //fast spin inflates the speed bound of the sphere, so advancement closes the gap slowly
+    let wall = Plane::init(&[2f64, 0f64, 0f64], &[-1f64, 0f64, 0f64]);
+    let still = Motion::init_linear(&[0f64, 0f64, 0f64]);
+    let a = Sphere::init(&[0f64, 0f64, 0f64], 1f64);
+    let ma = Motion::init(
+        &[1f64, 0f64, 0f64],
+        &[0f64, 100f64, 0f64],
+        &[0f64, 0f64, 0f64],
+    );
+    match intersect_ccd::query_time_of_impact(&a, &ma, &wall, &still, 2f64) {
+        //conservative time stays before the actual impact at time 1
+        Some(TimeOfImpact::NotConverged(t)) => assert!(t > 0f64 && t < 1f64),
+        _ => panic!("ccd return unexpected"),
+    }
+
+    //without spin the same motion converges
+    let ma = Motion::init_linear(&[1f64, 0f64, 0f64]);
+    let ret = expect_impact(intersect_ccd::query_time_of_impact(
+        &a, &ma, &wall, &still, 2f64,
+    ));
+    assert!((ret._time - 1f64).abs() < 0.0001f64);
+}
@@ -0,0 +1,88 @@
+use i_comparable::IComparableError;
+
+use intersect_epa;
+use mat::Mat3x1;
+use rbox::RecBox;
+use sphere::Sphere;
+use triprism::TriPrism;
+
+#[test]
+fn test_intersect_epa_query_penetration_box() {
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = RecBox::init(&[1.5f64, 0.2f64, -0.3f64], 1f64);
+        let ret = intersect_epa::query_penetration(&a, &b).expect("epa return unexpected");
+        assert!((ret._depth - 0.5f64).abs() < 0.0001f64);
+        assert!(ret
+            ._normal
+            .is_equal(
+                &Mat3x1 {
+                    _val: [1f64, 0f64, 0f64]
+                },
+                0.0001f64
+            )
+            .unwrap());
+        assert!((ret._point_a._val[0] - 1f64).abs() < 0.0001f64);
+        assert!((ret._point_b._val[0] - 0.5f64).abs() < 0.0001f64);
+    }
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = RecBox::init(&[0.1f64, 0.2f64, -1.7f64], 1f64);
+        let ret = intersect_epa::query_penetration(&a, &b).expect("epa return unexpected");
+        assert!((ret._depth - 0.3f64).abs() < 0.0001f64);
+        assert!(ret
+            ._normal
+            .is_equal(
+                &Mat3x1 {
+                    _val: [0f64, 0f64, -1f64]
+                },
+                0.0001f64
+            )
+            .unwrap());
+    }
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = RecBox::init(&[2.5f64, 0f64, 0f64], 1f64);
+        assert!(intersect_epa::query_penetration(&a, &b).is_none());
+    }
+}
+
+#[test]
+fn test_intersect_epa_query_penetration_sphere() {
+    {
+        let a = Sphere::init(&[0f64, 0f64, 0f64], 2f64);
+        let b = Sphere::init(&[0f64, 3f64, 0f64], 1.5f64);
+        let ret = intersect_epa::query_penetration(&a, &b).expect("epa return unexpected");
+        assert!((ret._depth - 0.5f64).abs() < 0.01f64);
+        assert!(ret
+            ._normal
+            .is_equal(
+                &Mat3x1 {
+                    _val: [0f64, 1f64, 0f64]
+                },
+                0.01f64
+            )
+            .unwrap());
+        let d = ret._point_a.minus(&ret._point_b).unwrap();
+        assert!((d.magnitude().unwrap() - ret._depth).abs() < 0.0001f64);
+    }
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = Sphere::init(&[0.2f64, 1.8f64, 0f64], 1f64);
+        let ret = intersect_epa::query_penetration(&a, &b).expect("epa return unexpected");
+        assert!((ret._depth - 0.2f64).abs() < 0.01f64);
+        assert!(ret._normal._val[1] > 0.99f64);
+    }
+}
+
+#[test]
+fn test_intersect_epa_query_penetration_prism() {
+    let a = TriPrism::init(
+        &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+        2f64,
+    );
+    let b = Sphere::init(&[1f64, -0.5f64, 1f64], 1f64);
+    let ret = intersect_epa::query_penetration(&a, &b).expect("epa return unexpected");
+    assert!((ret._depth - 0.5f64).abs() < 0.01f64);
+    assert!(ret._normal._val[1] < -0.99f64);
+}
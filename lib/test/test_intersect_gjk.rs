@@ -2,8 +2,13 @@ use i_comparable::IComparableError;
 use i_shape::IShape;
 
 use intersect_gjk;
+use line::Line3;
 use mat::Mat3x1;
+use plane::Plane;
+use point::Point3;
+use rbox::RecBox;
 use sphere::Sphere;
+use triprism::TriPrism;
 
 #[test]
 fn test_intersect_gjk_shape_support() {
@@ -71,13 +76,12 @@ fn test_intersect_gjk_query_intersect_positive() {
         let ret = intersect_gjk::query_intersect(&a, &b);
         assert!(ret.expect("gjk return unexpected"));
     }
-    //todo
-    // {
-    //     let a = Point3::init( &[ -9.9, 9.9, 9.9 ] );
-    //     let b = RecBox::init( &[ 0.,0.,0. ], 10. );
-    //     let ret = intersect_gjk::query_intersect( &a, &b );
-    //     assert!( ret.expect("gjk return unexpected") );
-    // }
+    {
+        let a = Point3::init(&[-9.9, 9.9, 9.9]);
+        let b = RecBox::init(&[0., 0., 0.], 10.);
+        let ret = intersect_gjk::query_intersect(&a, &b);
+        assert!(ret.expect("gjk return unexpected"));
+    }
 }
 
 #[test]
@@ -95,3 +99,266 @@ fn test_intersect_gjk_query_intersect_negative() {
         assert!(!ret.expect("gjk return unexpected"));
     }
 }
+
+#[test]
+fn test_intersect_gjk_query_intersect_convex() {
+    //box box
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = RecBox::init(&[1.5f64, 0.5f64, 0.5f64], 1f64);
+        let ret = intersect_gjk::query_intersect(&a, &b);
+        assert!(ret.expect("gjk return unexpected"));
+    }
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = RecBox::init(&[2.1f64, 0f64, 0f64], 1f64);
+        let ret = intersect_gjk::query_intersect(&a, &b);
+        assert!(!ret.expect("gjk return unexpected"));
+    }
+    //box prism
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = TriPrism::init(
+            &[0.5f64, 0.5f64, 0f64, 3f64, 0.5f64, 0f64, 0.5f64, 3f64, 0f64],
+            1f64,
+        );
+        let ret = intersect_gjk::query_intersect(&a, &b);
+        assert!(ret.expect("gjk return unexpected"));
+    }
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = TriPrism::init(
+            &[-3f64, 0f64, 0f64, -1.5f64, 0f64, 0f64, -3f64, 1f64, 0f64],
+            1f64,
+        );
+        let ret = intersect_gjk::query_intersect(&a, &b);
+        assert!(!ret.expect("gjk return unexpected"));
+    }
+    //prism sphere
+    {
+        let a = TriPrism::init(
+            &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+            2f64,
+        );
+        let b = Sphere::init(&[1f64, 1f64, 3f64], 1.5f64);
+        let ret = intersect_gjk::query_intersect(&a, &b);
+        assert!(ret.expect("gjk return unexpected"));
+    }
+    {
+        let a = TriPrism::init(
+            &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+            2f64,
+        );
+        let b = Sphere::init(&[-3f64, 1f64, 1f64], 1.5f64);
+        let ret = intersect_gjk::query_intersect(&a, &b);
+        assert!(!ret.expect("gjk return unexpected"));
+    }
+    //point in convex
+    {
+        let a = Point3::init(&[-0.9f64, 0.9f64, 0.9f64]);
+        let b = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let ret = intersect_gjk::query_intersect(&a, &b);
+        assert!(ret.expect("gjk return unexpected"));
+    }
+    {
+        let a = Point3::init(&[-1.1f64, 0.9f64, 0.9f64]);
+        let b = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let ret = intersect_gjk::query_intersect(&a, &b);
+        assert!(!ret.expect("gjk return unexpected"));
+    }
+    {
+        let a = Point3::init(&[1f64, 1f64, 1f64]);
+        let b = TriPrism::init(
+            &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+            2f64,
+        );
+        let ret = intersect_gjk::query_intersect(&b, &a);
+        assert!(ret.expect("gjk return unexpected"));
+    }
+}
+
+#[test]
+fn test_intersect_gjk_query_intersect_unsupported() {
+    let a = Sphere::init(&[0f64, 0f64, 0f64], 5f64);
+    let p = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64]);
+    let l = Line3::init(&[0f64, 0f64, 0f64], &[1f64, 1f64, 0f64]);
+    assert!(intersect_gjk::query_intersect(&a, &p).is_err());
+    assert!(intersect_gjk::query_intersect(&p, &a).is_err());
+    assert!(intersect_gjk::query_intersect(&l, &a).is_err());
+}
+
+#[test]
+fn test_intersect_gjk_query_intersect_3d() {
+    //separated along directions a planar simplex cannot resolve
+    {
+        let a = TriPrism::init(
+            &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+            2f64,
+        );
+        let b = Sphere::init(&[-2f64, 1f64, 1f64], 1.5f64);
+        let ret = intersect_gjk::query_intersect(&a, &b);
+        assert!(!ret.expect("gjk return unexpected"));
+    }
+    {
+        let a = TriPrism::init(
+            &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+            2f64,
+        );
+        for c in [[1f64, 1f64, 5f64], [4f64, 4f64, 1f64]].iter() {
+            let b = Sphere::init(c, 1.5f64);
+            let ret = intersect_gjk::query_intersect(&a, &b);
+            assert!(!ret.expect("gjk return unexpected"));
+        }
+    }
+    {
+        let b = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        for p in [
+            [0f64, -3f64, 0f64],
+            [0f64, 0f64, -3f64],
+            [1.1f64, 1.1f64, 1.1f64],
+        ]
+        .iter()
+        {
+            let a = Point3::init(p);
+            let ret = intersect_gjk::query_intersect(&a, &b);
+            assert!(!ret.expect("gjk return unexpected"));
+        }
+    }
+    //overlap requiring a tetrahedron enclosing the origin
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = RecBox::init(&[0.7f64, -0.6f64, 0.8f64], 1f64);
+        let ret = intersect_gjk::query_intersect(&a, &b);
+        assert!(ret.expect("gjk return unexpected"));
+    }
+    {
+        let b = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        for p in [
+            [0f64, -0.3f64, 0f64],
+            [0.2f64, 0.1f64, -0.9f64],
+            [0f64, 0f64, 0f64],
+        ]
+        .iter()
+        {
+            let a = Point3::init(p);
+            let ret = intersect_gjk::query_intersect(&a, &b);
+            assert!(ret.expect("gjk return unexpected"));
+        }
+    }
+    //touching
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = RecBox::init(&[2f64, 0.5f64, -0.5f64], 1f64);
+        let ret = intersect_gjk::query_intersect(&a, &b);
+        assert!(ret.expect("gjk return unexpected"));
+    }
+}
+
+#[test]
+fn test_intersect_gjk_query_simplex() {
+    let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    let b = Sphere::init(&[4f64, 0f64, 0f64], 1f64);
+    let s = intersect_gjk::query_simplex(
+        |v| {
+            let pa = a.get_support(v)?;
+            let pb = b.get_support(&v.scale(-1f64).unwrap())?;
+            Some(intersect_gjk::SupportPoint {
+                _v: pa.minus(&pb).unwrap(),
+                _a: pa,
+                _b: pb,
+            })
+        },
+        false,
+    )
+    .expect("gjk return unexpected");
+    assert!(!s._is_intersect);
+    assert!((s._closest.magnitude().unwrap() - 2f64).abs() < 0.0001f64);
+    assert_eq!(s._verts.len(), s._weights.len());
+}
+
+#[test]
+fn test_intersect_gjk_query_distance() {
+    //box box
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = RecBox::init(&[4f64, 0.5f64, 0f64], 1f64);
+        let ret = intersect_gjk::query_distance(&a, &b)
+            .unwrap()
+            .expect("gjk return unexpected");
+        assert!((ret._distance - 2f64).abs() < 0.0001f64);
+        assert!((ret._point_a._val[0] - 1f64).abs() < 0.0001f64);
+        assert!((ret._point_b._val[0] - 3f64).abs() < 0.0001f64);
+        assert!(ret
+            ._axis
+            .is_equal(
+                &Mat3x1 {
+                    _val: [1f64, 0f64, 0f64]
+                },
+                0.0001f64
+            )
+            .unwrap());
+    }
+    //sphere sphere
+    {
+        let a = Sphere::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = Sphere::init(&[3f64, 4f64, 0f64], 1f64);
+        let ret = intersect_gjk::query_distance(&a, &b)
+            .unwrap()
+            .expect("gjk return unexpected");
+        assert!((ret._distance - 3f64).abs() < 0.001f64);
+        assert!(ret
+            ._point_a
+            .is_equal(
+                &Mat3x1 {
+                    _val: [0.6f64, 0.8f64, 0f64]
+                },
+                0.001f64
+            )
+            .unwrap());
+        assert!(ret
+            ._point_b
+            .is_equal(
+                &Mat3x1 {
+                    _val: [2.4f64, 3.2f64, 0f64]
+                },
+                0.001f64
+            )
+            .unwrap());
+    }
+    //box corner sphere
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = Sphere::init(&[3f64, 3f64, 3f64], 1f64);
+        let ret = intersect_gjk::query_distance(&a, &b)
+            .unwrap()
+            .expect("gjk return unexpected");
+        assert!((ret._distance - (2f64 * 3f64.sqrt() - 1f64)).abs() < 0.001f64);
+        assert!(ret
+            ._point_a
+            .is_equal(
+                &Mat3x1 {
+                    _val: [1f64, 1f64, 1f64]
+                },
+                0.001f64
+            )
+            .unwrap());
+    }
+    //point prism
+    {
+        let a = Point3::init(&[3f64, 3f64, 1f64]);
+        let b = TriPrism::init(
+            &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+            2f64,
+        );
+        let ret = intersect_gjk::query_distance(&a, &b)
+            .unwrap()
+            .expect("gjk return unexpected");
+        assert!((ret._distance - 2f64.sqrt()).abs() < 0.0001f64);
+    }
+    //intersecting
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = Sphere::init(&[1.5f64, 0f64, 0f64], 1f64);
+        assert!(intersect_gjk::query_distance(&a, &b).unwrap().is_none());
+    }
+}
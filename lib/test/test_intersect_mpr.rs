@@ -0,0 +1,122 @@
+use i_comparable::IComparableError;
+use i_shape::IShape;
+
+use intersect_gjk;
+use intersect_mpr;
+use mat::Mat3x1;
+use plane::Plane;
+use point::Point3;
+use rbox::RecBox;
+use sphere::Sphere;
+use triangle::Triangle3;
+use triprism::TriPrism;
+
+#[test]
+fn test_intersect_mpr_query_intersect() {
+    {
+        let a = Sphere::init(&[0f64, 0f64, 0f64], 5f64);
+        let b = Sphere::init(&[7f64, 0f64, 0f64], 2.1f64);
+        assert!(intersect_mpr::query_intersect(&a, &b).expect("mpr return unexpected"));
+    }
+    {
+        let a = Sphere::init(&[0f64, 0f64, 0f64], 5f64);
+        let b = Sphere::init(&[7f64, 0f64, 0f64], 1.9f64);
+        assert!(!intersect_mpr::query_intersect(&a, &b).expect("mpr return unexpected"));
+    }
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = RecBox::init(&[0f64, 0f64, 0f64], 0.5f64);
+        assert!(intersect_mpr::query_intersect(&a, &b).expect("mpr return unexpected"));
+    }
+    {
+        let a = Sphere::init(&[0f64, 0f64, 0f64], 5f64);
+        let p = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64]);
+        assert!(intersect_mpr::query_intersect(&a, &p).is_none());
+    }
+}
+
+#[test]
+fn test_intersect_mpr_query_intersect_agrees_with_gjk() {
+    let prism = TriPrism::init(
+        &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+        2f64,
+    );
+    let cube = RecBox::init(&[1f64, 1f64, 1f64], 1f64);
+    for i in 0..8 {
+        for j in 0..8 {
+            for k in 0..8 {
+                let c = [
+                    -2f64 + 1.1f64 * i as f64,
+                    -2f64 + 1.1f64 * j as f64,
+                    -2f64 + 1.1f64 * k as f64,
+                ];
+                let shapes: [Box<dyn IShape>; 3] = [
+                    Box::new(Sphere::init(&c, 0.8f64)),
+                    Box::new(RecBox::init(&c, 0.6f64)),
+                    Box::new(Point3::init(&c)),
+                ];
+                for s in shapes.iter() {
+                    for other in [&prism as &dyn IShape, &cube as &dyn IShape].iter() {
+                        let gjk = intersect_gjk::query_intersect(*other, s.as_ref()).ok();
+                        let mpr = intersect_mpr::query_intersect(*other, s.as_ref());
+                        assert_eq!(gjk, mpr);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_intersect_mpr_query_intersect_flat() {
+    //centre of the box of the triangle lies on its hypotenuse
+    let a = Triangle3::init(&[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64]);
+    for i in 0..10 {
+        for j in 0..10 {
+            for k in 0..3 {
+                let c = [
+                    -1f64 + 0.6f64 * i as f64,
+                    -1f64 + 0.6f64 * j as f64,
+                    -0.6f64 + 0.6f64 * k as f64,
+                ];
+                let b = Sphere::init(&c, 0.5f64);
+                let expect = intersect_gjk::query_intersect(&a, &b).expect("gjk return unexpected");
+                let ret = intersect_mpr::query_intersect(&a, &b).expect("mpr return unexpected");
+                assert!(ret == expect);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_intersect_mpr_query_penetration() {
+    {
+        let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let b = RecBox::init(&[1.5f64, 0f64, 0f64], 1f64);
+        let ret = intersect_mpr::query_penetration(&a, &b).expect("mpr return unexpected");
+        assert!((ret._depth - 0.5f64).abs() < 0.0001f64);
+        assert!(ret
+            ._normal
+            .is_equal(
+                &Mat3x1 {
+                    _val: [1f64, 0f64, 0f64]
+                },
+                0.0001f64
+            )
+            .unwrap());
+    }
+    {
+        let a = Sphere::init(&[0f64, 0f64, 0f64], 2f64);
+        let b = Sphere::init(&[0f64, 0f64, -3f64], 1.5f64);
+        let ret = intersect_mpr::query_penetration(&a, &b).expect("mpr return unexpected");
+        assert!((ret._depth - 0.5f64).abs() < 0.01f64);
+        assert!(ret._normal._val[2] < -0.99f64);
+        assert!((ret._point_a._val[2] + 2f64).abs() < 0.01f64);
+        assert!((ret._point_b._val[2] + 1.5f64).abs() < 0.01f64);
+    }
+    {
+        let a = Sphere::init(&[0f64, 0f64, 0f64], 2f64);
+        let b = Sphere::init(&[0f64, 0f64, -4f64], 1.5f64);
+        assert!(intersect_mpr::query_penetration(&a, &b).is_none());
+    }
+}
@@ -0,0 +1,166 @@
+use i_comparable::IComparableError;
+use i_shape::IShape;
+
+use intersect_gjk;
+use mat::Mat3x1;
+use obox::OrientedBox;
+use plane::Plane;
+use point::Point3;
+use quat::Quat;
+use ray::Ray3;
+use rbox::RecBox;
+use sphere::Sphere;
+
+fn vec3(x: f64, y: f64, z: f64) -> Mat3x1<f64> {
+    Mat3x1 { _val: [x, y, z] }
+}
+
+fn rotation(degree: f64) -> Quat<f64> {
+    Quat::<f64>::init_from_axis_angle_degree((vec3(0f64, 0f64, 1f64), degree))
+}
+
+///long thin box along the diagonal of the xy plane
+fn init_box() -> OrientedBox {
+    OrientedBox::init(
+        &[0f64, 0f64, 0f64],
+        &[4f64, 0.5f64, 0.5f64],
+        &rotation(45f64),
+    )
+}
+
+#[test]
+fn test_intersect_obox_point() {
+    let a = init_box();
+    assert!(a.get_intersect(&Point3::init(&[2f64, 2f64, 0f64])).0);
+    assert!(Point3::init(&[2f64, 2f64, 0f64]).get_intersect(&a).0);
+    //inside the axis aligned box but outside of the oriented box
+    assert!(!a.get_intersect(&Point3::init(&[2f64, -2f64, 0f64])).0);
+}
+
+#[test]
+fn test_intersect_obox_ray() {
+    let a = init_box();
+    let n = 0.5f64.sqrt();
+    {
+        //across the box perpendicular to its long axis
+        let b = Ray3::init(&[5f64, -5f64, 0f64], &[-n, n, 0f64]);
+        let (t_enter, t_exit) = a
+            .intersect_ray(&b._ori, &b._dir)
+            .expect("oriented box ray return unexpected");
+        let d = 50f64.sqrt();
+        assert!((t_enter - (d - 0.5f64)).abs() < 0.0001f64);
+        assert!((t_exit - (d + 0.5f64)).abs() < 0.0001f64);
+        let ret = b.get_intersection(&a).unwrap();
+        assert!(ret._hits.len() == 2);
+        //outward normal of the box at entry
+        assert!(ret._hits[0]
+            ._normal
+            .unwrap()
+            .is_equal(&vec3(n, -n, 0f64), 0.0001f64)
+            .unwrap());
+    }
+    {
+        let b = Ray3::init(&[5f64, -5f64, 0f64], &[-1f64, 0f64, 0f64]);
+        assert!(!b.get_intersect(&a).0);
+        let b = Ray3::init(&[0f64, 0f64, 0f64], &[n, n, 0f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._hits.len() == 1);
+        assert!((ret._hits[0]._t.unwrap() - 4f64).abs() < 0.0001f64);
+    }
+}
+
+#[test]
+fn test_intersect_obox_sphere() {
+    let a = init_box();
+    let n = 0.5f64.sqrt();
+    {
+        let b = Sphere::init(&[1f64, -1f64, 0f64], 1f64);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        let depth = 1f64 - (2f64.sqrt() - 0.5f64);
+        assert!((ret._depth.unwrap() - depth).abs() < 0.0001f64);
+        assert!(ret._hits[0]
+            ._point
+            .is_equal(&vec3(0.5f64 * n, -0.5f64 * n, 0f64), 0.0001f64)
+            .unwrap());
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        let b = Sphere::init(&[2f64, -2f64, 0f64], 1f64);
+        assert!(!a.get_intersect(&b).0);
+    }
+    {
+        //center inside the box
+        let b = Sphere::init(&[0f64, 0f64, 0.3f64], 0.5f64);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!((ret._depth.unwrap() - 0.7f64).abs() < 0.0001f64);
+        assert!(ret._hits[0]
+            ._normal
+            .unwrap()
+            .is_equal(&vec3(0f64, 0f64, -1f64), 0.0001f64)
+            .unwrap());
+    }
+}
+
+#[test]
+fn test_intersect_obox_plane() {
+    let a = init_box();
+    let n = 0.5f64.sqrt();
+    {
+        //lowest corner lies 4.5 * sqrt(0.5) below the center
+        let b = Plane::init(&[0f64, -2f64, 0f64], &[0f64, 1f64, 0f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._depth.unwrap() - (4.5f64 * n - 2f64)).abs() < 0.0001f64);
+        assert!((ret._hits[0]._point[1] + 2f64).abs() < 0.0001f64);
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        let b = Plane::init(&[0f64, -4f64, 0f64], &[0f64, 1f64, 0f64]);
+        assert!(!a.get_intersect(&b).0);
+        assert!(!b.get_intersect(&a).0);
+    }
+}
+
+#[test]
+fn test_intersect_obox_sat() {
+    let a = init_box();
+    {
+        let b = OrientedBox::init(
+            &[0f64, 0f64, 0f64],
+            &[4f64, 0.5f64, 0.5f64],
+            &rotation(-45f64),
+        );
+        assert!(a.get_intersect(&b).0);
+        let c = OrientedBox::init(
+            &[3f64, -3f64, 0f64],
+            &[1f64, 0.5f64, 0.5f64],
+            &rotation(-45f64),
+        );
+        assert!(!a.get_intersect(&c).0);
+    }
+    {
+        //resting across the top face of a cube
+        let b = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+        let c = OrientedBox::init(
+            &[0f64, 0f64, 1.4f64],
+            &[2f64, 0.5f64, 0.5f64],
+            &rotation(30f64),
+        );
+        let ret = c.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._depth.unwrap() - 0.1f64).abs() < 0.0001f64);
+        assert!(b.get_intersect(&c).0);
+        let d = RecBox::init(&[3f64, -3f64, 0f64], 1f64);
+        assert!(!a.get_intersect(&d).0);
+    }
+}
+
+#[test]
+fn test_intersect_obox_gjk() {
+    let a = init_box();
+    let b = Sphere::init(&[2f64, 2f64, 0.8f64], 0.5f64);
+    assert!(intersect_gjk::query_intersect(&a, &b).unwrap());
+    let b = Sphere::init(&[2f64, -2f64, 0f64], 0.5f64);
+    assert!(!intersect_gjk::query_intersect(&a, &b).unwrap());
+}
@@ -87,3 +87,57 @@ fn test_intersect_ray_sphere() {
         }
     }
 }
+
+#[test]
+fn test_intersect_ray_sphere_intersection() {
+    //entry and exit points
+    {
+        let a = Ray3::init(&[5f64, 0f64, 0f64], &[1f64, 0f64, 0f64]);
+        let b = Sphere::init(&[20f64, 0f64, 0f64], 5f64);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert_eq!(ret._hits.len(), 2);
+        assert!((ret._hits[0]._t.unwrap() - 10f64).abs() < 0.0001f64);
+        assert!((ret._hits[1]._t.unwrap() - 20f64).abs() < 0.0001f64);
+        assert!(ret._hits[0]
+            ._normal
+            .unwrap()
+            .is_equal(
+                &Mat3x1 {
+                    _val: [-1f64, 0f64, 0f64]
+                },
+                0.0001f64
+            )
+            .unwrap());
+        assert!(ret._hits[1]
+            ._point
+            .is_equal(
+                &Mat3x1 {
+                    _val: [25f64, 0f64, 0f64]
+                },
+                0.0001f64
+            )
+            .unwrap());
+        //same locations seen from the sphere
+        let ret = b.get_intersection(&a).unwrap();
+        assert_eq!(ret._hits.len(), 2);
+        assert!(ret._hits[0]
+            ._normal
+            .unwrap()
+            .is_equal(
+                &Mat3x1 {
+                    _val: [1f64, 0f64, 0f64]
+                },
+                0.0001f64
+            )
+            .unwrap());
+    }
+    //ray starting inside only exits
+    {
+        let a = Ray3::init(&[20f64, 0f64, 0f64], &[1f64, 0f64, 0f64]);
+        let b = Sphere::init(&[20f64, 0f64, 0f64], 5f64);
+        let ret = a.get_intersection(&b).unwrap();
+        assert_eq!(ret._hits.len(), 1);
+        assert!((ret._hits[0]._t.unwrap() - 5f64).abs() < 0.0001f64);
+    }
+}
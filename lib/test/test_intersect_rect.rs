@@ -0,0 +1,182 @@
+use bound::AxisAlignedBBox;
+use bound_sphere::BoundSphere;
+use i_bound::IBound;
+use i_comparable::IComparableError;
+use i_shape::{IShape, ShapeType};
+
+use intersect_gjk;
+use line::Line3;
+use mat::Mat3x1;
+use plane::Plane;
+use point::Point3;
+use ray::Ray3;
+use rbox::RecBox;
+use rect::Rect3;
+use sphere::Sphere;
+
+fn vec3(x: f64, y: f64, z: f64) -> Mat3x1<f64> {
+    Mat3x1 { _val: [x, y, z] }
+}
+
+fn init_rect() -> Rect3 {
+    Rect3::init(
+        &[0f64, 0f64, 0f64],
+        &[2f64, 0f64, 0f64],
+        &[0f64, 1f64, 0f64],
+    )
+}
+
+#[test]
+fn test_intersect_rect_point() {
+    let a = init_rect();
+    assert!(a.get_intersect(&Point3::init(&[1.5f64, 0.5f64, 0f64])).0);
+    assert!(a.get_intersect(&Point3::init(&[-2f64, 1f64, 0f64])).0);
+    assert!(!a.get_intersect(&Point3::init(&[1.5f64, 1.5f64, 0f64])).0);
+    assert!(!a.get_intersect(&Point3::init(&[1f64, 0.5f64, 0.5f64])).0);
+    assert!(Point3::init(&[1.5f64, 0.5f64, 0f64]).get_intersect(&a).0);
+}
+
+#[test]
+fn test_intersect_rect_ray() {
+    let a = init_rect();
+    {
+        let b = Ray3::init(&[1f64, -0.5f64, 5f64], &[0f64, 0f64, -1f64]);
+        let ret = b.get_intersection(&a).unwrap();
+        assert!(ret._is_intersect);
+        let hit = &ret._hits[0];
+        assert!((hit._t.unwrap() - 5f64).abs() < 0.0001f64);
+        assert!(hit
+            ._point
+            .is_equal(&vec3(1f64, -0.5f64, 0f64), 0.0001f64)
+            .unwrap());
+        //normal of the rectangle facing the ray
+        assert!(hit
+            ._normal
+            .unwrap()
+            .is_equal(&vec3(0f64, 0f64, 1f64), 0.0001f64)
+            .unwrap());
+        let (t, s, r) = a
+            .intersect_ray(&b._ori, &b._dir)
+            .expect("rect ray return unexpected");
+        assert!((t - 5f64).abs() < 0.0001f64);
+        assert!((s - 0.5f64).abs() < 0.0001f64);
+        assert!((r + 0.5f64).abs() < 0.0001f64);
+    }
+    {
+        let b = Ray3::init(&[2.5f64, 0f64, 5f64], &[0f64, 0f64, -1f64]);
+        assert!(!b.get_intersect(&a).0);
+        let b = Ray3::init(&[1f64, 0f64, 5f64], &[0f64, 0f64, 1f64]);
+        assert!(!a.get_intersect(&b).0);
+    }
+    {
+        //tilted rectangle facing the x axis
+        let a = Rect3::init(
+            &[3f64, 0f64, 0f64],
+            &[0f64, 1f64, 1f64],
+            &[0f64, -1f64, 1f64],
+        );
+        let b = Ray3::init(&[0f64, 0.5f64, 0.5f64], &[1f64, 0f64, 0f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._hits[0]._t.unwrap() - 3f64).abs() < 0.0001f64);
+        let b = Ray3::init(&[0f64, 1.5f64, 0.6f64], &[1f64, 0f64, 0f64]);
+        assert!(!a.get_intersect(&b).0);
+    }
+}
+
+#[test]
+fn test_intersect_rect_line() {
+    let a = init_rect();
+    {
+        let b = Line3::init(&[1f64, 0.5f64, -1f64], &[1f64, 0.5f64, 3f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._hits[0]._t.unwrap() - 0.25f64).abs() < 0.0001f64);
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        //segment ends before reaching the rectangle
+        let b = Line3::init(&[1f64, 0.5f64, 3f64], &[1f64, 0.5f64, 1f64]);
+        assert!(!a.get_intersect(&b).0);
+    }
+}
+
+#[test]
+fn test_intersect_rect_sphere() {
+    let a = init_rect();
+    {
+        let b = Sphere::init(&[1f64, 0.5f64, 0.5f64], 1f64);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._depth.unwrap() - 0.5f64).abs() < 0.0001f64);
+        assert!(ret._hits[0]
+            ._point
+            .is_equal(&vec3(1f64, 0.5f64, 0f64), 0.0001f64)
+            .unwrap());
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        //closest to the corner
+        let b = Sphere::init(&[2.5f64, 1.5f64, 0f64], 1f64);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._hits[0]
+            ._point
+            .is_equal(&vec3(2f64, 1f64, 0f64), 0.0001f64)
+            .unwrap());
+        let b = Sphere::init(&[3f64, 2f64, 0f64], 1f64);
+        assert!(!a.get_intersect(&b).0);
+    }
+}
+
+#[test]
+fn test_intersect_rect_plane() {
+    let a = Rect3::init(
+        &[0f64, 0f64, 1f64],
+        &[1f64, 0f64, 0.5f64],
+        &[0f64, 1f64, 0f64],
+    );
+    {
+        let b = Plane::init(&[0f64, 0f64, 1f64], &[0f64, 0f64, 1f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._depth.unwrap() - 0.5f64).abs() < 0.0001f64);
+        assert!(ret._hits[0]._point[0] < 0f64);
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        let b = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 0f64, 1f64]);
+        assert!(!a.get_intersect(&b).0);
+    }
+}
+
+#[test]
+fn test_intersect_rect_gjk() {
+    let a = init_rect();
+    let b = RecBox::init(&[1.5f64, 0.5f64, 0.5f64], 1f64);
+    assert!(intersect_gjk::query_intersect(&a, &b).unwrap());
+    let b = RecBox::init(&[4f64, 0f64, 0f64], 1f64);
+    assert!(!intersect_gjk::query_intersect(&a, &b).unwrap());
+}
+
+#[test]
+fn test_intersect_rect_bound() {
+    //tilted about the x axis, corners at y, z = +-0.6, +-0.8
+    let a = Rect3::init(
+        &[1f64, 2f64, 3f64],
+        &[2f64, 0f64, 0f64],
+        &[0f64, 0.6f64, 0.8f64],
+    );
+    let d = a.get_shape_data();
+    assert_eq!(d.len(), 9);
+    let b = AxisAlignedBBox::init(ShapeType::Rect, &d);
+    assert_eq!(b._bound_lower, [-1f64, 1.4f64, 2.2f64]);
+    assert_eq!(b._bound_upper, [3f64, 2.6f64, 3.8f64]);
+    assert_eq!(a._bound._bound_lower, b._bound_lower);
+    assert_eq!(a._bound._bound_upper, b._bound_upper);
+    let s = BoundSphere::init(ShapeType::Rect, &d);
+    let c = s.get_centroid();
+    assert!((c[0] - 1f64).abs() < 0.0001f64);
+    assert!((c[1] - 2f64).abs() < 0.0001f64);
+    assert!((c[2] - 3f64).abs() < 0.0001f64);
+    assert!((s._radius - 5f64.sqrt()).abs() < 0.0001f64);
+}
@@ -0,0 +1,231 @@
+use error::MazthError;
+use i_shape::IShape;
+
+use intersect_gjk;
+use intersect_sat::{self, Feature, Polyhedron};
+use point::Point3;
+use rbox::RecBox;
+use triangle::Triangle3;
+use triprism::TriPrism;
+
+///prism with a ridge along (1,-1,0) pointing towards the vertical edge of the unit box at x = y = 1
+fn ridge_prism(ridge: f64) -> TriPrism {
+    let h = 0.5f64.sqrt();
+    TriPrism::init(
+        &[
+            ridge - h,
+            ridge + h,
+            0f64,
+            3f64 - h,
+            3f64 + h,
+            -1f64,
+            2f64 - h,
+            2f64 + h,
+            1.5f64,
+        ],
+        2f64,
+    )
+}
+
+#[test]
+fn test_intersect_sat_polyhedron() {
+    let b = Polyhedron::init_from_shape(&RecBox::init(&[1f64, 2f64, 3f64], 0.5f64)).unwrap();
+    assert_eq!(b._verts.len(), 8);
+    assert_eq!(b._faces.len(), 6);
+    assert_eq!(b._edges.len(), 12);
+    let p = Polyhedron::init_from_shape(&ridge_prism(1f64)).unwrap();
+    assert_eq!(p._verts.len(), 6);
+    assert_eq!(p._faces.len(), 5);
+    assert_eq!(p._edges.len(), 9);
+    //face loops are counter clockwise around outward normals
+    for poly in [b, p].iter() {
+        for (f, n) in poly._faces.iter().zip(poly._normals.iter()) {
+            let e0 = poly._verts[f[1]].minus(&poly._verts[f[0]]).unwrap();
+            let e1 = poly._verts[f[2]].minus(&poly._verts[f[1]]).unwrap();
+            assert!(e0.cross(&e1).unwrap().dot(n).unwrap() > 0f64);
+            for v in poly._verts.iter() {
+                assert!(v.minus(&poly._verts[f[0]]).unwrap().dot(n).unwrap() < 0.000001f64);
+            }
+        }
+    }
+    assert!(Polyhedron::init_from_shape(&Point3::init(&[0f64, 0f64, 0f64])).is_none());
+}
+
+#[test]
+fn test_intersect_sat_box_box() {
+    let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    {
+        let b = RecBox::init(&[1.5f64, 0.2f64, -0.3f64], 1f64);
+        let ret = intersect_sat::query_intersect(&a, &b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._depth - 0.5f64).abs() < 0.000001f64);
+        assert!((ret._normal._val[0] - 1f64).abs() < 0.000001f64);
+        assert_eq!(ret._feature, Feature::FaceA(1));
+        let p = ret._point.unwrap();
+        assert!(p._val[0] >= 0.5f64 && p._val[0] <= 1f64);
+    }
+    {
+        let b = RecBox::init(&[0.1f64, -2.3f64, 0f64], 1f64);
+        let ret = intersect_sat::query_intersect(&a, &b).unwrap();
+        assert!(!ret._is_intersect);
+        assert!((ret._depth + 0.3f64).abs() < 0.000001f64);
+        assert!((ret._normal._val[1] + 1f64).abs() < 0.000001f64);
+        assert!(ret._point.is_none());
+    }
+    {
+        let b = RecBox::init(&[2f64, 0f64, 0f64], 1f64);
+        let ret = intersect_sat::query_intersect(&a, &b).unwrap();
+        assert!(ret._is_intersect);
+        assert!(ret._depth.abs() < 0.000001f64);
+    }
+}
+
+#[test]
+fn test_intersect_sat_flat_box() {
+    //zero thickness box keeps its two opposite facing faces only
+    let a = RecBox::init_extents(&[0f64, 0f64, 0f64], &[1f64, 1f64, 0f64]);
+    let p = Polyhedron::init_from_shape(&a).unwrap();
+    assert_eq!(p._faces.len(), 2);
+    assert!(p
+        ._normals
+        .iter()
+        .all(|x| x._val.iter().all(|y| y.is_finite())));
+    assert!((p._normals[0].dot(&p._normals[1]).unwrap() + 1f64).abs() < 0.000001f64);
+    {
+        let b = RecBox::init(&[0.5f64, 0.5f64, 0.2f64], 0.5f64);
+        let ret = intersect_sat::query_intersect(&a, &b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._depth - 0.3f64).abs() < 0.000001f64);
+        assert!((ret._normal._val[2] - 1f64).abs() < 0.000001f64);
+        let ret = intersect_sat::query_intersect(&b, &a).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._normal._val[2] + 1f64).abs() < 0.000001f64);
+    }
+    {
+        let b = RecBox::init(&[0.5f64, 0.5f64, -0.7f64], 0.5f64);
+        let ret = intersect_sat::query_intersect(&a, &b).unwrap();
+        assert!(!ret._is_intersect);
+        assert!((ret._depth + 0.2f64).abs() < 0.000001f64);
+    }
+}
+
+#[test]
+fn test_intersect_sat_triangle() {
+    let a = Triangle3::init(&[0f64, 0f64, 0f64, 2f64, 0f64, 0f64, 0f64, 2f64, 0f64]);
+    let p = Polyhedron::init_from_shape(&a).unwrap();
+    assert_eq!(p._faces.len(), 2);
+    assert_eq!(p.get_sides().len(), 3);
+    {
+        //coplanar and only separated across the hypotenuse
+        let b = Triangle3::init(&[2f64, 2f64, 0f64, 1.2f64, 2f64, 0f64, 2f64, 1.2f64, 0f64]);
+        let ret = intersect_sat::query_intersect(&a, &b).unwrap();
+        assert!(!ret._is_intersect);
+        assert!((ret._depth + 1.2f64 / 2f64.sqrt()).abs() < 0.000001f64);
+    }
+    {
+        //coplanar and overlapping, contact point lies in both triangles
+        let b = Triangle3::init(&[1f64, 1f64, 0f64, 0.2f64, 1f64, 0f64, 1f64, 0.2f64, 0f64]);
+        let ret = intersect_sat::query_intersect(&a, &b).unwrap();
+        assert!(ret._is_intersect);
+        let c = ret._point.unwrap();
+        assert!(c[0] + c[1] <= 2f64 + 0.000001f64);
+        assert!(c[0] + c[1] >= 1.2f64 - 0.000001f64);
+    }
+    //polyhedra with volume have no sides
+    let b = Polyhedron::init_from_shape(&RecBox::init(&[0f64, 0f64, 0f64], 1f64)).unwrap();
+    assert!(b.get_sides().is_empty());
+}
+
+#[test]
+fn test_intersect_sat_edge_edge() {
+    let a = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    {
+        let b = ridge_prism(0.95f64);
+        let ret = intersect_sat::query_intersect(&a, &b).unwrap();
+        assert!(ret._is_intersect);
+        match ret._feature {
+            Feature::Edge(_, _) => {}
+            _ => panic!("unexpected feature"),
+        }
+        assert!((ret._depth - 0.05f64 * 2f64.sqrt()).abs() < 0.000001f64);
+        assert!((ret._normal._val[0] - 0.5f64.sqrt()).abs() < 0.000001f64);
+        assert!((ret._normal._val[1] - 0.5f64.sqrt()).abs() < 0.000001f64);
+        let p = ret._point.unwrap();
+        assert!(a.get_intersect(&Point3::init(&p._val)).0);
+        assert!(b.get_intersect(&Point3::init(&p._val)).0);
+    }
+    {
+        let b = ridge_prism(1.05f64);
+        let ret = intersect_sat::query_intersect(&a, &b).unwrap();
+        assert!(!ret._is_intersect);
+        match ret._feature {
+            Feature::Edge(_, _) => {}
+            _ => panic!("unexpected feature"),
+        }
+        assert!((ret._depth + 0.05f64 * 2f64.sqrt()).abs() < 0.000001f64);
+    }
+}
+
+#[test]
+fn test_intersect_sat_get_intersect() {
+    let prism = TriPrism::init(
+        &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+        2f64,
+    );
+    for i in 0..7 {
+        for j in 0..7 {
+            let c = [
+                -1.5f64 + 0.9f64 * i as f64,
+                -1.5f64 + 0.9f64 * j as f64,
+                1f64,
+            ];
+            let b = RecBox::init(&c, 0.7f64);
+            let p = TriPrism::init(
+                &[
+                    c[0],
+                    c[1],
+                    -0.5f64,
+                    c[0] + 1f64,
+                    c[1],
+                    -0.5f64,
+                    c[0],
+                    c[1] + 1f64,
+                    -0.5f64,
+                ],
+                1f64,
+            );
+            for other in [&b as &dyn IShape, &p as &dyn IShape].iter() {
+                let expected = intersect_gjk::query_intersect(&prism, *other).unwrap();
+                let (is_intersect, point) = prism.get_intersect(*other);
+                assert_eq!(is_intersect, expected);
+                assert_eq!(other.get_intersect(&prism).0, expected);
+                if is_intersect {
+                    let point = Point3::init(&point.unwrap()._val);
+                    assert!(prism.get_intersect(&point).0);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_intersect_sat_degenerate() {
+    //collinear base with zero height leaves no face or edge axis to test
+    let a = TriPrism::init(
+        &[0f64, 0f64, 0f64, 1f64, 0f64, 0f64, 2f64, 0f64, 0f64],
+        0f64,
+    );
+    let b = TriPrism::init(
+        &[0f64, 0f64, 0f64, 1f64, 0f64, 0f64, 3f64, 0f64, 0f64],
+        0f64,
+    );
+    assert!(intersect_sat::query_intersect(&a, &b).is_none());
+    match a.get_intersection(&b) {
+        Err(MazthError::DegenerateInput) => {}
+        _ => panic!("unexpected result"),
+    }
+    //axes of the box still decide against a degenerate prism
+    let c = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    assert!(a.get_intersection(&c).is_ok());
+    assert!(c.get_intersection(&a).is_ok());
+}
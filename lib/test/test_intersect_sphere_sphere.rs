@@ -55,3 +55,28 @@ fn test_intersect_sphere_sphere() {
         }
     }
 }
+
+#[test]
+fn test_intersect_sphere_sphere_intersection() {
+    let a = Sphere::init(&[10f64, 0f64, 0f64], 5f64);
+    let b = Sphere::init(&[18f64, 0f64, 0f64], 5f64);
+    let ret = a.get_intersection(&b).unwrap();
+    assert!(ret._is_intersect);
+    assert!((ret._depth.unwrap() - 2f64).abs() < 0.0001f64);
+    assert!(ret._hits[0]
+        ._normal
+        .unwrap()
+        .is_equal(
+            &Mat3x1 {
+                _val: [-1f64, 0f64, 0f64]
+            },
+            0.0001f64
+        )
+        .unwrap());
+    assert!(
+        !Sphere::init(&[30f64, 0f64, 0f64], 5f64)
+            .get_intersection(&a)
+            .unwrap()
+            ._is_intersect
+    );
+}
@@ -0,0 +1,237 @@
+use error::MazthError;
+use i_comparable::IComparableError;
+use i_shape::IShape;
+
+use intersect_gjk;
+use line::Line3;
+use mat::Mat3x1;
+use plane::Plane;
+use point::Point3;
+use ray::Ray3;
+use rbox::RecBox;
+use sphere::Sphere;
+use triangle::Triangle3;
+use triprism::TriPrism;
+
+fn vec3(x: f64, y: f64, z: f64) -> Mat3x1<f64> {
+    Mat3x1 { _val: [x, y, z] }
+}
+
+fn init_triangle() -> Triangle3 {
+    Triangle3::init(&[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64])
+}
+
+#[test]
+fn test_intersect_triangle_point() {
+    let a = init_triangle();
+    assert!(a.get_intersect(&Point3::init(&[1f64, 1f64, 0f64])).0);
+    assert!(a.get_intersect(&Point3::init(&[2f64, 2f64, 0f64])).0);
+    assert!(!a.get_intersect(&Point3::init(&[3f64, 3f64, 0f64])).0);
+    assert!(!a.get_intersect(&Point3::init(&[1f64, 1f64, 0.5f64])).0);
+    assert!(Point3::init(&[1f64, 1f64, 0f64]).get_intersect(&a).0);
+}
+
+#[test]
+fn test_intersect_triangle_ray() {
+    let a = init_triangle();
+    {
+        let b = Ray3::init(&[1f64, 2f64, 5f64], &[0f64, 0f64, -1f64]);
+        let ret = b.get_intersection(&a).unwrap();
+        assert!(ret._is_intersect);
+        let hit = &ret._hits[0];
+        assert!((hit._t.unwrap() - 5f64).abs() < 0.0001f64);
+        assert!(hit
+            ._point
+            .is_equal(&vec3(1f64, 2f64, 0f64), 0.0001f64)
+            .unwrap());
+        //normal of the triangle facing the ray
+        assert!(hit
+            ._normal
+            .unwrap()
+            .is_equal(&vec3(0f64, 0f64, 1f64), 0.0001f64)
+            .unwrap());
+        let (t, u, v) = a
+            .intersect_ray(&b._ori, &b._dir)
+            .expect("triangle ray return unexpected");
+        assert!((t - 5f64).abs() < 0.0001f64);
+        assert!((u - 0.25f64).abs() < 0.0001f64);
+        assert!((v - 0.5f64).abs() < 0.0001f64);
+    }
+    {
+        //from behind
+        let b = Ray3::init(&[1f64, 1f64, -5f64], &[0f64, 0f64, 1f64]);
+        let ret = b.get_intersection(&a).unwrap();
+        assert!(ret._is_intersect);
+        assert!(ret._hits[0]
+            ._normal
+            .unwrap()
+            .is_equal(&vec3(0f64, 0f64, -1f64), 0.0001f64)
+            .unwrap());
+    }
+    {
+        let b = Ray3::init(&[3f64, 3f64, 5f64], &[0f64, 0f64, -1f64]);
+        assert!(!b.get_intersect(&a).0);
+        let b = Ray3::init(&[1f64, 1f64, 5f64], &[0f64, 0f64, 1f64]);
+        assert!(!a.get_intersect(&b).0);
+    }
+}
+
+#[test]
+fn test_intersect_triangle_line() {
+    let a = init_triangle();
+    {
+        let b = Line3::init(&[1f64, 1f64, -1f64], &[1f64, 1f64, 3f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._hits[0]._t.unwrap() - 0.25f64).abs() < 0.0001f64);
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        //segment ends before reaching the triangle
+        let b = Line3::init(&[1f64, 1f64, 3f64], &[1f64, 1f64, 1f64]);
+        assert!(!a.get_intersect(&b).0);
+    }
+}
+
+#[test]
+fn test_intersect_triangle_sphere() {
+    let a = init_triangle();
+    {
+        let b = Sphere::init(&[1f64, 1f64, 0.5f64], 1f64);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._depth.unwrap() - 0.5f64).abs() < 0.0001f64);
+        assert!(ret._hits[0]
+            ._point
+            .is_equal(&vec3(1f64, 1f64, 0f64), 0.0001f64)
+            .unwrap());
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        //closest to the edge on the x axis
+        let b = Sphere::init(&[2f64, -0.9f64, 0f64], 1f64);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._hits[0]
+            ._point
+            .is_equal(&vec3(2f64, 0f64, 0f64), 0.0001f64)
+            .unwrap());
+        let b = Sphere::init(&[3f64, 3f64, 0f64], 1f64);
+        assert!(!a.get_intersect(&b).0);
+    }
+}
+
+#[test]
+fn test_intersect_triangle_plane() {
+    let a = Triangle3::init(&[0f64, 0f64, 0f64, 4f64, 0f64, 1f64, 0f64, 4f64, 2f64]);
+    {
+        let b = Plane::init(&[0f64, 0f64, 0.5f64], &[0f64, 0f64, 1f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._depth.unwrap() - 0.5f64).abs() < 0.0001f64);
+        assert!(ret._hits[0]
+            ._point
+            .is_equal(&vec3(0f64, 0f64, 0.5f64), 0.0001f64)
+            .unwrap());
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        let b = Plane::init(&[0f64, 0f64, -0.5f64], &[0f64, 0f64, 1f64]);
+        assert!(!a.get_intersect(&b).0);
+    }
+}
+
+#[test]
+fn test_intersect_triangle_box() {
+    let b = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    {
+        //piercing the top face
+        let a = Triangle3::init(&[0f64, 0f64, 0.5f64, 3f64, 0f64, 3f64, 0f64, 3f64, 3f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        //bounds overlap but the triangle passes by the corner
+        let a = Triangle3::init(&[3.5f64, 0f64, 0f64, 0f64, 3.5f64, 0f64, 0f64, 0f64, 3.5f64]);
+        assert!(!a.get_intersect(&b).0);
+        let c = Triangle3::init(&[1.5f64, 0f64, 0f64, 0f64, 1.5f64, 0f64, 0f64, 0f64, 1.5f64]);
+        assert!(c.get_intersect(&b).0);
+    }
+    {
+        //resting on the top face from above
+        let a = Triangle3::init(&[
+            -0.5f64, -0.5f64, 0.9f64, 0.5f64, -0.5f64, 0.9f64, 0f64, 0.5f64, 0.9f64,
+        ]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!((ret._depth.unwrap() - 0.1f64).abs() < 0.0001f64);
+        assert!(ret._hits[0]
+            ._normal
+            .unwrap()
+            .is_equal(&vec3(0f64, 0f64, 1f64), 0.0001f64)
+            .unwrap());
+    }
+}
+
+#[test]
+fn test_intersect_triangle_triangle() {
+    let a = init_triangle();
+    {
+        let b = Triangle3::init(&[1f64, 1f64, -1f64, 1f64, 1f64, 1f64, -1f64, -1f64, 1f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!(!ret._hits.is_empty());
+        for h in ret._hits.iter() {
+            assert!(h._point[2].abs() < 0.0001f64);
+        }
+    }
+    {
+        let b = Triangle3::init(&[3f64, 3f64, -1f64, 3f64, 3f64, 1f64, 5f64, 5f64, 0f64]);
+        assert!(!a.get_intersect(&b).0);
+    }
+    {
+        //coplanar
+        let b = Triangle3::init(&[1f64, 1f64, 0f64, 5f64, 1f64, 0f64, 1f64, 5f64, 0f64]);
+        assert!(a.get_intersect(&b).0);
+        let c = Triangle3::init(&[3f64, 3f64, 0f64, 5f64, 3f64, 0f64, 3f64, 5f64, 0f64]);
+        assert!(!a.get_intersect(&c).0);
+    }
+}
+
+#[test]
+fn test_intersect_triangle_degenerate() {
+    //collinear vertices on both triangles leave no separating axis to test
+    let a = Triangle3::init(&[0f64, 0f64, 0f64, 1f64, 0f64, 0f64, 2f64, 0f64, 0f64]);
+    let b = Triangle3::init(&[0f64, 0f64, 0f64, 1f64, 0f64, 0f64, 3f64, 0f64, 0f64]);
+    match a.get_intersection(&b) {
+        Err(MazthError::DegenerateInput) => {}
+        _ => panic!("unexpected result for degenerate triangle intersection"),
+    }
+    assert!(b.get_intersection(&a).is_err());
+}
+
+#[test]
+fn test_intersect_triangle_prism() {
+    let a = init_triangle();
+    let b = TriPrism::init(
+        &[1f64, 1f64, -1f64, 3f64, 1f64, -1f64, 1f64, 3f64, -1f64],
+        2f64,
+    );
+    let ret = a.get_intersection(&b).unwrap();
+    assert!(ret._is_intersect);
+    assert!(b.get_intersect(&a).0);
+    let c = TriPrism::init(
+        &[1f64, 1f64, 0.5f64, 3f64, 1f64, 0.5f64, 1f64, 3f64, 0.5f64],
+        2f64,
+    );
+    assert!(!a.get_intersect(&c).0);
+    assert!(!c.get_intersect(&a).0);
+}
+
+#[test]
+fn test_intersect_triangle_gjk() {
+    let a = init_triangle();
+    let b = RecBox::init(&[1f64, 1f64, 0.5f64], 1f64);
+    assert!(intersect_gjk::query_intersect(&a, &b).unwrap());
+    let b = RecBox::init(&[4f64, 4f64, 0f64], 1f64);
+    assert!(!intersect_gjk::query_intersect(&a, &b).unwrap());
+}
@@ -0,0 +1,60 @@
+use i_shape::IShape;
+
+use ray::Ray3;
+use sphere::Sphere;
+use triprism::TriPrism;
+
+fn init_prism() -> TriPrism {
+    TriPrism::init(
+        &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+        2f64,
+    )
+}
+
+#[test]
+fn test_intersect_triprism_ray() {
+    let a = init_prism();
+    {
+        let b = Ray3::init(&[-5f64, 1f64, 1f64], &[1f64, 0f64, 0f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert_eq!(ret._hits.len(), 2);
+        //enters through the side at x = 0 and leaves through the slanted side at x + y = 4
+        assert!((ret._hits[0]._t.unwrap() - 5f64).abs() < 0.0001f64);
+        assert!((ret._hits[1]._t.unwrap() - 8f64).abs() < 0.0001f64);
+        assert!((ret._hits[0]._normal.unwrap()[0] - 1f64).abs() < 0.0001f64);
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        //starts inside and leaves through the top
+        let b = Ray3::init(&[1f64, 1f64, 1f64], &[0f64, 0f64, 1f64]);
+        let ret = a.get_intersection(&b).unwrap();
+        assert_eq!(ret._hits.len(), 1);
+        assert!((ret._hits[0]._point[2] - 2f64).abs() < 0.0001f64);
+    }
+    {
+        //passes above the prism and points away from it
+        let b = Ray3::init(&[-5f64, 1f64, 3f64], &[1f64, 0f64, 0f64]);
+        assert!(!a.get_intersect(&b).0);
+        let b = Ray3::init(&[-5f64, 1f64, 1f64], &[-1f64, 0f64, 0f64]);
+        assert!(!b.get_intersect(&a).0);
+    }
+}
+
+#[test]
+fn test_intersect_triprism_sphere() {
+    let a = init_prism();
+    {
+        let b = Sphere::init(&[1f64, -0.5f64, 1f64], 1f64);
+        let ret = a.get_intersection(&b).unwrap();
+        assert!(ret._is_intersect);
+        assert!((ret._depth.unwrap() - 0.5f64).abs() < 0.001f64);
+        assert!((ret._hits[0]._normal.unwrap()[1] - 1f64).abs() < 0.001f64);
+        assert!(b.get_intersect(&a).0);
+    }
+    {
+        let b = Sphere::init(&[3f64, 3f64, 1f64], 1f64);
+        assert!(!a.get_intersect(&b).0);
+        assert!(!b.get_intersect(&a).0);
+    }
+}
@@ -243,4 +243,26 @@ fn test_mat4() {
             )
             .expect("mat3 is_equal invalid"));
     }
+    {
+        //eigen decomposition of a symmetric matrix
+        let v1 = Mat3::<f64>::init([2f64, 1f64, 0f64, 1f64, 2f64, 0f64, 0f64, 0f64, 5f64], true);
+        let (values, vectors) = v1.eigen_symmetric();
+        let expect = [5f64, 3f64, 1f64];
+        for i in 0..3 {
+            assert!((values[i] - expect[i]).abs() < 0.0001f64);
+            let v = Mat3x1 {
+                _val: [
+                    vectors.index(0, i as u64),
+                    vectors.index(1, i as u64),
+                    vectors.index(2, i as u64),
+                ],
+            };
+            assert!((v.magnitude().unwrap() - 1f64).abs() < 0.0001f64);
+            assert!(v1
+                .mul_mat3x1(&v)
+                .unwrap()
+                .is_equal(&v.scale(values[i]).unwrap(), 0.0001f64)
+                .unwrap());
+        }
+    }
 }
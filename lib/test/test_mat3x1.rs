@@ -70,4 +70,14 @@ fn test_mat3x1() {
             .expect("Mat3x1 is_equal invalid")
             == false
     );
+    {
+        let v8 = v.midpoint(&v2).expect("midpoint invalid");
+        assert!(v8
+            .is_equal(&Mat3x1 { _val: [6f64; 3] }, 0.0001f64)
+            .expect("Mat3x1 is_equal invalid"));
+    }
+    {
+        let v9 = Mat3x1::<f64>::init_from_slice(&[1f64, 2f64, 3f64, 4f64]);
+        assert!(v9._val == [1f64, 2f64, 3f64]);
+    }
 }
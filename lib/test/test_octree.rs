@@ -0,0 +1,188 @@
+use bound::AxisAlignedBBox;
+use frustum::{Containment, Frustum};
+use i_comparable::IComparableError;
+use i_shape::IShape;
+use line::Line3;
+use mat::Mat3x1;
+use octree::Octree;
+use plane::Plane;
+use point::Point3;
+use ray::Ray3;
+use rbox::RecBox;
+use sphere::Sphere;
+
+fn init_shapes(with_boxes: bool) -> Vec<Box<dyn IShape>> {
+    let mut shapes: Vec<Box<dyn IShape>> = vec![];
+    for i in 0..6 {
+        for j in 0..6 {
+            for k in 0..6 {
+                let c = [3f64 * i as f64, 3f64 * j as f64, 3f64 * k as f64];
+                if !with_boxes || (i + j + k) % 2 == 0 {
+                    shapes.push(Box::new(Sphere::init(&c, 0.5f64 + 0.1f64 * (i % 3) as f64)));
+                } else {
+                    shapes.push(Box::new(RecBox::init(&c, 0.4f64 + 0.1f64 * (j % 3) as f64)));
+                }
+            }
+        }
+    }
+    shapes
+}
+
+#[test]
+fn test_octree_query_ray() {
+    let mut shapes = init_shapes(false);
+    shapes.push(Box::new(Plane::init(
+        &[0f64, -5f64, 0f64],
+        &[0f64, 1f64, 0f64],
+    )));
+    let plane_index = shapes.len() - 1;
+    let octree = Octree::init(shapes, 5, 2);
+    assert!(octree._nodes.len() > 1);
+    assert!(octree._unbounded == vec![plane_index]);
+
+    {
+        let r = Ray3::init(&[-10f64, 6f64, 3f64], &[1f64, 0f64, 0f64]);
+        match octree.query_ray(&r) {
+            Some((i, loc)) => {
+                //first shape along the row is a sphere at (0,6,3)
+                assert!(i == 13);
+                let radius = octree._shapes[i].get_shape_data()[3];
+                assert!(loc
+                    .is_equal(
+                        &Mat3x1 {
+                            _val: [-radius, 6f64, 3f64]
+                        },
+                        0.0001f64
+                    )
+                    .unwrap());
+            }
+            _ => panic!("unexpected result for octree ray query"),
+        }
+    }
+    {
+        let r = Ray3::init(&[30f64, 6f64, 3f64], &[-1f64, 0f64, 0f64]);
+        match octree.query_ray(&r) {
+            Some((i, _)) => assert!(i == 193),
+            _ => panic!("unexpected result for octree ray query"),
+        }
+    }
+    {
+        //misses the grid of spheres and hits the plane
+        let r = Ray3::init(&[1.5f64, 20f64, 1.5f64], &[0f64, -1f64, 0f64]);
+        match octree.query_ray(&r) {
+            Some((i, loc)) => {
+                assert!(i == plane_index);
+                assert!(loc
+                    .is_equal(
+                        &Mat3x1 {
+                            _val: [1.5f64, -5f64, 1.5f64]
+                        },
+                        0.0001f64
+                    )
+                    .unwrap());
+            }
+            _ => panic!("unexpected result for octree ray query"),
+        }
+    }
+    {
+        let r = Ray3::init(&[1.5f64, 20f64, 1.5f64], &[0f64, 1f64, 0f64]);
+        assert!(octree.query_ray(&r).is_none());
+    }
+}
+
+#[test]
+fn test_octree_query_point() {
+    let octree = Octree::init(init_shapes(true), 4, 1);
+    let shapes = init_shapes(true);
+
+    for p in [
+        [3.2f64, 0f64, 0f64],
+        [0.1f64, 0.1f64, 15.2f64],
+        [1.5f64, 1.5f64, 1.5f64],
+    ]
+    .iter()
+    {
+        let point = Point3::init(&p[..]);
+        let expect: Vec<usize> = shapes
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| x.get_bound().intersect(&point._bound) && x.get_intersect(&point).0)
+            .map(|(i, _)| i)
+            .collect();
+        assert!(octree.query_point(&p[..]) == expect);
+    }
+    assert!(octree.query_point(&[3.2f64, 0f64, 0f64]) == vec![36]);
+    assert!(octree.query_point(&[1.5f64, 1.5f64, 1.5f64]).is_empty());
+
+    let query = AxisAlignedBBox {
+        _bound_lower: [2f64, 2f64, 2f64],
+        _bound_upper: [4f64, 4f64, 7f64],
+    };
+    let expect: Vec<usize> = shapes
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| x.get_bound().intersect(&query))
+        .map(|(i, _)| i)
+        .collect();
+    assert!(octree.query_bound(&query) == expect);
+
+    //single node tree
+    let octree = Octree::init(init_shapes(true), 0, 1);
+    assert!(octree._nodes.len() == 1);
+    assert!(octree.query_point(&[3.2f64, 0f64, 0f64]) == vec![36]);
+}
+
+#[test]
+fn test_octree_query_frustum() {
+    let octree = Octree::init(init_shapes(true), 4, 1);
+    let shapes = init_shapes(true);
+
+    let frustum = Frustum::init_perspective(
+        &[7.5f64, 7.5f64, -5f64],
+        &[0f64, 0f64, 1f64],
+        &[0f64, 1f64, 0f64],
+        0.5f64,
+        1.5f64,
+        1f64,
+        12f64,
+    );
+    let expect: Vec<usize> = shapes
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| {
+            frustum.cull_aabb(&AxisAlignedBBox::init_from_bound(x.get_bound()))
+                != Containment::Outside
+        })
+        .map(|(i, _)| i)
+        .collect();
+    assert!(!expect.is_empty());
+    assert!(expect.len() < shapes.len());
+    assert!(octree.query_frustum(&frustum) == expect);
+
+    //frustum enclosing every shape
+    let frustum = Frustum::init_perspective(
+        &[7.5f64, 7.5f64, -20f64],
+        &[0f64, 0f64, 1f64],
+        &[0f64, 1f64, 0f64],
+        1.5f64,
+        1f64,
+        1f64,
+        100f64,
+    );
+    assert!(octree.query_frustum(&frustum) == (0..shapes.len()).collect::<Vec<usize>>());
+}
+
+#[test]
+fn test_octree_unsupported_pairs() {
+    //lines support neither rays nor points
+    let shapes: Vec<Box<dyn IShape>> = vec![
+        Box::new(Line3::init(&[0f64, 0f64, 0f64], &[10f64, 0f64, 0f64])),
+        Box::new(Sphere::init(&[5f64, 0f64, 0f64], 1f64)),
+    ];
+    let octree = Octree::init(shapes, 4, 1);
+    assert!(octree.query_point(&[5f64, 0f64, 0f64]) == vec![1]);
+    let (i, _) = octree
+        .query_ray(&Ray3::init(&[5f64, 5f64, 0f64], &[0f64, -1f64, 0f64]))
+        .expect("octree ray query unexpected");
+    assert!(i == 1);
+}
@@ -0,0 +1,173 @@
+use bound::AxisAlignedBBox;
+use bound_sphere::BoundSphere;
+use i_shape::{IShape, ShapeType};
+use plane::Plane;
+use point::Point3;
+use spatial_hash::{Handle, SpatialHash};
+use sphere::Sphere;
+
+fn init_points() -> Vec<Point3> {
+    let mut points = vec![];
+    for i in 0..200 {
+        let t = i as f64;
+        points.push(Point3::init(&[
+            (t * 1.7f64).sin() * 10f64,
+            (t * 0.9f64).cos() * 10f64,
+            (t * 0.37f64).sin() * 5f64,
+        ]));
+    }
+    points
+}
+
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+#[test]
+fn test_spatial_hash_query_radius() {
+    let points = init_points();
+    let mut grid = SpatialHash::init(1.5f64);
+    let handles: Vec<Handle> = points.iter().map(|x| grid.insert(x.get_bound())).collect();
+
+    let q = [1f64, -2f64, 0.5f64];
+    for radius in [0.5f64, 2f64, 4.5f64].iter() {
+        let ret = grid.query_radius(&q, *radius);
+        let mut expect: Vec<Handle> = points
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| distance(&x.get_shape_data()[..], &q) <= *radius)
+            .map(|(i, _)| handles[i])
+            .collect();
+        expect.sort();
+        assert!(ret == expect);
+    }
+
+    //spheres are found by their bounds
+    let s = Sphere::init(&[30f64, 0f64, 0f64], 2f64);
+    let h = grid.insert(s.get_bound());
+    assert!(grid.query_radius(&[33f64, 0f64, 0f64], 1.1f64) == vec![h]);
+    assert!(grid.query_radius(&[33f64, 0f64, 0f64], 0.9f64).is_empty());
+
+    //moved sphere is found at its new location only
+    let s = Sphere::init(&[-30f64, 0f64, 0f64], 2f64);
+    assert!(grid.update(h, s.get_bound()));
+    assert!(grid.query_radius(&[33f64, 0f64, 0f64], 1.1f64).is_empty());
+    assert!(grid.query_radius(&[-33f64, 0f64, 0f64], 1.1f64) == vec![h]);
+
+    assert!(grid.remove(h));
+    assert!(!grid.remove(h));
+    assert!(grid.query_radius(&[-33f64, 0f64, 0f64], 1.1f64).is_empty());
+
+    //near the corner of the box of a bounding sphere but outside of the sphere itself
+    let s = BoundSphere::init(ShapeType::Sphere, &[30f64, 0f64, 0f64, 2f64]);
+    let h = grid.insert(&s);
+    assert!(!grid
+        .query_radius(&[31.8f64, 1.8f64, 1.8f64], 1.2f64)
+        .is_empty());
+    assert!(grid
+        .query_radius(&[31.8f64, 1.8f64, 1.8f64], 0.1f64)
+        .is_empty());
+    let ret = grid.query_nearest(&[31.8f64, 1.8f64, 1.8f64], 1);
+    assert!(ret[0].0 == h);
+    assert!((ret[0].1 - (3f64 * 1.8f64 * 1.8f64).sqrt() + 2f64).abs() < 0.0001f64);
+}
+
+#[test]
+fn test_spatial_hash_query_nearest() {
+    let points = init_points();
+    let mut grid = SpatialHash::init(2f64);
+    let handles: Vec<Handle> = points.iter().map(|x| grid.insert(x.get_bound())).collect();
+
+    for q in [
+        [0f64, 0f64, 0f64],
+        [7f64, -3f64, 2f64],
+        [40f64, 40f64, 40f64],
+    ]
+    .iter()
+    {
+        let ret = grid.query_nearest(&q[..], 5);
+        let mut expect: Vec<(Handle, f64)> = points
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (handles[i], distance(&x.get_shape_data()[..], &q[..])))
+            .collect();
+        expect.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        assert!(ret.len() == 5);
+        for i in 0..5 {
+            assert!((ret[i].1 - expect[i].1).abs() < 0.0001f64);
+        }
+        assert!(ret[0].0 == expect[0].0);
+    }
+
+    //requesting more than stored returns everything
+    assert!(grid.query_nearest(&[0f64, 0f64, 0f64], 1000).len() == points.len());
+    assert!(grid.query_nearest(&[0f64, 0f64, 0f64], 0).is_empty());
+    assert!(SpatialHash::init(1f64)
+        .query_nearest(&[0f64, 0f64, 0f64], 3)
+        .is_empty());
+
+    //sparse grid with the only bound far away from the query
+    let mut grid = SpatialHash::init(1f64);
+    let h = grid.insert(Point3::init(&[1e6f64, 0f64, 0f64]).get_bound());
+    let ret = grid.query_nearest(&[0f64, 0f64, 0f64], 1);
+    assert!(ret.len() == 1);
+    assert!(ret[0].0 == h);
+    assert!((ret[0].1 - 1e6f64).abs() < 0.0001f64);
+}
+
+#[test]
+fn test_spatial_hash_query_bound() {
+    let points = init_points();
+    let mut grid = SpatialHash::init(3f64);
+    let handles: Vec<Handle> = points.iter().map(|x| grid.insert(x.get_bound())).collect();
+
+    let query = AxisAlignedBBox {
+        _bound_lower: [-2f64, 0f64, -1f64],
+        _bound_upper: [5f64, 8f64, 3f64],
+    };
+    let ret = grid.query_bound(&query);
+    let mut expect: Vec<Handle> = points
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| x.get_bound().intersect(&query))
+        .map(|(i, _)| handles[i])
+        .collect();
+    expect.sort();
+    assert!(!expect.is_empty());
+    assert!(ret == expect);
+
+    //unbounded shapes are reported by every overlapping query
+    let p = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64]);
+    let h = grid.insert(p.get_bound());
+    assert!(grid.query_bound(&query).contains(&h));
+    assert!(grid.query_radius(&[100f64, 100f64, 100f64], 1f64) == vec![h]);
+    assert!(grid.query_nearest(&[100f64, 100f64, 100f64], 1)[0].0 == h);
+    assert!(grid.query_bound(p.get_bound()).len() == points.len() + 1);
+}
+
+#[test]
+fn test_spatial_hash_oversized() {
+    //a box spanning 1e15 cells is kept aside instead of being hashed
+    let mut grid = SpatialHash::init(0.001f64);
+    let large = AxisAlignedBBox {
+        _bound_lower: [-50f64, -50f64, -50f64],
+        _bound_upper: [50f64, 50f64, 50f64],
+    };
+    let h = grid.insert(&large);
+    assert!(grid._unbounded == vec![h.0]);
+    assert!(grid._cells.is_empty());
+    assert!(grid.query_radius(&[40f64, 40f64, 40f64], 0.001f64) == vec![h]);
+    assert!(grid.query_radius(&[60f64, 0f64, 0f64], 1f64).is_empty());
+
+    //shrinking it hashes it into cells
+    let small = AxisAlignedBBox {
+        _bound_lower: [0f64, 0f64, 0f64],
+        _bound_upper: [0.0025f64, 0.0005f64, 0.0005f64],
+    };
+    assert!(grid.update(h, &small));
+    assert!(grid._unbounded.is_empty());
+    assert!(grid._cells.len() == 3);
+    assert!(grid.query_radius(&[0.002f64, 0f64, 0f64], 0.0001f64) == vec![h]);
+    assert!(grid.remove(h));
+    assert!(grid._cells.is_empty());
+}
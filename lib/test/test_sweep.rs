@@ -0,0 +1,243 @@
+use i_comparable::IComparableError;
+
+use bound::AxisAlignedBBox;
+use i_shape::ShapeType;
+use mat::Mat3x1;
+use plane::Plane;
+use rbox::RecBox;
+use sphere::Sphere;
+use sweep;
+use triprism::TriPrism;
+
+fn vec3(x: f64, y: f64, z: f64) -> Mat3x1<f64> {
+    Mat3x1 { _val: [x, y, z] }
+}
+
+#[test]
+fn test_sweep_sphere_plane() {
+    let b = Plane::init(&[0f64, 0f64, 0f64], &[0f64, 1f64, 0f64]);
+    {
+        let a = Sphere::init(&[0f64, 10f64, 0f64], 1f64);
+        let ret = sweep::sweep_sphere_plane(&a, &vec3(3f64, -18f64, 0f64), &b)
+            .expect("sweep return unexpected");
+        assert!((ret._time - 0.5f64).abs() < 0.0001f64);
+        assert!(ret
+            ._point
+            .is_equal(&vec3(1.5f64, 0f64, 0f64), 0.0001f64)
+            .unwrap());
+        assert!(ret
+            ._normal
+            .is_equal(&vec3(0f64, 1f64, 0f64), 0.0001f64)
+            .unwrap());
+    }
+    {
+        let a = Sphere::init(&[0f64, 10f64, 0f64], 1f64);
+        assert!(sweep::sweep_sphere_plane(&a, &vec3(0f64, -8f64, 0f64), &b).is_none());
+        assert!(sweep::sweep_sphere_plane(&a, &vec3(0f64, 8f64, 0f64), &b).is_none());
+    }
+    {
+        let a = Sphere::init(&[0f64, 0.5f64, 0f64], 1f64);
+        let ret = sweep::sweep_sphere_plane(&a, &vec3(0f64, 8f64, 0f64), &b)
+            .expect("sweep return unexpected");
+        assert_eq!(ret._time, 0f64);
+    }
+}
+
+#[test]
+fn test_sweep_sphere_sphere() {
+    let b = Sphere::init(&[10f64, 0f64, 0f64], 2f64);
+    {
+        let a = Sphere::init(&[0f64, 0f64, 0f64], 1f64);
+        let ret = sweep::sweep_sphere_sphere(&a, &vec3(20f64, 0f64, 0f64), &b)
+            .expect("sweep return unexpected");
+        assert!((ret._time - 0.35f64).abs() < 0.0001f64);
+        assert!(ret
+            ._point
+            .is_equal(&vec3(8f64, 0f64, 0f64), 0.0001f64)
+            .unwrap());
+        assert!(ret
+            ._normal
+            .is_equal(&vec3(-1f64, 0f64, 0f64), 0.0001f64)
+            .unwrap());
+    }
+    {
+        //passes by at distance 3.5
+        let a = Sphere::init(&[0f64, 3.5f64, 0f64], 1f64);
+        assert!(sweep::sweep_sphere_sphere(&a, &vec3(20f64, 0f64, 0f64), &b).is_none());
+    }
+}
+
+#[test]
+fn test_sweep_sphere_triangle() {
+    let tri = [
+        vec3(0f64, 0f64, 0f64),
+        vec3(4f64, 0f64, 0f64),
+        vec3(0f64, 4f64, 0f64),
+    ];
+    {
+        //face from below
+        let a = Sphere::init(&[1f64, 1f64, -5f64], 1f64);
+        let ret = sweep::sweep_sphere_triangle(&a, &vec3(0f64, 0f64, 10f64), &tri)
+            .expect("sweep return unexpected");
+        assert!((ret._time - 0.4f64).abs() < 0.0001f64);
+        assert!(ret
+            ._normal
+            .is_equal(&vec3(0f64, 0f64, -1f64), 0.0001f64)
+            .unwrap());
+    }
+    {
+        //edge along the x axis
+        let a = Sphere::init(&[2f64, -5f64, 0f64], 1f64);
+        let ret = sweep::sweep_sphere_triangle(&a, &vec3(0f64, 10f64, 0f64), &tri)
+            .expect("sweep return unexpected");
+        assert!((ret._time - 0.4f64).abs() < 0.0001f64);
+        assert!(ret
+            ._point
+            .is_equal(&vec3(2f64, 0f64, 0f64), 0.0001f64)
+            .unwrap());
+        assert!(ret
+            ._normal
+            .is_equal(&vec3(0f64, -1f64, 0f64), 0.0001f64)
+            .unwrap());
+    }
+    {
+        //vertex at origin
+        let a = Sphere::init(&[-5f64, -5f64, 0f64], 1f64);
+        let ret = sweep::sweep_sphere_triangle(&a, &vec3(10f64, 10f64, 0f64), &tri)
+            .expect("sweep return unexpected");
+        let expected = (50f64.sqrt() - 1f64) / 200f64.sqrt();
+        assert!((ret._time - expected).abs() < 0.0001f64);
+        assert!(ret
+            ._point
+            .is_equal(&vec3(0f64, 0f64, 0f64), 0.0001f64)
+            .unwrap());
+    }
+    {
+        //passes beside the hypotenuse
+        let a = Sphere::init(&[3f64, 3f64, -5f64], 1f64);
+        assert!(sweep::sweep_sphere_triangle(&a, &vec3(0f64, 0f64, 10f64), &tri).is_none());
+    }
+}
+
+#[test]
+fn test_sweep_sphere_box() {
+    let b = RecBox::init(&[0f64, 0f64, 0f64], 1f64);
+    {
+        let a = Sphere::init(&[-5f64, 0.5f64, 0f64], 1f64);
+        let ret = sweep::sweep_sphere_box(&a, &vec3(10f64, 0f64, 0f64), &b)
+            .expect("sweep return unexpected");
+        assert!((ret._time - 0.3f64).abs() < 0.0001f64);
+        assert!(ret
+            ._point
+            .is_equal(&vec3(-1f64, 0.5f64, 0f64), 0.0001f64)
+            .unwrap());
+        assert!(ret
+            ._normal
+            .is_equal(&vec3(-1f64, 0f64, 0f64), 0.0001f64)
+            .unwrap());
+    }
+    {
+        //edge at y = 1, z = 1
+        let a = Sphere::init(&[0f64, 5f64, 1.6f64], 1f64);
+        let ret = sweep::sweep_sphere_box(&a, &vec3(0f64, -10f64, 0f64), &b)
+            .expect("sweep return unexpected");
+        assert!((ret._time - 0.32f64).abs() < 0.0001f64);
+        assert!(ret
+            ._point
+            .is_equal(&vec3(0f64, 1f64, 1f64), 0.0001f64)
+            .unwrap());
+        assert!(ret
+            ._normal
+            .is_equal(&vec3(0f64, 0.8f64, 0.6f64), 0.0001f64)
+            .unwrap());
+    }
+    {
+        //vertex at -1, 1, 1
+        let offset = 0.9f64 / 2f64.sqrt();
+        let a = Sphere::init(&[-5f64, 1f64 + offset, 1f64 + offset], 1f64);
+        let ret = sweep::sweep_sphere_box(&a, &vec3(10f64, 0f64, 0f64), &b)
+            .expect("sweep return unexpected");
+        assert!((ret._time - (4f64 - 0.19f64.sqrt()) / 10f64).abs() < 0.0001f64);
+        assert!(ret
+            ._point
+            .is_equal(&vec3(-1f64, 1f64, 1f64), 0.0001f64)
+            .unwrap());
+    }
+    {
+        let a = Sphere::init(&[-5f64, 2.5f64, 0f64], 1f64);
+        assert!(sweep::sweep_sphere_box(&a, &vec3(10f64, 0f64, 0f64), &b).is_none());
+    }
+    {
+        let a = Sphere::init(&[0.5f64, 0f64, 0f64], 0.2f64);
+        let ret = sweep::sweep_sphere_box(&a, &vec3(10f64, 0f64, 0f64), &b)
+            .expect("sweep return unexpected");
+        assert_eq!(ret._time, 0f64);
+        assert!(ret
+            ._normal
+            .is_equal(&vec3(1f64, 0f64, 0f64), 0.0001f64)
+            .unwrap());
+    }
+}
+
+#[test]
+fn test_sweep_sphere_triprism() {
+    let b = TriPrism::init(
+        &[0f64, 0f64, 0f64, 4f64, 0f64, 0f64, 0f64, 4f64, 0f64],
+        2f64,
+    );
+    {
+        //slanted face x + y = 4
+        let a = Sphere::init(&[5f64, 5f64, 1f64], 1f64);
+        let ret = sweep::sweep_sphere_triprism(&a, &vec3(-4f64, -4f64, 0f64), &b)
+            .expect("sweep return unexpected");
+        let expected = (3f64 * 2f64.sqrt() - 1f64) / 32f64.sqrt();
+        assert!((ret._time - expected).abs() < 0.0001f64);
+        let n = 0.5f64.sqrt();
+        assert!(ret._normal.is_equal(&vec3(n, n, 0f64), 0.0001f64).unwrap());
+    }
+    {
+        //top face
+        let a = Sphere::init(&[1f64, 1f64, 10f64], 1f64);
+        let ret = sweep::sweep_sphere_triprism(&a, &vec3(0f64, 0f64, -10f64), &b)
+            .expect("sweep return unexpected");
+        assert!((ret._time - 0.7f64).abs() < 0.0001f64);
+        assert!(ret
+            ._normal
+            .is_equal(&vec3(0f64, 0f64, 1f64), 0.0001f64)
+            .unwrap());
+    }
+}
+
+#[test]
+fn test_sweep_aabb() {
+    let b = AxisAlignedBBox::init(ShapeType::Box, &[0f64, 0f64, 0f64, 1f64]);
+    {
+        let a = AxisAlignedBBox::init(ShapeType::Box, &[-5f64, 1.5f64, 0f64, 1f64]);
+        let ret =
+            sweep::sweep_aabb(&a, &vec3(10f64, 0f64, 0f64), &b).expect("sweep return unexpected");
+        assert!((ret._time - 0.3f64).abs() < 0.0001f64);
+        assert!(ret
+            ._normal
+            .is_equal(&vec3(-1f64, 0f64, 0f64), 0.0001f64)
+            .unwrap());
+        assert!(ret
+            ._point
+            .is_equal(&vec3(-1f64, 0.75f64, 0f64), 0.0001f64)
+            .unwrap());
+    }
+    {
+        let a = AxisAlignedBBox::init(ShapeType::Box, &[-5f64, 2.5f64, 0f64, 1f64]);
+        assert!(sweep::sweep_aabb(&a, &vec3(10f64, 0f64, 0f64), &b).is_none());
+        assert!(sweep::sweep_aabb(&a, &vec3(10f64, -2f64, 0f64), &b).is_some());
+    }
+    {
+        let a = AxisAlignedBBox::init(ShapeType::Box, &[0f64, 1.5f64, 0f64, 1f64]);
+        let ret =
+            sweep::sweep_aabb(&a, &vec3(10f64, 0f64, 0f64), &b).expect("sweep return unexpected");
+        assert_eq!(ret._time, 0f64);
+        assert!(ret
+            ._normal
+            .is_equal(&vec3(0f64, 1f64, 0f64), 0.0001f64)
+            .unwrap());
+    }
+}
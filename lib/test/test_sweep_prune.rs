@@ -0,0 +1,105 @@
+use bound::AxisAlignedBBox;
+use i_bound::IBound;
+use i_shape::ShapeType;
+use sweep_prune::{Handle, SweepPrune};
+
+fn expect_pairs(sap: &SweepPrune, handles: &[Handle]) -> Vec<(Handle, Handle)> {
+    let mut expect = vec![];
+    for i in 0..handles.len() {
+        for j in i + 1..handles.len() {
+            let a = sap.get_bound(handles[i]).unwrap();
+            let b = sap.get_bound(handles[j]).unwrap();
+            if a.intersect(b) {
+                expect.push(if handles[i] < handles[j] {
+                    (handles[i], handles[j])
+                } else {
+                    (handles[j], handles[i])
+                });
+            }
+        }
+    }
+    expect.sort();
+    expect
+}
+
+#[test]
+fn test_sweep_prune_insert_remove() {
+    let mut sap = SweepPrune::init();
+    let a = sap.insert(&AxisAlignedBBox {
+        _bound_lower: [0f64, 0f64, 0f64],
+        _bound_upper: [10f64, 10f64, 10f64],
+    });
+    let b = sap.insert(&AxisAlignedBBox {
+        _bound_lower: [5f64, 5f64, 5f64],
+        _bound_upper: [6f64, 6f64, 6f64],
+    });
+    let c = sap.insert(&AxisAlignedBBox {
+        _bound_lower: [10f64, 20f64, 0f64],
+        _bound_upper: [20f64, 30f64, 10f64],
+    });
+    //touching bounds are reported
+    let d = sap.insert(&AxisAlignedBBox {
+        _bound_lower: [10f64, 0f64, 0f64],
+        _bound_upper: [20f64, 10f64, 10f64],
+    });
+
+    let pairs: Vec<(Handle, Handle)> = sap.query_pairs().collect();
+    assert!(pairs == vec![(a, b), (a, d)]);
+
+    assert!(sap.remove(a));
+    assert!(!sap.remove(a));
+    assert!(sap.query_pairs().next().is_none());
+
+    //freed handles are reused
+    let e = sap.insert(&AxisAlignedBBox::init(
+        ShapeType::Sphere,
+        &[15f64, 15f64, 5f64, 6f64],
+    ));
+    assert!(e == a);
+    let pairs: Vec<(Handle, Handle)> = sap.query_pairs().collect();
+    assert!(pairs == vec![(e, c), (e, d)]);
+}
+
+#[test]
+fn test_sweep_prune_coherent_motion() {
+    let mut sap = SweepPrune::init();
+    let mut handles = vec![];
+    let mut centers = vec![];
+    for i in 0..6 {
+        for j in 0..6 {
+            for k in 0..2 {
+                let c = [2f64 * i as f64, 2f64 * j as f64, 2f64 * k as f64];
+                let r = 0.6f64 + 0.15f64 * ((i + 2 * j + k) % 4) as f64;
+                handles.push(sap.insert(&AxisAlignedBBox::init(
+                    ShapeType::Sphere,
+                    &[c[0], c[1], c[2], r],
+                )));
+                centers.push((c, r));
+            }
+        }
+    }
+    let pairs: Vec<(Handle, Handle)> = sap.query_pairs().collect();
+    assert!(!pairs.is_empty());
+    assert!(pairs == expect_pairs(&sap, &handles[..]));
+
+    for frame in 0..20 {
+        for (n, h) in handles.iter().enumerate() {
+            let (c, r) = centers[n];
+            let phase = (frame + n) as f64 * 0.3f64;
+            let offset = [
+                phase.sin() * 0.7f64,
+                phase.cos() * 0.5f64,
+                0.2f64 * phase.sin(),
+            ];
+            sap.update(
+                *h,
+                &AxisAlignedBBox::init(
+                    ShapeType::Sphere,
+                    &[c[0] + offset[0], c[1] + offset[1], c[2] + offset[2], r],
+                ),
+            );
+        }
+        let pairs: Vec<(Handle, Handle)> = sap.query_pairs().collect();
+        assert!(pairs == expect_pairs(&sap, &handles[..]));
+    }
+}
@@ -0,0 +1,251 @@
+use i_bound::IBound;
+use i_shape::{IShape, ShapeType};
+use i_vicinity::IVicinity;
+
+use bound::AxisAlignedBBox;
+use error::MazthError;
+use intersect_epa;
+use intersect_gjk;
+use intersect_sat;
+use intersection::{Hit, Intersection};
+use mat::Mat3x1;
+
+#[derive(Debug, Clone)]
+pub struct Triangle3 {
+    ///vertices in ccw order around _normal
+    pub _verts: [Mat3x1<f64>; 3],
+    pub _normal: Mat3x1<f64>,
+    pub _bound: AxisAlignedBBox,
+    pub _vicinity: f64,
+}
+
+impl Triangle3 {
+    pub fn init(verts: &[f64]) -> Triangle3 {
+        assert!(verts.len() == 9);
+        let v = [
+            Mat3x1 {
+                _val: [verts[0], verts[1], verts[2]],
+            },
+            Mat3x1 {
+                _val: [verts[3], verts[4], verts[5]],
+            },
+            Mat3x1 {
+                _val: [verts[6], verts[7], verts[8]],
+            },
+        ];
+        let n = v[1]
+            .minus(&v[0])
+            .unwrap()
+            .cross(&v[2].minus(&v[0]).unwrap())
+            .unwrap();
+        let m = n.magnitude().unwrap();
+        Triangle3 {
+            _verts: v,
+            //degenerate triangles keep a zero normal
+            _normal: if m > 0f64 {
+                n.scale(1f64 / m).unwrap()
+            } else {
+                n
+            },
+            _bound: AxisAlignedBBox::init(ShapeType::Trig, verts),
+            _vicinity: 0.000001f64,
+        }
+    }
+
+    ///barycentric weights of the vertices for point p projected onto the plane of the triangle
+    pub fn get_barycentric(&self, p: &Mat3x1<f64>) -> (f64, f64, f64) {
+        intersect_epa::barycentric(p, &self._verts[0], &self._verts[1], &self._verts[2])
+    }
+
+    ///moller-trumbore ray triangle test, returns ray parameter t and barycentric weights u, v of vertices 1 and 2,
+    ///dir is not required to be normalized and t is in multiples of it
+    pub fn intersect_ray(&self, ori: &Mat3x1<f64>, dir: &Mat3x1<f64>) -> Option<(f64, f64, f64)> {
+        let e1 = self._verts[1].minus(&self._verts[0]).unwrap();
+        let e2 = self._verts[2].minus(&self._verts[0]).unwrap();
+        let p = dir.cross(&e2).unwrap();
+        let det = e1.dot(&p).unwrap();
+        if det.abs() <= self._vicinity * self._vicinity {
+            //ray is parallel to the triangle
+            return None;
+        }
+        let inv_det = 1f64 / det;
+        let s = ori.minus(&self._verts[0]).unwrap();
+        let u = s.dot(&p).unwrap() * inv_det;
+        if !(0f64..=1f64).contains(&u) {
+            return None;
+        }
+        let q = s.cross(&e1).unwrap();
+        let v = dir.dot(&q).unwrap() * inv_det;
+        if v < 0f64 || u + v > 1f64 {
+            return None;
+        }
+        let t = e2.dot(&q).unwrap() * inv_det;
+        if t < 0f64 {
+            return None;
+        }
+        Some((t, u, v))
+    }
+
+    ///closest point of the triangle to p
+    pub fn get_closest_point(&self, p: &Mat3x1<f64>) -> Mat3x1<f64> {
+        //closest point to the origin of the triangle moved by -p
+        let verts: Vec<Mat3x1<f64>> = self._verts.iter().map(|x| x.minus(p).unwrap()).collect();
+        let (indices, weights) = intersect_gjk::closest_triangle(&verts, 0, 1, 2);
+        intersect_gjk::combine(&verts, &indices, &weights)
+            .plus(p)
+            .unwrap()
+    }
+
+    fn is_inside(&self, p: &Mat3x1<f64>) -> bool {
+        let (u, v, w) = self.get_barycentric(p);
+        u >= -self._vicinity && v >= -self._vicinity && w >= -self._vicinity
+    }
+}
+
+impl IShape for Triangle3 {
+    fn get_shape_data(&self) -> Vec<f64> {
+        self._verts.iter().flat_map(|x| x._val.to_vec()).collect()
+    }
+    fn get_type(&self) -> ShapeType {
+        ShapeType::Trig
+    }
+    fn get_bound(&self) -> &dyn IBound {
+        &self._bound
+    }
+    // this shall test for intersection of bounding shapes first before procedding to test intersection using algorithms of higher complexity
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError> {
+        if !self.get_bound().intersect(other.get_bound()) {
+            Ok(Default::default())
+        } else {
+            let d = other.get_shape_data();
+            match other.get_type() {
+                ShapeType::Point => {
+                    let p = Mat3x1::<f64>::init_from_slice(&d);
+                    let dist = p
+                        .minus(&self._verts[0])
+                        .unwrap()
+                        .dot(&self._normal)
+                        .unwrap();
+                    if !self.within_vicinity(dist, 0f64) || !self.is_inside(&p) {
+                        return Ok(Default::default());
+                    }
+                    Ok(Intersection::init_point(p))
+                }
+                ShapeType::Ray | ShapeType::Line => {
+                    let ori = Mat3x1::<f64>::init_from_slice(&d[0..3]);
+                    let dir = match other.get_type() {
+                        ShapeType::Ray => Mat3x1::<f64>::init_from_slice(&d[3..6]),
+                        _ => Mat3x1::<f64>::init_from_slice(&d[3..6])
+                            .minus(&ori)
+                            .unwrap(),
+                    };
+                    if dir.magnitude().unwrap() == 0f64 {
+                        return Err(MazthError::DegenerateInput);
+                    }
+                    match self.intersect_ray(&ori, &dir) {
+                        Some((t, _, _)) if other.get_type() == ShapeType::Ray || t <= 1f64 => {
+                            //normal of the triangle facing the ray, seen from the triangle it points away from it
+                            let facing = if self._normal.dot(&dir).unwrap() > 0f64 {
+                                self._normal
+                            } else {
+                                self._normal.scale(-1f64).unwrap()
+                            };
+                            Ok(Intersection {
+                                _is_intersect: true,
+                                _hits: vec![Hit {
+                                    _normal: Some(facing),
+                                    _t: Some(t),
+                                    ..Hit::init(ori.plus(&dir.scale(t).unwrap()).unwrap())
+                                }],
+                                _depth: None,
+                            })
+                        }
+                        _ => Ok(Default::default()),
+                    }
+                }
+                ShapeType::Sphere => {
+                    let c = Mat3x1::<f64>::init_from_slice(&d);
+                    let p = self.get_closest_point(&c);
+                    let v = p.minus(&c).unwrap();
+                    let dist = v.magnitude().unwrap();
+                    if dist > d[3] {
+                        return Ok(Default::default());
+                    }
+                    let normal = if dist > 0f64 {
+                        v.scale(1f64 / dist).unwrap()
+                    } else {
+                        self._normal
+                    };
+                    Ok(Intersection {
+                        _is_intersect: true,
+                        _hits: vec![Hit {
+                            _normal: Some(normal),
+                            ..Hit::init(p)
+                        }],
+                        _depth: Some(d[3] - dist),
+                    })
+                }
+                ShapeType::Plane => {
+                    //plane bounds the half space behind its normal
+                    let ori = Mat3x1::<f64>::init_from_slice(&d[0..3]);
+                    let n = Mat3x1::<f64>::init_from_slice(&d[3..6]);
+                    let (deepest, dist) = self
+                        ._verts
+                        .iter()
+                        .map(|x| (*x, x.minus(&ori).unwrap().dot(&n).unwrap()))
+                        .fold((self._verts[0], f64::INFINITY), |acc, x| {
+                            if x.1 < acc.1 {
+                                x
+                            } else {
+                                acc
+                            }
+                        });
+                    if dist > self._vicinity {
+                        return Ok(Default::default());
+                    }
+                    Ok(Intersection {
+                        _is_intersect: true,
+                        _hits: vec![Hit {
+                            _normal: Some(n),
+                            ..Hit::init(deepest.minus(&n.scale(dist).unwrap()).unwrap())
+                        }],
+                        _depth: Some(-dist),
+                    })
+                }
+                ShapeType::Box | ShapeType::Trig | ShapeType::TriPrism | ShapeType::OrientedBox => {
+                    //degenerate triangles leave no axis to separate along
+                    intersect_sat::query_intersect(self, other)
+                        .map(|x| x.get_intersection())
+                        .ok_or(MazthError::DegenerateInput)
+                }
+                _ => Err(MazthError::UnsupportedPair(
+                    self.get_type(),
+                    other.get_type(),
+                )),
+            }
+        }
+    }
+    fn get_support(&self, v: &Mat3x1<f64>) -> Option<Mat3x1<f64>> {
+        if v.magnitude() != Some(0f64) {
+            let furthest = self
+                ._verts
+                .iter()
+                .map(|x| x.dot(v).unwrap())
+                .enumerate()
+                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+                .unwrap();
+            Some(self._verts[furthest.0])
+        } else {
+            None
+        }
+    }
+}
+
+impl IVicinity<f64> for Triangle3 {
+    fn set_vicinity(&mut self, epsilon: f64) {
+        self._vicinity = epsilon.abs();
+    }
+    fn within_vicinity(&self, a: f64, b: f64) -> bool {
+        a + self._vicinity >= b && a - self._vicinity <= b
+    }
+}
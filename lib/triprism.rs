@@ -4,6 +4,10 @@ use i_shape::{IShape, ShapeType};
 use i_vicinity::IVicinity;
 
 use bound::AxisAlignedBBox;
+use dispatch;
+use error::MazthError;
+use intersect_sat;
+use intersection::{Hit, Intersection};
 use mat::Mat3x1;
 
 use plane::Plane;
@@ -119,6 +123,29 @@ impl TriPrism {
             _vicinity: 0.000001f64,
         }
     }
+
+    ///point and outward unit normal of each facet, bottom and top followed by the sides starting at edge 0-1 of the base,
+    ///returns None for a prism without volume
+    pub fn get_facets(&self) -> Option<[(Mat3x1<f64>, Mat3x1<f64>); 5]> {
+        let n = self._normal_height;
+        let mut facets: [(Mat3x1<f64>, Mat3x1<f64>); 5] = Default::default();
+        facets[0] = (self._tri_base[0], n.scale(-1f64).unwrap());
+        facets[1] = (self._tri_base2[0], n);
+        for i in 0..3 {
+            let e = self._tri_base[(i + 1) % 3]
+                .minus(&self._tri_base[i])
+                .unwrap();
+            facets[2 + i] = (self._tri_base[i], e.cross(&n).unwrap());
+        }
+        for f in facets.iter_mut() {
+            let m = f.1.magnitude().unwrap();
+            if m <= 0f64 || !m.is_finite() {
+                return None;
+            }
+            f.1 = f.1.scale(1f64 / m).unwrap();
+        }
+        Some(facets)
+    }
 }
 
 impl IShape for TriPrism {
@@ -145,9 +172,9 @@ impl IShape for TriPrism {
         &self._bound
     }
     // this shall test for intersection of bounding shapes first before procedding to test intersection using algorithms of higher complexity
-    fn get_intersect(&self, other: &dyn IShape) -> (bool, Option<Mat3x1<f64>>) {
+    fn get_intersection(&self, other: &dyn IShape) -> Result<Intersection, MazthError> {
         if !self.get_bound().intersect(other.get_bound()) {
-            return (false, None);
+            return Ok(Default::default());
         } else {
             match other.get_type() {
                 ShapeType::Point => {
@@ -198,9 +225,9 @@ impl IShape for TriPrism {
                     });
 
                     if is_inside {
-                        (true, Some(other_point))
+                        Ok(Intersection::init_point(other_point))
                     } else {
-                        (false, None)
+                        Ok(Default::default())
                     }
                 }
                 ShapeType::Line => {
@@ -260,16 +287,27 @@ impl IShape for TriPrism {
                         .iter()
                         .all(|(vert, normal)| !(b.minus(vert).unwrap().dot(normal).unwrap() > 0.));
 
+                    let end_point = |p: Mat3x1<f64>, t: f64| Intersection {
+                        _is_intersect: true,
+                        _hits: vec![Hit {
+                            _t: Some(t),
+                            ..Hit::init(p)
+                        }],
+                        _depth: None,
+                    };
                     if a_is_inside {
-                        return (true, Some(a));
+                        return Ok(end_point(a, 0f64));
                     } else if b_is_inside {
-                        return (true, Some(b));
+                        return Ok(end_point(b, 1f64));
                     }
 
                     //continue test using ray plane intersection
 
                     let v = b.minus(&a).unwrap();
                     let mag = v.magnitude().unwrap();
+                    if mag == 0f64 {
+                        return Err(MazthError::DegenerateInput);
+                    }
 
                     let r = Ray3::init(&[a[0], a[1], a[2]], &[v[0], v[1], v[2]]);
 
@@ -336,7 +374,7 @@ impl IShape for TriPrism {
 
                     let mut intersect_point = None;
                     let mut is_inside = false;
-                    for i in facets.iter() {
+                    for (idx, i) in facets.iter().enumerate() {
                         let res = r.get_intersect(i);
                         if res.0 {
                             let collide_point = res.1.unwrap();
@@ -351,21 +389,93 @@ impl IShape for TriPrism {
                                 continue;
                             } else {
                                 is_inside = true;
-                                intersect_point = res.1;
+                                //facet normal pointing from the line towards the prism
+                                intersect_point = Some(Hit {
+                                    _point: collide_point,
+                                    _normal: i._normal.scale(-1f64),
+                                    _t: Some(mag2 / mag),
+                                    _feature: Some(idx),
+                                    _feature_other: None,
+                                });
                                 break;
                             }
                         }
                     }
 
                     if is_inside {
-                        (true, intersect_point)
+                        Ok(Intersection {
+                            _is_intersect: true,
+                            _hits: intersect_point.into_iter().collect(),
+                            _depth: None,
+                        })
                     } else {
-                        (false, None)
+                        Ok(Default::default())
                     }
                 }
-                _ => {
-                    unimplemented!();
+                ShapeType::Ray => {
+                    let d = other.get_shape_data();
+                    let ori = Mat3x1 {
+                        _val: [d[0], d[1], d[2]],
+                    };
+                    let dir = Mat3x1 {
+                        _val: [d[3], d[4], d[5]],
+                    };
+                    let facets = match self.get_facets() {
+                        Some(x) if dir.magnitude().unwrap() > 0f64 => x,
+                        _ => return Err(MazthError::DegenerateInput),
+                    };
+                    //clip the ray against the half space behind each facet
+                    let mut enter = (f64::NEG_INFINITY, None);
+                    let mut exit = (f64::INFINITY, None);
+                    for (idx, (p, n)) in facets.iter().enumerate() {
+                        let dist = ori.minus(p).unwrap().dot(n).unwrap();
+                        let speed = dir.dot(n).unwrap();
+                        if speed == 0f64 {
+                            if dist > 0f64 {
+                                return Ok(Default::default());
+                            }
+                        } else {
+                            let t = -dist / speed;
+                            if speed < 0f64 && t > enter.0 {
+                                enter = (t, Some(idx));
+                            } else if speed > 0f64 && t < exit.0 {
+                                exit = (t, Some(idx));
+                            }
+                        }
+                    }
+                    if enter.0 > exit.0 || exit.0 < 0f64 {
+                        return Ok(Default::default());
+                    }
+                    //entry is skipped if the ray starts inside the prism, normals point along the ray into the prism
+                    let hits = [enter, exit]
+                        .iter()
+                        .filter(|x| x.0 >= 0f64)
+                        .map(|(t, idx)| Hit {
+                            _normal: idx.map(|i| facets[i].1.scale(-1f64).unwrap()),
+                            _t: Some(*t),
+                            _feature: *idx,
+                            ..Hit::init(ori.plus(&dir.scale(*t).unwrap()).unwrap())
+                        })
+                        .collect();
+                    Ok(Intersection {
+                        _is_intersect: true,
+                        _hits: hits,
+                        _depth: None,
+                    })
+                }
+                ShapeType::Sphere => {
+                    //closest point of the prism has no closed form, resolve through support functions
+                    dispatch::query_support(self, other)
+                }
+                ShapeType::Box | ShapeType::TriPrism | ShapeType::OrientedBox | ShapeType::Trig => {
+                    intersect_sat::query_intersect(self, other)
+                        .map(|x| x.get_intersection())
+                        .ok_or(MazthError::DegenerateInput)
                 }
+                _ => Err(MazthError::UnsupportedPair(
+                    self.get_type(),
+                    other.get_type(),
+                )),
             }
         }
     }